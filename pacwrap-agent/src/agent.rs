@@ -19,11 +19,12 @@
 use std::{
     env,
     fs::{self, File},
-    io::ErrorKind::NotFound,
-    os::unix::prelude::FileExt,
+    io::{ErrorKind::NotFound, Read, Write},
+    os::fd::FromRawFd,
 };
 
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 
 use pacwrap_core::{
     config::Global,
@@ -36,13 +37,22 @@ use pacwrap_core::{
             download::{self, DownloadEvent},
             progress::{self, ProgressEvent},
             query,
+            trace,
+        },
+        transaction::{
+            TransactionFlags,
+            TransactionHandle,
+            TransactionMetadata,
+            TransactionParameters,
+            TransactionType,
+            MAGIC_NUMBER,
         },
-        transaction::{TransactionHandle, TransactionMetadata, TransactionParameters, TransactionType, MAGIC_NUMBER},
         utils::{erroneous_preparation, erroneous_transaction},
         AlpmConfigData,
         SyncError,
+        AGENT_VERSION_FD,
     },
-    utils::{bytebuffer::ByteBuffer, print_warning},
+    utils::{bytebuffer::ByteBuffer, print_warning, status, verbosity, warnings},
     Error,
     ErrorGeneric,
     Result,
@@ -51,9 +61,14 @@ use pacwrap_core::{
 use crate::error::AgentError;
 
 const AGENT_PARAMS: &str = "/mnt/agent_params";
+const HEADER_LENGTH: usize = 7;
+const CHECKSUM_LENGTH: usize = 32;
 
 pub fn transact() -> Result<()> {
-    let mut header = ByteBuffer::with_capacity(7).read();
+    verbosity::adopt();
+    report_version();
+
+    let mut payload = Vec::new();
     let mut file = match File::open(AGENT_PARAMS) {
         Ok(file) => file,
         Err(error) => {
@@ -67,16 +82,22 @@ pub fn transact() -> Result<()> {
         }
     };
 
-    file.read_exact_at(header.as_slice_mut(), 0).prepend_io(|| AGENT_PARAMS.into())?;
-    decode_header(&mut header)?;
+    file.read_to_end(&mut payload).prepend_io(|| AGENT_PARAMS.into())?;
 
-    let params: TransactionParameters = deserialize(&mut file)?;
-    let config: Global = deserialize(&mut file)?;
-    let alpm_remotes: AlpmConfigData = deserialize(&mut file)?;
-    let mut metadata: TransactionMetadata = deserialize(&mut file)?;
+    let mut payload = decode_payload(&payload)?;
+    let params: TransactionParameters = deserialize(&mut payload)?;
+    let config: Global = deserialize(&mut payload)?;
+    let alpm_remotes: AlpmConfigData = deserialize(&mut payload)?;
+    let mut metadata: TransactionMetadata = deserialize(&mut payload)?;
     let handle = TransactionHandle::new(&mut metadata);
     let (transflags, ..) = handle.metadata().retrieve_flags();
-    let alpm = sync::instantiate_alpm_agent(&config, &alpm_remotes, &transflags.expect("TransactionFlags"));
+    let alpm = sync::instantiate_alpm_agent(
+        &config,
+        &alpm_remotes,
+        &transflags.expect("TransactionFlags"),
+        params.arch(),
+        params.snapshot(),
+    );
     let mut handle = handle.alpm_handle(alpm).config(&config).agent();
     let mut logger = Logger::new("pacwrap-agent").location("/mnt/share/pacwrap.log")?;
 
@@ -102,6 +123,14 @@ fn conduct_transaction(
     let pkind = config.progress();
     let bytes = agent.bytes();
     let files = agent.files();
+    let rate = agent.download_rate();
+
+    if flags.0.is_some_and(|a| a.contains(TransactionFlags::TRACE)) {
+        trace::instantiate("/mnt/share/trace.log", false)?;
+    }
+
+    status::adopt();
+    warnings::adopt();
 
     if let Err(error) = handle.alpm_mut().trans_init(flags.1.expect("ALPM TransFlag")) {
         err!(SyncError::InitializationFailure(error.to_string()))?
@@ -122,7 +151,11 @@ fn conduct_transaction(
     }
 
     let progress_cb = ProgressEvent::new().style(pkind.0).configure(&action);
-    let download_cb = DownloadEvent::new().style(pkind.1).total(bytes, files).configure(&mode, pkind.1);
+    let download_cb = DownloadEvent::new()
+        .style(pkind.1)
+        .total(bytes, files)
+        .throttle(rate)
+        .configure(&mode, pkind.1);
 
     handle.alpm().set_question_cb((), query::callback);
     handle.alpm().set_progress_cb(progress_cb, progress::callback(&mode, pkind.0));
@@ -147,6 +180,30 @@ fn conduct_transaction(
     Ok(())
 }
 
+/// Report the agent's version to the host, such that the host may state it upon a version mismatch.
+fn report_version() {
+    if let Some(fd) = env::var(AGENT_VERSION_FD).ok().and_then(|fd| fd.parse().ok()) {
+        let _ = writeln!(unsafe { File::from_raw_fd(fd) }, "{}", env!("CARGO_PKG_VERSION"));
+    }
+}
+
+fn decode_payload(payload: &[u8]) -> Result<&[u8]> {
+    if payload.len() < HEADER_LENGTH + CHECKSUM_LENGTH {
+        err!(AgentError::DeserializationError("Parameter payload is truncated.".into()))?
+    }
+
+    let (payload, checksum) = payload.split_at(payload.len() - CHECKSUM_LENGTH);
+    let mut header = ByteBuffer::from(payload[.. HEADER_LENGTH].to_vec()).read();
+
+    decode_header(&mut header)?;
+
+    if Sha256::digest(payload).as_slice() != checksum {
+        err!(AgentError::ChecksumMismatch)?
+    }
+
+    Ok(payload)
+}
+
 fn decode_header(buffer: &mut ByteBuffer) -> Result<()> {
     let magic = buffer.read_le_32();
     let major: (u8, u8) = (*VERSION_MAJOR as u8, buffer.read_byte());
@@ -164,8 +221,8 @@ fn decode_header(buffer: &mut ByteBuffer) -> Result<()> {
     Ok(())
 }
 
-fn deserialize<T: for<'de> Deserialize<'de>>(stdin: &mut File) -> Result<T> {
-    match bincode::deserialize_from::<&mut File, T>(stdin) {
+fn deserialize<T: for<'de> Deserialize<'de>>(payload: &mut &[u8]) -> Result<T> {
+    match bincode::deserialize_from::<&mut &[u8], T>(payload) {
         Ok(meta) => Ok(meta),
         Err(error) => err!(AgentError::DeserializationError(error.as_ref().to_string())),
     }
@@ -17,18 +17,36 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use std::fmt::{Display, Formatter};
+use std::{
+    fmt::{Display, Formatter},
+    process::exit,
+};
+
+use serde::Serialize;
 
 use pacwrap_core::{
     constants::{BOLD, RESET},
+    sync::SyncError,
+    utils::strip_ansi,
+    Error,
     ErrorTrait,
 };
 
+#[derive(Serialize)]
+struct ErrorReport<'a> {
+    kind: String,
+    code: i32,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<&'a SyncError>,
+}
+
 #[derive(Debug)]
 pub enum AgentError {
     DeserializationError(String),
     InvalidVersion(u8, u8, u8, u8, u8, u8),
     InvalidMagic(u32, u32),
+    ChecksumMismatch,
     IOError(&'static str, std::io::ErrorKind),
     DirectExecution,
 }
@@ -38,20 +56,53 @@ impl Display for AgentError {
         match self {
             Self::DirectExecution => write!(fmter, "Direct execution of this binary is unsupported."),
             Self::InvalidMagic(magic, comparator) => write!(fmter, "Magic mismatch {} != {}", magic, comparator),
-            Self::InvalidVersion(a, b, c, d, e, f) => {
-                write!(fmter, "Version mismatch {}.{}.{} != {}.{}.{}", a, b, c, d, e, f)
-            }
+            Self::ChecksumMismatch => write!(fmter, "Checksum mismatch: Parameter payload is corrupt."),
+            Self::InvalidVersion(a, b, c, d, e, f) => write!(
+                fmter,
+                "Version mismatch: {}pacwrap-agent v{a}.{b}.{c}{} is incompatible with {}pacwrap v{d}.{e}.{f}{}.
+Ensure pacwrap and its runtime are upgraded to the same release, then try again.",
+                *BOLD,
+                *RESET,
+                *BOLD,
+                *RESET
+            ),
             Self::DeserializationError(error) => write!(fmter, "Deserilization error: {}", error),
             Self::IOError(file, error) => write!(fmter, "'{}{}{}' {}", *BOLD, file, *RESET, error),
         }
     }
 }
 
+impl<'a> ErrorReport<'a> {
+    fn new(error: &'a Error, code: i32) -> Self {
+        let debug = format!("{:?}", error.kind());
+
+        Self {
+            kind: debug.split(['(', ' ', '{']).next().unwrap_or_default().into(),
+            code,
+            message: strip_ansi(&error.to_string()),
+            detail: error.downcast::<SyncError>().ok(),
+        }
+    }
+}
+
+/// Emit the error as a single-line JSON object upon stderr, in lieu of formatted text, and then exit.
+pub fn report_json(error: &Error) -> ! {
+    let code = error.kind().code();
+
+    match serde_json::to_string(&ErrorReport::new(error, code)) {
+        Ok(json) => eprintln!("{json}"),
+        Err(_) => error.error(),
+    }
+
+    exit(code)
+}
+
 impl ErrorTrait for AgentError {
     fn code(&self) -> i32 {
         match self {
-            Self::InvalidMagic(..) => 6,
-            Self::InvalidVersion(..) => 5,
+            Self::ChecksumMismatch => 7,
+            Self::InvalidVersion(..) => 6,
+            Self::InvalidMagic(..) => 5,
             Self::DeserializationError(..) => 4,
             Self::IOError(..) => 3,
             _ => 2,
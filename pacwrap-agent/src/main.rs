@@ -23,7 +23,7 @@ use pacwrap_core::{
     Error,
 };
 
-use crate::error::AgentError;
+use crate::error::{report_json, AgentError};
 
 mod agent;
 mod error;
@@ -31,12 +31,16 @@ mod error;
 fn main() {
     let arguments = &mut Arguments::new().populate();
     let param = arguments.next().unwrap_or_default();
+    let json = arguments.into_iter().any(|arg| arg == Operand::Long("json"));
     let result = match param {
         Operand::Value("transact") => agent::transact(),
         _ => err!(AgentError::DirectExecution),
     };
 
     if let Err(error) = result {
-        error.error();
+        match json {
+            true => report_json(&error),
+            false => error.error(),
+        }
     }
 }
@@ -18,6 +18,7 @@
  */
 
 use std::{
+    collections::BTreeMap,
     fmt::{Display, Formatter},
     fs::File,
     io::{ErrorKind::NotFound, Write},
@@ -36,7 +37,7 @@ use crate::{
 
 pub use self::{
     cache::ContainerCache,
-    container::{Container, ContainerHandle, ContainerType},
+    container::{Container, ContainerHandle, ContainerType, NetworkMode},
     dbus::Dbus,
     filesystem::{BindError, Filesystem},
     global::{global, Global},
@@ -45,13 +46,21 @@ pub use self::{
 };
 
 pub mod cache;
+pub mod completion;
 pub mod container;
 pub mod dbus;
+pub mod effective;
 pub mod filesystem;
 pub mod global;
+pub mod hooks;
+pub mod include;
 pub mod init;
+pub mod limits;
 pub mod permission;
 pub mod register;
+pub mod schema;
+pub mod template;
+pub mod validate;
 pub mod vars;
 
 #[derive(Debug, Clone)]
@@ -89,11 +98,25 @@ pub fn provide_handle(instance: &str) -> Result<ContainerHandle> {
     handle(vars)
 }
 
-pub fn compose_handle<'a>(instance: &'a str, path: Option<&'a str>) -> Result<ContainerHandle<'a>> {
+pub fn override_handle<'a>(instance: &str, path: &str) -> Result<ContainerHandle<'a>> {
+    let vars = ContainerVariables::new(instance);
+
+    if !Path::new(vars.root()).exists() {
+        err!(ErrorKind::InstanceNotFound(instance.into()))?
+    }
+
+    handle(vars.config(path))
+}
+
+pub fn compose_handle<'a>(instance: &'a str, path: Option<&'a str>, staged: bool) -> Result<ContainerHandle<'a>> {
     let vars = match path {
         Some(path) => ContainerVariables::new(instance).config(path),
         None => ContainerVariables::new(instance),
     };
+    let vars = match staged {
+        true => vars.staged(),
+        false => vars,
+    };
 
     if Path::new(vars.root()).exists() {
         err!(ConfigError::AlreadyExists(instance.into()))?
@@ -102,7 +125,33 @@ pub fn compose_handle<'a>(instance: &'a str, path: Option<&'a str>) -> Result<Co
     Ok(handle(vars)?.stamp().create())
 }
 
-pub fn provide_new_handle<'a>(instance: &'a str, instype: ContainerType, deps: Vec<&'a str>) -> Result<ContainerHandle<'a>> {
+pub fn manifest_handles<'a>(path: &str) -> Result<Vec<ContainerHandle<'a>>> {
+    let file = File::open(path).prepend_io(|| path.into())?;
+    let manifest: BTreeMap<String, Container> = match serde_yaml::from_reader(&file) {
+        Ok(manifest) => manifest,
+        Err(error) => err!(ConfigError::Load(path.into(), error.to_string()))?,
+    };
+    let mut handles = Vec::new();
+
+    for (instance, container) in manifest {
+        let vars = ContainerVariables::new(&instance);
+
+        if Path::new(vars.root()).exists() {
+            err!(ConfigError::AlreadyExists(instance))?
+        }
+
+        handles.push(ContainerHandle::new(container, vars).stamp().create());
+    }
+
+    Ok(handles)
+}
+
+pub fn provide_new_handle<'a>(
+    instance: &'a str,
+    instype: ContainerType,
+    deps: Vec<&'a str>,
+    profile: bool,
+) -> Result<ContainerHandle<'a>> {
     match handle(ContainerVariables::new(instance)) {
         Ok(mut handle) => {
             handle.metadata_mut().set_metadata(deps, vec![]);
@@ -110,7 +159,10 @@ pub fn provide_new_handle<'a>(instance: &'a str, instype: ContainerType, deps: V
         }
         Err(err) => {
             if let Ok(ConfigError::ConfigNotFound(..)) = err.downcast::<ConfigError>() {
-                let cfg = Container::new(instype, deps, vec![]);
+                let cfg = match profile {
+                    true => Container::new(instype, deps, vec![]).profile(global()?.profile())?,
+                    false => Container::new(instype, deps, vec![]),
+                };
                 let vars = ContainerVariables::new(instance);
 
                 return Ok(ContainerHandle::new(cfg, vars).create());
@@ -139,8 +191,13 @@ fn handle<'a>(vars: ContainerVariables) -> Result<ContainerHandle<'a>> {
                 Ok(file) => file,
                 Err(error) => err!(ConfigError::Load(vars.instance().into(), error.to_string()))?,
             };
+            let (config, included) = include::merge(vars.instance(), config)?;
+            let config = match serde_yaml::from_value(config) {
+                Ok(config) => config,
+                Err(error) => err!(ConfigError::Load(vars.instance().into(), error.to_string()))?,
+            };
 
-            Ok(ContainerHandle::new(config, vars))
+            Ok(ContainerHandle::new(config, vars).included(included))
         }
         Err(error) => match error.kind() {
             NotFound => err!(ConfigError::ConfigNotFound(vars.config_path().into()))?,
@@ -22,7 +22,7 @@ use std::{fs::read_dir, path::Path, result::Result as StdResult};
 use indexmap::IndexMap;
 
 use crate::{
-    config::{provide_handle, provide_new_handle, ConfigError, ContainerHandle, ContainerType},
+    config::{completion, provide_handle, provide_new_handle, ConfigError, ContainerHandle, ContainerType},
     constants::{CONFIG_DIR, CONTAINER_DIR},
     err,
     error::*,
@@ -48,7 +48,7 @@ impl<'a> ContainerCache<'a> {
         }
     }
 
-    pub fn add(&mut self, ins: &'a str, instype: ContainerType, deps: Vec<&'a str>) -> Result<()> {
+    pub fn add(&mut self, ins: &'a str, instype: ContainerType, deps: Vec<&'a str>, profile: bool) -> Result<()> {
         if self.instances.get(ins).is_some() {
             err!(ConfigError::AlreadyExists(ins.into()))?
         }
@@ -59,7 +59,7 @@ impl<'a> ContainerCache<'a> {
             }
         }
 
-        self.register(ins, provide_new_handle(ins, instype, deps.to_vec())?);
+        self.register(ins, provide_new_handle(ins, instype, deps.to_vec(), profile)?);
         Ok(())
     }
 
@@ -107,6 +107,22 @@ impl<'a> ContainerCache<'a> {
         self.instances.iter().map(|a| a.1).collect()
     }
 
+    pub fn downstream(&'a self, ins: &str) -> Vec<&'a str> {
+        self.instances
+            .iter()
+            .filter(|a| !matches!(a.1.metadata().container_type(), ContainerType::Symbolic))
+            .filter(|a| self.depends_upon(a.1, ins, 0))
+            .map(|a| *a.0)
+            .collect()
+    }
+
+    fn depends_upon(&'a self, handle: &'a ContainerHandle<'a>, ins: &str, depth: usize) -> bool {
+        depth < self.instances.len()
+            && handle.metadata().dependencies().iter().any(|dep| {
+                *dep == ins || self.instances.get(dep).is_some_and(|dep| self.depends_upon(dep, ins, depth + 1))
+            })
+    }
+
     pub fn filter_target(&'a self, target: &[&'a str], filter: Vec<ContainerType>) -> Vec<&'a str> {
         self.instances
             .iter()
@@ -135,6 +151,16 @@ impl<'a> ContainerCache<'a> {
             .collect()
     }
 
+    /// Containers tagged with the specified tag, excluding symbolic containers, which cannot be transacted.
+    pub fn tagged(&self, tag: &str) -> Vec<&'a str> {
+        self.instances
+            .iter()
+            .filter(|a| a.1.metadata().container_type() != &ContainerType::Symbolic)
+            .filter(|a| a.1.metadata().tags().contains(&tag))
+            .map(|a| *a.0)
+            .collect()
+    }
+
     pub fn filter_handle(&'a self, filter: Vec<ContainerType>) -> Vec<&'a ContainerHandle<'a>> {
         self.instances
             .iter()
@@ -157,6 +183,13 @@ impl<'a> ContainerCache<'a> {
         }
     }
 
+    pub fn get_instance_mut(&mut self, ins: &str) -> Result<&mut ContainerHandle<'a>> {
+        match self.instances.get_mut(ins) {
+            Some(ins) => Ok(ins),
+            None => err!(ErrorKind::InstanceNotFound(ins.into())),
+        }
+    }
+
     pub fn get_instance_option(&self, ins: &str) -> Option<&ContainerHandle> {
         self.instances.get(ins)
     }
@@ -183,14 +216,23 @@ pub fn populate_config_from<'a>(vec: &Vec<&'a str>) -> Result<ContainerCache<'a>
 }
 
 pub fn populate<'a>() -> Result<ContainerCache<'a>> {
-    populate_from(
+    let cache = populate_from(
         &read_dir(*CONTAINER_DIR)
             .prepend_io(|| CONTAINER_DIR.to_string())?
             .filter_map(StdResult::ok)
             .filter(|e| e.metadata().is_ok_and(|f| f.is_dir() || f.is_symlink()))
             .filter_map(|e| e.file_name().to_str().map(|f| f.to_string().leak() as &'a str))
+            .filter(|e| !e.starts_with('.'))
             .collect::<Vec<&str>>(),
-    )
+    )?;
+
+    if completion::enabled() {
+        if let Err(error) = completion::refresh(&cache, false) {
+            error.warn();
+        }
+    }
+
+    Ok(cache)
 }
 
 pub fn populate_config<'a>() -> Result<ContainerCache<'a>> {
@@ -0,0 +1,108 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+    fs::{self, create_dir_all, read_dir, remove_file, rename},
+    path::Path,
+    result::Result as StdResult,
+    time::SystemTime,
+};
+
+use crate::{config::ContainerCache, constants::{CACHE_DIR, CONFIG_DIR}, ErrorGeneric, Result};
+
+pub fn directory() -> String {
+    format!("{}/completion", *CACHE_DIR)
+}
+
+pub fn enabled() -> bool {
+    Path::new(&directory()).is_dir()
+}
+
+/// Precompute the name lists read directly by shell completion scripts, in lieu of invoking pacwrap.
+///
+/// The container list is regenerated whenever it is older than the container configuration directory,
+/// and each package list whenever it is older than the respective container's local package database.
+/// Maintenance of these lists is engaged only once the cache directory exists.
+pub fn refresh(cache: &ContainerCache, force: bool) -> Result<()> {
+    let directory = directory();
+    let packages = format!("{directory}/packages");
+    let containers = format!("{directory}/containers");
+    let config = format!("{}/container", *CONFIG_DIR);
+
+    create_dir_all(&packages).prepend_io(|| packages.clone())?;
+
+    if force || is_stale(&containers, &config) {
+        write(&containers, cache.registered().iter().map(|a| a.to_string()).collect())?;
+
+        for entry in read_dir(&packages).prepend_io(|| packages.clone())?.filter_map(StdResult::ok) {
+            if entry.file_name().to_str().is_some_and(|a| cache.get_instance_option(a).is_none()) {
+                remove_file(entry.path()).prepend_io(|| entry.path().to_string_lossy().into())?;
+            }
+        }
+    }
+
+    for handle in cache.registered_handles() {
+        let path = format!("{packages}/{}", handle.vars().instance());
+        let localdb = format!("{}/var/lib/pacman/local", handle.vars().root());
+
+        if force || is_stale(&path, &localdb) {
+            write(&path, package_names(&localdb))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn package_names(localdb: &str) -> Vec<String> {
+    let mut names: Vec<String> = match read_dir(localdb) {
+        Ok(dir) => dir
+            .filter_map(StdResult::ok)
+            .filter(|e| e.metadata().is_ok_and(|f| f.is_dir()))
+            .filter_map(|e| e.file_name().to_str().and_then(|f| f.rsplitn(3, '-').nth(2).map(|a| a.to_string())))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    names.sort();
+    names
+}
+
+fn is_stale(path: &str, reference: &str) -> bool {
+    match (modified(path), modified(reference)) {
+        (Some(cached), Some(reference)) => cached < reference,
+        (Some(_), None) => false,
+        (None, _) => true,
+    }
+}
+
+fn modified(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn write(path: &str, lines: Vec<String>) -> Result<()> {
+    let temporary = format!("{path}.tmp");
+    let mut contents = lines.join("\n");
+
+    if !contents.is_empty() {
+        contents.push('\n');
+    }
+
+    fs::write(&temporary, contents).prepend_io(|| temporary.clone())?;
+    rename(&temporary, path).prepend_io(|| path.into())
+}
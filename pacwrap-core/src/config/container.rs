@@ -24,17 +24,28 @@ use std::{
     vec::Vec,
 };
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
 
 use crate::{
     config::{
         dbus::Dbus,
         filesystem::{home::Home, root::Root, Filesystem},
+        global::Profile,
+        hooks::ContainerHooks,
+        limits::ContainerLimits,
+        template::TemplateLock,
+        include,
         permission::{none::None, Permission},
         save,
         vars::ContainerVariables,
+        ConfigError,
     },
-    constants::UNIX_TIMESTAMP,
+    constants::{CONFIG_FILE, UNIX_TIMESTAMP},
+    err,
+    sync::filesystem::disk_usage,
+    Error,
     Result,
 };
 
@@ -47,7 +58,7 @@ pub struct Container<'a> {
     runtime: ContainerRuntime,
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Clone, JsonSchema)]
 pub struct ContainerShadow<'a> {
     #[serde(flatten)]
     metadata: ContainerMetadata<'a>,
@@ -77,6 +88,21 @@ impl<'a> Container<'a> {
             runtime: ContainerRuntime::new(),
         }
     }
+
+    /// Shallow-merge the default profile, such that each module specified by the profile is appended to the
+    /// container's own, unless the container already engages a module of the same kind.
+    pub fn profile(mut self, profile: &Profile) -> Result<Self> {
+        let filesystems: Vec<Box<dyn Filesystem>> = modules(profile.filesystems())?;
+        let permissions: Vec<Box<dyn Permission>> = modules(profile.permissions())?;
+        let dbus: Vec<Box<dyn Dbus>> = modules(profile.dbus())?;
+        let fs_modules: Vec<&'static str> = self.runtime.filesystems.iter().map(|a| a.module()).collect();
+        let per_modules: Vec<&'static str> = self.runtime.permissions.iter().map(|a| a.module()).collect();
+
+        self.runtime.filesystems.extend(filesystems.into_iter().filter(|a| !fs_modules.contains(&a.module())));
+        self.runtime.permissions.extend(permissions.into_iter().filter(|a| !per_modules.contains(&a.module())));
+        self.runtime.dbus.extend(dbus);
+        Ok(self)
+    }
 }
 
 #[derive(Clone)]
@@ -84,6 +110,7 @@ pub struct ContainerHandle<'a> {
     inner: Container<'a>,
     meta: ContainerVariables,
     creation: bool,
+    included: [usize; 3],
 }
 
 impl<'a> ContainerHandle<'a> {
@@ -92,6 +119,7 @@ impl<'a> ContainerHandle<'a> {
             inner: ins,
             meta: ins_vars,
             creation: false,
+            included: [0; 3],
         }
     }
 
@@ -100,9 +128,15 @@ impl<'a> ContainerHandle<'a> {
             inner: container.inner.clone(),
             meta: container_vars,
             creation: false,
+            included: container.included,
         }
     }
 
+    pub fn included(mut self, included: [usize; 3]) -> Self {
+        self.included = included;
+        self
+    }
+
     pub fn create(mut self) -> Self {
         self.creation = true;
         self
@@ -117,6 +151,13 @@ impl<'a> ContainerHandle<'a> {
         &self.inner.runtime
     }
 
+    /// Adopt the runtime configuration of another container, alongside its included entries, retaining
+    /// this container's own metadata.
+    pub fn copy_config(&mut self, from: &ContainerHandle) {
+        self.inner.runtime = from.inner.runtime.clone();
+        self.included = from.included;
+    }
+
     pub fn metadata_mut(&mut self) -> &mut ContainerMetadata<'a> {
         &mut self.inner.metadata
     }
@@ -130,7 +171,12 @@ impl<'a> ContainerHandle<'a> {
     }
 
     pub fn default_vars(mut self) -> Self {
-        self.meta = ContainerVariables::new(self.meta.instance());
+        let vars = ContainerVariables::new(self.meta.instance());
+
+        self.meta = match self.meta.is_staged() {
+            true => vars.staged(),
+            false => vars,
+        };
         self
     }
 
@@ -138,8 +184,18 @@ impl<'a> ContainerHandle<'a> {
         &self.meta
     }
 
+    /// Measure the disk usage of the container root, returning its unique size on disk and apparent total.
+    pub fn disk_usage(&self) -> Result<(u64, u64)> {
+        disk_usage(self.meta.root())
+    }
+
     pub fn save(&self) -> Result<()> {
-        save(&self.inner, self.meta.config_path())
+        let path = self.meta.config_path();
+
+        match self.included.iter().any(|a| *a > 0) {
+            true => save(&include::strip(&self.inner, &self.included, path)?, path),
+            false => save(&self.inner, path),
+        }
     }
 }
 
@@ -150,7 +206,7 @@ impl Debug for ContainerHandle<'_> {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub struct ContainerRuntime {
     #[serde(default)]
     enable_userns: bool,
@@ -160,12 +216,42 @@ pub struct ContainerRuntime {
     allow_forking: bool,
     #[serde(default = "default_true")]
     seccomp: bool,
+    #[serde(default)]
+    network: NetworkMode,
+    #[serde(default = "default_true")]
+    bind_resolv: bool,
+    #[serde(skip_serializing_if = "is_false", default)]
+    bind_fontconfig: bool,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     filesystems: Vec<Box<dyn Filesystem>>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     permissions: Vec<Box<dyn Permission>>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     dbus: Vec<Box<dyn Dbus>>,
+    #[serde(skip_serializing_if = "ContainerHooks::is_empty", default)]
+    hooks: ContainerHooks,
+    #[serde(skip_serializing_if = "ContainerLimits::is_empty", default)]
+    limits: ContainerLimits,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    quota: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    tmp_size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    template_lock: Option<TemplateLock>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    exclude_paths: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    alpm_hook_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    architecture: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    snapshot_date: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    include: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    locales: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    build_dir: Option<String>,
 }
 
 impl Default for ContainerRuntime {
@@ -184,9 +270,24 @@ impl ContainerRuntime {
             allow_forking: false,
             retain_session: false,
             enable_userns: false,
+            network: NetworkMode::None,
+            bind_resolv: true,
+            bind_fontconfig: false,
             permissions: Vec::from(default_per),
             dbus: Vec::new(),
             filesystems: Vec::from(default_fs),
+            hooks: ContainerHooks::default(),
+            limits: ContainerLimits::default(),
+            quota: Option::None,
+            tmp_size: Option::None,
+            template_lock: Option::None,
+            exclude_paths: Vec::new(),
+            locales: Vec::new(),
+            build_dir: Option::None,
+            alpm_hook_dir: Option::None,
+            architecture: Option::None,
+            snapshot_date: Option::None,
+            include: Vec::new(),
         }
     }
 
@@ -202,6 +303,14 @@ impl ContainerRuntime {
         &self.dbus
     }
 
+    pub fn hooks(&self) -> &ContainerHooks {
+        &self.hooks
+    }
+
+    pub fn limits(&self) -> &ContainerLimits {
+        &self.limits
+    }
+
     pub fn allow_forking(&self) -> &bool {
         &self.allow_forking
     }
@@ -217,6 +326,59 @@ impl ContainerRuntime {
     pub fn seccomp(&self) -> &bool {
         &self.seccomp
     }
+
+    pub fn network(&self) -> &NetworkMode {
+        &self.network
+    }
+
+    pub fn bind_resolv(&self) -> &bool {
+        &self.bind_resolv
+    }
+
+    pub fn bind_fontconfig(&self) -> &bool {
+        &self.bind_fontconfig
+    }
+
+    pub fn quota(&self) -> Option<&str> {
+        self.quota.as_deref()
+    }
+
+    pub fn tmp_size(&self) -> Option<&str> {
+        self.tmp_size.as_deref()
+    }
+
+    pub fn template_lock(&self) -> Option<&TemplateLock> {
+        self.template_lock.as_ref()
+    }
+
+    pub fn exclude_paths(&self) -> &Vec<String> {
+        &self.exclude_paths
+    }
+
+    pub fn alpm_hook_dir(&self) -> Option<&str> {
+        self.alpm_hook_dir.as_deref()
+    }
+
+    pub fn architecture(&self) -> Option<&str> {
+        self.architecture.as_deref()
+    }
+
+    pub fn snapshot_date(&self) -> Option<&str> {
+        self.snapshot_date.as_deref()
+    }
+
+    pub fn locales(&self) -> &Vec<String> {
+        &self.locales
+    }
+
+    pub fn build_dir(&self) -> Option<&str> {
+        self.build_dir.as_deref()
+    }
+
+    /// Name of the first configured locale, exported as `LANG` within the container.
+    pub fn locale(&self) -> Option<&str> {
+        self.locales.first().and_then(|a| a.split_whitespace().next())
+    }
 }
 
 impl Debug for ContainerRuntime {
@@ -224,11 +386,37 @@ impl Debug for ContainerRuntime {
         writeln!(fmter, "allow_forking:       {}", self.allow_forking)?;
         writeln!(fmter, "retain_session:      {}", self.retain_session)?;
         writeln!(fmter, "enable_userns:       {}", self.enable_userns)?;
-        writeln!(fmter, "seccomp:             {}", self.seccomp)
+        writeln!(fmter, "seccomp:             {}", self.seccomp)?;
+        writeln!(fmter, "network:             {}", self.network)?;
+        writeln!(fmter, "bind_resolv:         {}", self.bind_resolv)?;
+        writeln!(fmter, "bind_fontconfig:     {}", self.bind_fontconfig)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkMode {
+    Host,
+    #[default]
+    None,
+}
+
+impl NetworkMode {
+    fn as_str<'a>(&self) -> &'a str {
+        match self {
+            Self::Host => "host",
+            Self::None => "none",
+        }
+    }
+}
+
+impl Display for NetworkMode {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        fmt.write_str(self.as_str())
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, JsonSchema)]
 pub enum ContainerType {
     Symbolic,
     Base,
@@ -259,7 +447,7 @@ impl Default for ContainerType {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub struct ContainerMetadata<'a> {
     #[serde(default)]
     container_type: ContainerType,
@@ -269,6 +457,14 @@ pub struct ContainerMetadata<'a> {
     explicit_packages: Vec<Cow<'a, str>>,
     #[serde(default = "time_as_seconds")]
     meta_version: u64,
+    #[serde(skip_serializing_if = "is_false", default)]
+    frozen: bool,
+    #[serde(skip_serializing_if = "is_false", default)]
+    minimal: bool,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    description: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    tags: Vec<Cow<'a, str>>,
 }
 
 impl<'a> ContainerMetadata<'a> {
@@ -278,6 +474,10 @@ impl<'a> ContainerMetadata<'a> {
             dependencies: deps.iter().map(|a| (*a).into()).collect(),
             explicit_packages: pkgs.iter().map(|a| (*a).into()).collect(),
             meta_version: *UNIX_TIMESTAMP,
+            frozen: false,
+            minimal: false,
+            description: Option::None,
+            tags: Vec::new(),
         }
     }
 
@@ -292,6 +492,10 @@ impl<'a> ContainerMetadata<'a> {
         self.meta_version = *UNIX_TIMESTAMP;
     }
 
+    pub fn set_minimal(&mut self, minimal: bool) {
+        self.minimal = minimal;
+    }
+
     pub fn container_type(&self) -> &ContainerType {
         &self.container_type
     }
@@ -307,12 +511,39 @@ impl<'a> ContainerMetadata<'a> {
     pub fn timestamp(&self) -> u64 {
         self.meta_version
     }
+
+    pub fn frozen(&self) -> bool {
+        self.frozen
+    }
+
+    pub fn minimal(&self) -> bool {
+        self.minimal
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn tags(&self) -> Vec<&str> {
+        self.tags.iter().map(|a| a.as_ref()).collect()
+    }
+}
+
+fn modules<T: for<'de> Deserialize<'de>>(entries: &[Value]) -> Result<Vec<T>> {
+    match serde_yaml::from_value(Value::Sequence(entries.to_vec())) {
+        Ok(modules) => Ok(modules),
+        Err(error) => err!(ConfigError::Load(CONFIG_FILE.to_string(), error.to_string())),
+    }
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
 fn time_as_seconds() -> u64 {
     *UNIX_TIMESTAMP
 }
@@ -17,9 +17,10 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::exec::args::ExecutionArgs;
+use crate::{config::schema::modules, exec::args::ExecutionArgs};
 
 use dyn_clone::{clone_trait_object, DynClone};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 
 mod appindicator;
 mod socket;
@@ -31,3 +32,20 @@ pub trait Dbus: DynClone {
 }
 
 clone_trait_object!(Dbus);
+
+impl JsonSchema for dyn Dbus {
+    fn schema_name() -> String {
+        "Dbus".into()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        modules(
+            "module",
+            vec![
+                ("socket", socket::Socket::json_schema(gen)),
+                ("appindicator", appindicator::AppIndicator::json_schema(gen)),
+                ("xdg_portal", xdg_portal::XdgPortal::json_schema(gen)),
+            ],
+        )
+    }
+}
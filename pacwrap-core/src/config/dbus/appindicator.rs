@@ -1,9 +1,10 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{config::Dbus, exec::args::ExecutionArgs};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct AppIndicator;
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AppIndicator;
 
 #[typetag::serde(name = "appindicator")]
 impl Dbus for AppIndicator {
@@ -1,9 +1,10 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{config::Dbus, exec::args::ExecutionArgs};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Socket {
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Socket {
     policy: String,
     address: Vec<String>,
 }
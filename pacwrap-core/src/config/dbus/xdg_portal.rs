@@ -1,11 +1,12 @@
 #![allow(non_camel_case_types)]
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{config::Dbus, exec::args::ExecutionArgs};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct XdgPortal;
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct XdgPortal;
 
 #[typetag::serde(name = "xdg_portal")]
 impl Dbus for XdgPortal {
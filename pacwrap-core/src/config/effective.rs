@@ -0,0 +1,153 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{env::var, fs::File, io::ErrorKind::NotFound};
+
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+
+use crate::{
+    config::{global, include, ConfigError, ContainerHandle},
+    constants::CONFIG_FILE,
+    err,
+    Error,
+    ErrorKind,
+    Result,
+};
+
+const DEFAULT: &str = "default";
+const BWRAP_VAR: &str = "PACWRAP_BWRAP";
+
+/// Render the resolved global configuration as YAML, with each value annotated by its origin.
+pub fn global_config() -> Result<String> {
+    let raw = raw(&CONFIG_FILE)?;
+    let env = var(BWRAP_VAR).ok();
+    let mut value = to_value(global()?, &CONFIG_FILE)?;
+
+    if let (Some(exec), Some(Value::Mapping(config))) = (&env, value.get_mut("config")) {
+        config.insert("bwrap_executable".into(), exec.as_str().into());
+    }
+
+    let origin = |path: &[&str], _: Option<usize>| match path {
+        ["config", "bwrap_executable"] if env.is_some() => format!("${BWRAP_VAR}"),
+        _ => source(&raw, path, &CONFIG_FILE),
+    };
+
+    Ok(render(&value, &origin))
+}
+
+/// Render the resolved configuration of a container as YAML, with each value annotated by its origin,
+/// including entries merged from fragments specified by the `include` directive.
+pub fn container_config(handle: &ContainerHandle) -> Result<String> {
+    let path = handle.vars().config_path();
+    let raw = raw(path)?;
+    let origins = include::origins(handle.vars().instance(), &raw)?;
+    let mut value = to_value(handle.metadata(), path)?;
+
+    if let (Value::Mapping(map), Value::Mapping(runtime)) = (&mut value, to_value(handle.config(), path)?) {
+        map.extend(runtime);
+    }
+
+    let origin = |keys: &[&str], item: Option<usize>| {
+        let included = include::INCLUDE_KEYS.iter().position(|a| Some(a) == keys.first());
+
+        match (included, item) {
+            (Some(idx), Some(item)) if item < origins[idx].len() => origins[idx][item].clone(),
+            _ => source(&raw, keys, path),
+        }
+    };
+
+    Ok(render(&value, &origin))
+}
+
+fn raw(path: &str) -> Result<Value> {
+    match File::open(path) {
+        Ok(file) => match serde_yaml::from_reader(file) {
+            Ok(value) => Ok(value),
+            Err(error) => err!(ConfigError::Load(path.into(), error.to_string())),
+        },
+        Err(error) if error.kind() == NotFound => Ok(Value::Null),
+        Err(error) => err!(ErrorKind::IOError(path.into(), error.kind())),
+    }
+}
+
+fn to_value<T: Serialize>(config: &T, path: &str) -> Result<Value> {
+    match serde_yaml::to_value(config) {
+        Ok(value) => Ok(value),
+        Err(error) => err!(ConfigError::Load(path.into(), error.to_string())),
+    }
+}
+
+fn source(raw: &Value, keys: &[&str], path: &str) -> String {
+    match keys.iter().try_fold(raw, |value, key| value.get(key)) {
+        Some(_) => path.into(),
+        None => DEFAULT.into(),
+    }
+}
+
+fn render(value: &Value, origin: &dyn Fn(&[&str], Option<usize>) -> String) -> String {
+    let mut out = String::new();
+
+    if let Value::Mapping(map) = value {
+        mapping(&mut out, map, &mut Vec::new(), origin);
+    }
+
+    out
+}
+
+fn mapping<'a>(out: &mut String, map: &'a Mapping, keys: &mut Vec<&'a str>, origin: &dyn Fn(&[&str], Option<usize>) -> String) {
+    let pad = "  ".repeat(keys.len());
+
+    for (key, value) in map {
+        let Some(key) = key.as_str() else {
+            continue;
+        };
+
+        keys.push(key);
+
+        match value {
+            Value::Mapping(map) if !map.is_empty() => {
+                out.push_str(&format!("{pad}{key}:\n"));
+                mapping(out, map, keys, origin);
+            }
+            Value::Sequence(seq) if !seq.is_empty() => {
+                out.push_str(&format!("{pad}{key}:\n"));
+
+                for (idx, item) in seq.iter().enumerate() {
+                    out.push_str(&format!("{pad}- {}  # {}\n", flow(item), origin(keys, Some(idx))));
+                }
+            }
+            _ => out.push_str(&format!("{pad}{key}: {}  # {}\n", flow(value), origin(keys, None))),
+        }
+
+        keys.pop();
+    }
+}
+
+fn flow(value: &Value) -> String {
+    match value {
+        Value::Mapping(map) => format!(
+            "{{{}}}",
+            map.iter().map(|(key, value)| format!("{}: {}", flow(key), flow(value))).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Sequence(seq) => format!("[{}]", seq.iter().map(flow).collect::<Vec<_>>().join(", ")),
+        Value::Tagged(tagged) => flow(&tagged.value),
+        value => serde_yaml::to_string(value).map(|a| a.trim_end().to_string()).unwrap_or_default(),
+    }
+}
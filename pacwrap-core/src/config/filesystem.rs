@@ -20,8 +20,12 @@
 use std::fmt::{Display, Formatter};
 
 use dyn_clone::{clone_trait_object, DynClone};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 
-use crate::{config::ContainerVariables, exec::args::ExecutionArgs};
+use crate::{
+    config::{schema::modules, ContainerVariables},
+    exec::args::ExecutionArgs,
+};
 
 mod dir;
 pub mod home;
@@ -60,6 +64,26 @@ pub trait Filesystem: DynClone {
 
 clone_trait_object!(Filesystem);
 
+impl JsonSchema for dyn Filesystem {
+    fn schema_name() -> String {
+        "Filesystem".into()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        modules(
+            "mount",
+            vec![
+                ("root", root::Root::json_schema(gen)),
+                ("home", home::Home::json_schema(gen)),
+                ("sysfs", sys::System::json_schema(gen)),
+                ("dir", dir::Dir::json_schema(gen)),
+                ("to_home", to_home::ToHome::json_schema(gen)),
+                ("to_root", to_root::ToRoot::json_schema(gen)),
+            ],
+        )
+    }
+}
+
 fn default_permission() -> String {
     "ro".into()
 }
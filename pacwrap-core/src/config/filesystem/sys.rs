@@ -18,6 +18,7 @@
  */
 use std::path::Path;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -28,8 +29,8 @@ use crate::{
     exec::args::ExecutionArgs,
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct System {
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct System {
     #[serde(skip_serializing_if = "is_default_path", default = "default_path")]
     path: Vec<String>,
 }
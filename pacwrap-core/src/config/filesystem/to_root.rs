@@ -18,6 +18,7 @@
  */
 use std::path::Path;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -28,13 +29,13 @@ use crate::{
     exec::args::ExecutionArgs,
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ToRoot {
     #[serde(skip_serializing_if = "Vec::is_empty", default, rename = "volumes")]
     mounts: Vec<Mount>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 struct Mount {
     #[serde(skip_serializing_if = "is_default_permission", default = "default_permission")]
     permission: String,
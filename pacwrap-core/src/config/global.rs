@@ -17,12 +17,14 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use std::sync::OnceLock;
+use std::{collections::HashMap, sync::OnceLock};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
 
 use crate::{
-    config::{load_config, save},
+    config::{load_config, save, Dbus, Filesystem, Permission},
     constants::CONFIG_FILE,
     sync::event::summary::SummaryKind,
     Result,
@@ -30,14 +32,14 @@ use crate::{
 
 static CONFIG: OnceLock<Global> = OnceLock::new();
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub enum Verbosity {
     None,
     Basic,
     Verbose,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub enum ProgressKind {
     Simple,
     Condensed,
@@ -58,15 +60,32 @@ impl Default for ProgressKind {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Global {
     #[serde(default = "Configuration::new")]
     config: Configuration,
     #[serde(default = "AlpmConfiguration::new")]
     alpm: AlpmConfiguration,
+    #[serde(skip_serializing_if = "Profile::is_empty", default)]
+    profile: Profile,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+/// Permissions, filesystems, and dbus modules inherited by containers upon their creation. Entries are retained
+/// verbatim, given that modules are neither `Send` nor `Sync`, and are deserialized upon being inherited.
+#[derive(Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct Profile {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    #[schemars(with = "Vec<Box<dyn Filesystem>>")]
+    filesystems: Vec<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    #[schemars(with = "Vec<Box<dyn Permission>>")]
+    permissions: Vec<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    #[schemars(with = "Vec<Box<dyn Dbus>>")]
+    dbus: Vec<Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Progress {
     #[serde(default = "ProgressKind::default")]
     transact: ProgressKind,
@@ -74,7 +93,7 @@ pub struct Progress {
     download: ProgressKind,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Configuration {
     #[serde(default = "SummaryKind::default")]
     summary: SummaryKind,
@@ -82,9 +101,17 @@ pub struct Configuration {
     logging: Verbosity,
     #[serde(default = "Progress::new")]
     progress: Progress,
+    #[serde(default = "dbus_timeout")]
+    dbus_timeout: u64,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    bwrap_executable: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    editor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    home_skeleton: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub struct AlpmConfiguration {
     #[serde(default = "ignore_pkg")]
     ignore_pkg: Vec<String>,
@@ -96,12 +123,16 @@ pub struct AlpmConfiguration {
     sig_level_local: String,
     #[serde(default = "parallel_downloads")]
     parallel_downloads: u32,
+    #[serde(default)]
+    max_download_rate: u64,
     #[serde(default = "default_true")]
     check_space: bool,
     #[serde(default = "default_true")]
     download_timeout: bool,
     #[serde(default)]
     disable_sandbox: bool,
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    repo_cache: HashMap<String, String>,
 }
 
 impl Configuration {
@@ -110,6 +141,10 @@ impl Configuration {
             summary: SummaryKind::Basic,
             logging: Verbosity::Basic,
             progress: Progress::new(),
+            dbus_timeout: dbus_timeout(),
+            bwrap_executable: None,
+            editor: None,
+            home_skeleton: None,
         }
     }
 
@@ -124,6 +159,22 @@ impl Configuration {
     pub fn summary(&self) -> &SummaryKind {
         &self.summary
     }
+
+    pub fn dbus_timeout(&self) -> u64 {
+        self.dbus_timeout
+    }
+
+    pub fn bwrap_executable(&self) -> Option<&str> {
+        self.bwrap_executable.as_deref()
+    }
+
+    pub fn editor(&self) -> Option<&str> {
+        self.editor.as_deref()
+    }
+
+    pub fn home_skeleton(&self) -> Option<&str> {
+        self.home_skeleton.as_deref()
+    }
 }
 
 impl Progress {
@@ -135,6 +186,24 @@ impl Progress {
     }
 }
 
+impl Profile {
+    pub fn is_empty(&self) -> bool {
+        self.filesystems.is_empty() && self.permissions.is_empty() && self.dbus.is_empty()
+    }
+
+    pub fn filesystems(&self) -> &Vec<Value> {
+        &self.filesystems
+    }
+
+    pub fn permissions(&self) -> &Vec<Value> {
+        &self.permissions
+    }
+
+    pub fn dbus(&self) -> &Vec<Value> {
+        &self.dbus
+    }
+}
+
 impl AlpmConfiguration {
     fn new() -> Self {
         Self {
@@ -143,9 +212,11 @@ impl AlpmConfiguration {
             sig_level: sig_level(),
             sig_level_local: sig_level_opt(),
             parallel_downloads: parallel_downloads(),
+            max_download_rate: 0,
             check_space: true,
             download_timeout: true,
             disable_sandbox: false,
+            repo_cache: HashMap::new(),
         }
     }
 
@@ -153,6 +224,10 @@ impl AlpmConfiguration {
         self.sig_level.split(" ").map(|a| a.into()).collect()
     }
 
+    pub fn sig_level_local(&self) -> Vec<String> {
+        self.sig_level_local.split(" ").map(|a| a.into()).collect()
+    }
+
     pub fn download_timeout(&self) -> bool {
         !self.download_timeout
     }
@@ -161,6 +236,10 @@ impl AlpmConfiguration {
         self.parallel_downloads
     }
 
+    pub fn max_download_rate(&self) -> u64 {
+        self.max_download_rate
+    }
+
     pub fn check_space(&self) -> bool {
         self.check_space
     }
@@ -169,6 +248,10 @@ impl AlpmConfiguration {
         self.disable_sandbox
     }
 
+    pub fn repo_cache(&self, repo: &str) -> Option<&str> {
+        self.repo_cache.get(repo).map(|a| a.as_str())
+    }
+
     pub fn held(&self) -> Vec<&str> {
         self.hold_pkg.iter().map(|a| a.as_ref()).collect()
     }
@@ -189,6 +272,7 @@ impl Global {
         Self {
             config: Configuration::new(),
             alpm: AlpmConfiguration::new(),
+            profile: Profile::default(),
         }
     }
 
@@ -200,6 +284,10 @@ impl Global {
         &self.alpm
     }
 
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
     pub fn save(&self) -> Result<()> {
         save(&self, &CONFIG_FILE)
     }
@@ -239,6 +327,10 @@ fn parallel_downloads() -> u32 {
     1
 }
 
+fn dbus_timeout() -> u64 {
+    100
+}
+
 fn default_true() -> bool {
     true
 }
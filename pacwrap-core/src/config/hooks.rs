@@ -0,0 +1,64 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct ContainerHooks {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pre_transaction: Vec<Hook>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    post_transaction: Vec<Hook>,
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
+pub struct Hook {
+    exec: String,
+    #[serde(default)]
+    fatal: bool,
+}
+
+impl ContainerHooks {
+    pub fn is_empty(&self) -> bool {
+        self.pre_transaction.is_empty() && self.post_transaction.is_empty()
+    }
+
+    pub fn pre_transaction(&self) -> &Vec<Hook> {
+        &self.pre_transaction
+    }
+
+    pub fn post_transaction(&self) -> &Vec<Hook> {
+        &self.post_transaction
+    }
+}
+
+impl Hook {
+    pub fn exec(&self) -> &str {
+        &self.exec
+    }
+
+    pub fn fatal(&self) -> bool {
+        self.fatal
+    }
+
+    pub fn arguments(&self) -> Vec<&str> {
+        vec!["/bin/sh", "-c", &self.exec]
+    }
+}
@@ -0,0 +1,190 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs::File,
+    io::ErrorKind::NotFound,
+};
+
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+
+use crate::{
+    config::ConfigError,
+    constants::{BOLD, CONFIG_DIR, RESET},
+    err,
+    impl_error,
+    Error,
+    ErrorKind,
+    ErrorTrait,
+    Result,
+};
+
+/// Container configuration keys which may be supplied by an included fragment.
+pub const INCLUDE_KEYS: [&str; 3] = ["filesystems", "permissions", "dbus"];
+
+#[derive(Debug, Clone)]
+pub enum IncludeError {
+    NotFound(String, String),
+    Load(String, String, String),
+    InvalidKey(String, String, String),
+    InvalidDirective(String),
+}
+
+impl_error!(IncludeError);
+
+impl Display for IncludeError {
+    fn fmt(&self, fmter: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::NotFound(ins, path) =>
+                write!(fmter, "Container {}{ins}{}: Included fragment '{path}' not found.", *BOLD, *RESET),
+            Self::Load(ins, path, error) =>
+                write!(fmter, "Container {}{ins}{}: Failed to load included fragment '{path}': {error}", *BOLD, *RESET),
+            Self::InvalidKey(ins, path, key) => write!(
+                fmter,
+                "Container {}{ins}{}: Included fragment '{path}' specifies '{key}': Only {} may be included.",
+                *BOLD,
+                *RESET,
+                INCLUDE_KEYS.join(", ")
+            ),
+            Self::InvalidDirective(ins) =>
+                write!(fmter, "Container {}{ins}{}: 'include' must specify a path or a list of paths.", *BOLD, *RESET),
+        }
+    }
+}
+
+/// Merge the fragments specified by the `include` directive into the container configuration. Fragment entries
+/// are prepended in the order included, such that the container's own entries follow and thereby take precedence.
+///
+/// Returns the merged configuration alongside the count of included entries for each of [`INCLUDE_KEYS`].
+pub fn merge(instance: &str, mut config: Value) -> Result<(Value, [usize; 3])> {
+    let mut included = [0; 3];
+    let paths: Vec<String> = match config.get("include") {
+        Some(Value::String(path)) => vec![path.clone()],
+        Some(Value::Sequence(paths)) => match paths.iter().map(|a| a.as_str().map(|a| a.to_string())).collect() {
+            Some(paths) => paths,
+            None => err!(IncludeError::InvalidDirective(instance.into()))?,
+        },
+        Some(Value::Null) | None => return Ok((config, included)),
+        Some(_) => err!(IncludeError::InvalidDirective(instance.into()))?,
+    };
+    let mut entries = entries(instance, &paths)?.map(|a| a.into_iter().map(|a| a.1).collect::<Vec<_>>());
+
+    if let Some(map) = config.as_mapping_mut() {
+        map.insert("include".into(), Value::Sequence(paths.into_iter().map(Value::String).collect()));
+
+        for (idx, key) in INCLUDE_KEYS.iter().enumerate() {
+            let mut merged = std::mem::take(&mut entries[idx]);
+
+            included[idx] = merged.len();
+
+            if let Some(Value::Sequence(own)) = map.get(key) {
+                merged.extend(own.iter().cloned());
+            }
+
+            if !merged.is_empty() {
+                map.insert((*key).into(), Value::Sequence(merged));
+            }
+        }
+    }
+
+    Ok((config, included))
+}
+
+/// Resolve the fragment path from which each included entry of [`INCLUDE_KEYS`] originates, in merged order.
+pub fn origins(instance: &str, config: &Value) -> Result<[Vec<String>; 3]> {
+    let paths: Vec<String> = match config.get("include") {
+        Some(Value::String(path)) => vec![path.clone()],
+        Some(Value::Sequence(paths)) => paths.iter().filter_map(|a| a.as_str().map(|a| a.to_string())).collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(entries(instance, &paths)?.map(|a| a.into_iter().map(|a| paths[a.0].clone()).collect()))
+}
+
+/// Serialize the container configuration with included entries stripped, so as to preserve the `include`
+/// directive upon the configuration being saved.
+pub fn strip<T: Serialize>(config: &T, included: &[usize; 3], path: &str) -> Result<Value> {
+    let mut value = match serde_yaml::to_value(config) {
+        Ok(value) => value,
+        Err(error) => err!(ConfigError::Save(path.into(), error.to_string()))?,
+    };
+
+    if let Some(map) = value.as_mapping_mut() {
+        for (idx, key) in INCLUDE_KEYS.iter().enumerate() {
+            if let Some(Value::Sequence(seq)) = map.get_mut(key) {
+                seq.drain(.. included[idx].min(seq.len()));
+
+                if seq.is_empty() {
+                    map.remove(key);
+                }
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn entries(instance: &str, paths: &[String]) -> Result<[Vec<(usize, Value)>; 3]> {
+    let mut entries: [Vec<(usize, Value)>; 3] = Default::default();
+
+    for (origin, path) in paths.iter().enumerate() {
+        for (key, value) in fragment(instance, path)? {
+            let idx = match key.as_str().and_then(|key| INCLUDE_KEYS.iter().position(|a| *a == key)) {
+                Some(idx) => idx,
+                None => err!(IncludeError::InvalidKey(instance.into(), path.into(), display(&key)))?,
+            };
+
+            match value {
+                Value::Sequence(seq) => entries[idx].extend(seq.into_iter().map(|a| (origin, a))),
+                Value::Null => continue,
+                _ => err!(IncludeError::Load(instance.into(), path.into(), format!("'{}' must be a list.", INCLUDE_KEYS[idx])))?,
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+fn fragment(instance: &str, path: &str) -> Result<Mapping> {
+    let resolved = match path.starts_with('/') {
+        true => path.to_string(),
+        false => format!("{}/{path}", *CONFIG_DIR),
+    };
+    let file = match File::open(&resolved) {
+        Ok(file) => file,
+        Err(error) if error.kind() == NotFound => err!(IncludeError::NotFound(instance.into(), resolved.clone()))?,
+        Err(error) => err!(ErrorKind::IOError(resolved.clone(), error.kind()))?,
+    };
+
+    match serde_yaml::from_reader(file) {
+        Ok(Value::Mapping(map)) => Ok(map),
+        Ok(Value::Null) => Ok(Mapping::new()),
+        Ok(_) => err!(IncludeError::Load(instance.into(), resolved, "Fragment must be a mapping.".into())),
+        Err(error) => err!(IncludeError::Load(instance.into(), resolved, error.to_string())),
+    }
+}
+
+fn display(key: &Value) -> String {
+    match key {
+        Value::String(key) => key.clone(),
+        _ => format!("{key:?}"),
+    }
+}
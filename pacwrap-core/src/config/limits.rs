@@ -0,0 +1,57 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct ContainerLimits {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    cpu: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    memory: Option<String>,
+}
+
+impl ContainerLimits {
+    pub fn is_empty(&self) -> bool {
+        self.cpu.is_none() && self.memory.is_none()
+    }
+
+    pub fn cpu(&self) -> Option<f64> {
+        self.cpu
+    }
+
+    pub fn memory(&self) -> Option<&str> {
+        self.memory.as_deref()
+    }
+
+    pub fn controllers(&self) -> Vec<&'static str> {
+        let mut controllers = Vec::new();
+
+        if self.cpu.is_some() {
+            controllers.push("cpu");
+        }
+
+        if self.memory.is_some() {
+            controllers.push("memory");
+        }
+
+        controllers
+    }
+}
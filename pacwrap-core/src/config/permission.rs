@@ -19,10 +19,12 @@
 
 use std::fmt::{Display, Formatter};
 
-use crate::exec::args::ExecutionArgs;
+use crate::{config::schema::modules, exec::args::ExecutionArgs};
 
 use dyn_clone::{clone_trait_object, DynClone};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 
+mod audio;
 mod dev;
 mod display;
 mod env;
@@ -31,6 +33,7 @@ mod net;
 pub mod none;
 mod pipewire;
 mod pulseaudio;
+mod wayland;
 
 pub enum Condition {
     Success,
@@ -61,3 +64,27 @@ impl Display for PermError {
 }
 
 clone_trait_object!(Permission);
+
+impl JsonSchema for dyn Permission {
+    fn schema_name() -> String {
+        "Permission".into()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        modules(
+            "module",
+            vec![
+                ("none", none::None::json_schema(gen)),
+                ("net", net::Network::json_schema(gen)),
+                ("dev", dev::Dev::json_schema(gen)),
+                ("env", env::Environment::json_schema(gen)),
+                ("gpu", gpu::Graphics::json_schema(gen)),
+                ("audio", audio::Audio::json_schema(gen)),
+                ("display", display::Display::json_schema(gen)),
+                ("wayland", wayland::Wayland::json_schema(gen)),
+                ("pipewire", pipewire::Pipewire::json_schema(gen)),
+                ("pulseaudio", pulseaudio::Pulseaudio::json_schema(gen)),
+            ],
+        )
+    }
+}
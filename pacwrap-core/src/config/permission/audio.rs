@@ -0,0 +1,70 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{
+        permission::{Condition::Success, PermError::Warn, *},
+        Permission,
+    },
+    constants::XDG_RUNTIME_DIR,
+    exec::args::ExecutionArgs,
+    utils::check_socket,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Audio;
+
+#[typetag::serde(name = "audio")]
+impl Permission for Audio {
+    fn check(&self) -> Result<Option<Condition>, PermError> {
+        if !check_socket(&pipewire_socket()) && !check_socket(&pulseaudio_socket()) {
+            Err(Warn("Neither Pipewire nor PulseAudio sockets were found.".into()))?
+        }
+
+        Ok(Some(Success))
+    }
+
+    fn register(&self, args: &mut ExecutionArgs) {
+        let (pipewire, pulseaudio) = (pipewire_socket(), pulseaudio_socket());
+
+        if check_socket(&pipewire) {
+            args.robind(&pipewire, &pipewire);
+        }
+
+        if check_socket(&pulseaudio) {
+            args.robind(&pulseaudio, &pulseaudio);
+            args.env("PULSE_SERVER", &format!("unix:{}", pulseaudio));
+        }
+    }
+
+    fn module(&self) -> &'static str {
+        "audio"
+    }
+}
+
+fn pipewire_socket() -> String {
+    format!("{}/pipewire-0", *XDG_RUNTIME_DIR)
+}
+
+fn pulseaudio_socket() -> String {
+    format!("{}/pulse/native", *XDG_RUNTIME_DIR)
+}
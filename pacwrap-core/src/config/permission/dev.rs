@@ -19,6 +19,7 @@
 
 use std::path::Path;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -29,8 +30,8 @@ use crate::{
     exec::args::ExecutionArgs,
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Dev {
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Dev {
     devices: Vec<String>,
 }
 
@@ -19,6 +19,7 @@
 
 use std::path::Path;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -35,8 +36,8 @@ use crate::{
     utils::check_socket,
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Display;
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Display;
 
 #[typetag::serde(name = "display")]
 impl Permission for Display {
@@ -19,6 +19,7 @@
 
 use std::env;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -30,7 +31,7 @@ use crate::{
     utils::print_warning,
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Environment {
     #[serde(skip_serializing_if = "String::is_empty", default)]
     var: String,
@@ -40,7 +41,7 @@ pub struct Environment {
     variables: Vec<Var>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 struct Var {
     var: String,
     #[serde(skip_serializing_if = "String::is_empty", default)]
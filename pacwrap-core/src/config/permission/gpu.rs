@@ -19,6 +19,7 @@
 
 use std::{fs::read_dir, path::Path, sync::OnceLock};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -37,8 +38,8 @@ use crate::{
 
 static GPU_DEV: OnceLock<Vec<String>> = OnceLock::new();
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Graphics;
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Graphics;
 
 #[typetag::serde(name = "gpu")]
 impl Permission for Graphics {
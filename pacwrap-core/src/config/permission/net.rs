@@ -17,6 +17,7 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -27,7 +28,7 @@ use crate::{
     exec::args::{Argument::HostNetworking, ExecutionArgs},
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Network;
 
 #[typetag::serde(name = "net")]
@@ -38,7 +39,6 @@ impl Permission for Network {
 
     fn register(&self, args: &mut ExecutionArgs) {
         args.push_env(HostNetworking);
-        args.bind("/etc/resolv.conf", "/etc/resolv.conf");
     }
 
     fn module(&self) -> &'static str {
@@ -19,6 +19,7 @@
 
 use std::path::Path;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -31,8 +32,8 @@ use crate::{
     utils::check_socket,
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Pulseaudio {
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Pulseaudio {
     #[serde(skip_serializing_if = "is_default_socket", default = "default_socket")]
     socket: String,
 }
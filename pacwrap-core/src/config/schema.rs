@@ -0,0 +1,103 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    schema::{InstanceType, Schema, SchemaObject, SubschemaValidation},
+    visit::{visit_schema_object, Visitor},
+    JsonSchema,
+};
+use serde_json::Value;
+
+use crate::config::{container::ContainerShadow, Global};
+
+//Keys absent from an object's declared properties are not deserialized, hence are refused by the schema.
+#[derive(Debug, Clone)]
+struct Strict;
+
+impl Visitor for Strict {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        if let Some(object) = schema.object.as_mut().filter(|a| !a.properties.is_empty() && a.additional_properties.is_none()) {
+            object.additional_properties = Some(Box::new(Schema::Bool(false)));
+        }
+
+        visit_schema_object(self, schema)
+    }
+}
+
+/// Schema describing the global configuration file, `pacwrap.yml`.
+pub fn global_schema() -> Value {
+    schema::<Global>("pacwrap.yml")
+}
+
+/// Schema describing a container configuration file, `<container>.yml`.
+pub fn container_schema() -> Value {
+    let mut schema = schema::<ContainerShadow>("container.yml");
+
+    //The default meta_version is the time of generation, which is meaningless to an editor.
+    if let Some(Value::Object(property)) = schema.pointer_mut("/properties/meta_version") {
+        property.remove("default");
+    }
+
+    schema
+}
+
+/// Schema of a module deserialized by typetag: One of each module's own schema, bearing the module's name
+/// in the required tag field.
+pub(crate) fn modules(tag: &str, modules: Vec<(&str, Schema)>) -> Schema {
+    let variants = modules
+        .into_iter()
+        .map(|(name, schema)| {
+            //Unit structs are represented solely by their tag.
+            let mut schema = match schema {
+                Schema::Object(schema) if schema.object.is_some() => schema,
+                _ => SchemaObject {
+                    instance_type: Some(InstanceType::Object.into()),
+                    ..Default::default()
+                },
+            };
+            let object = schema.object();
+            let name = SchemaObject {
+                const_value: Some(name.into()),
+                ..Default::default()
+            };
+
+            object.properties.insert(tag.into(), name.into());
+            object.required.insert(tag.into());
+            schema.into()
+        })
+        .collect();
+
+    SchemaObject {
+        subschemas: Some(Box::new(SubschemaValidation {
+            one_of: Some(variants),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+fn schema<T: JsonSchema>(title: &str) -> Value {
+    let settings = SchemaSettings::draft2019_09().with_visitor(Strict);
+    let mut root = SchemaGenerator::new(settings).into_root_schema_for::<T>();
+
+    root.schema.metadata().title = Some(title.into());
+    serde_json::to_value(root).unwrap_or(Value::Null)
+}
@@ -0,0 +1,135 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LockMode {
+    #[default]
+    Refuse,
+    Warn,
+}
+
+/// Restricts the packages which may be installed upon a slice container, such that packages intended for
+/// applications are not inadvertently shared with every downstream container.
+#[derive(Serialize, Deserialize, Clone, Default, JsonSchema)]
+pub struct TemplateLock {
+    #[serde(default)]
+    mode: LockMode,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    allow: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    deny: Vec<String>,
+}
+
+impl TemplateLock {
+    pub fn mode(&self) -> LockMode {
+        self.mode
+    }
+
+    pub fn allow(&self) -> &Vec<String> {
+        &self.allow
+    }
+
+    pub fn deny(&self) -> &Vec<String> {
+        &self.deny
+    }
+
+    /// A package is permitted should it match no pattern in the denylist and, where an allowlist is declared,
+    /// match at least one pattern therein.
+    pub fn permits(&self, pkg: &str) -> bool {
+        let matches = |patterns: &[String]| patterns.iter().any(|a| glob(a).is_some_and(|a| a.is_match(pkg)));
+
+        !matches(&self.deny) && (self.allow.is_empty() || matches(&self.allow))
+    }
+}
+
+pub fn glob(pattern: &str) -> Option<Regex> {
+    let pattern: String = pattern
+        .chars()
+        .map(|a| match a {
+            '*' => ".*".into(),
+            '?' => ".".into(),
+            _ => regex::escape(&a.to_string()),
+        })
+        .collect();
+
+    Regex::new(&format!("^{pattern}$")).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lock(allow: &[&str], deny: &[&str]) -> TemplateLock {
+        TemplateLock {
+            mode: LockMode::default(),
+            allow: allow.iter().map(|a| a.to_string()).collect(),
+            deny: deny.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn glob_wildcards() {
+        let glob = glob("lib*-?").unwrap();
+
+        assert!(glob.is_match("libfoo-1"));
+        assert!(glob.is_match("lib-2"));
+        assert!(!glob.is_match("libfoo-12"));
+        assert!(!glob.is_match("xlibfoo-1"));
+    }
+
+    #[test]
+    fn glob_literal() {
+        let glob = glob("gtk+3.0").unwrap();
+
+        assert!(glob.is_match("gtk+3.0"));
+        assert!(!glob.is_match("gtkk3.0"));
+        assert!(!glob.is_match("gtk+3x0"));
+    }
+
+    #[test]
+    fn permits_unrestricted() {
+        assert!(lock(&[], &[]).permits("firefox"));
+    }
+
+    #[test]
+    fn permits_deny() {
+        let lock = lock(&[], &["firefox*", "chromium"]);
+
+        assert!(!lock.permits("firefox"));
+        assert!(!lock.permits("firefox-developer-edition"));
+        assert!(!lock.permits("chromium"));
+        assert!(lock.permits("gtk3"));
+    }
+
+    #[test]
+    fn permits_allow() {
+        let lock = lock(&["lib*", "gtk?"], &["libreoffice*"]);
+
+        assert!(lock.permits("libx11"));
+        assert!(lock.permits("gtk3"));
+        assert!(!lock.permits("gtk4-demos"));
+        assert!(!lock.permits("libreoffice-fresh"));
+        assert!(!lock.permits("firefox"));
+    }
+}
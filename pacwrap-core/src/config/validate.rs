@@ -0,0 +1,304 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs::{read_dir, read_to_string},
+    path::Path,
+    str::FromStr,
+};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value as Schema;
+use serde_yaml::Value;
+use simplebyteunit::simplebyteunit::ByteUnit;
+
+use crate::{
+    config::{
+        schema::{container_schema, global_schema},
+        template::glob,
+        Container,
+        ContainerHandle,
+        ContainerType,
+        ContainerVariables,
+        Global,
+    },
+    constants::{BOLD, CONFIG_DIR, CONFIG_FILE, RESET},
+    sync::{architecture, filesystem::exclusion, is_valid_signature, snapshot_path, ARCHIVE_ARCH},
+};
+
+pub struct Problem {
+    path: String,
+    field: Option<String>,
+    message: String,
+}
+
+impl Problem {
+    fn new(path: &str, field: Option<&str>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            field: field.map(|a| a.into()),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, fmter: &mut Formatter<'_>) -> FmtResult {
+        match &self.field {
+            Some(field) => write!(fmter, "'{}': {}{field}{}: {}", self.path, *BOLD, *RESET, self.message),
+            None => write!(fmter, "'{}': {}", self.path, self.message),
+        }
+    }
+}
+
+/// Load and validate the global configuration and all container configurations, returning every problem
+/// encountered rather than failing upon the first.
+pub fn validate() -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let dir = format!("{}/container", *CONFIG_DIR);
+
+    if let Some(global) = load::<Global>(&CONFIG_FILE, &global_schema(), &mut problems) {
+        validate_global(&global, &mut problems);
+    }
+
+    let mut containers: Vec<String> = match read_dir(&dir) {
+        Ok(dir) => dir
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.file_name().to_str().and_then(|f| f.strip_suffix(".yml").map(|f| f.to_string())))
+            .collect(),
+        Err(error) => {
+            problems.push(Problem::new(&dir, None, error.to_string()));
+            Vec::new()
+        }
+    };
+
+    let schema = container_schema();
+
+    containers.sort();
+
+    for instance in containers.iter() {
+        let path = format!("{dir}/{instance}.yml");
+
+        if let Some(container) = load::<Container>(&path, &schema, &mut problems) {
+            let handle = ContainerHandle::new(container, ContainerVariables::new(instance));
+
+            validate_container(&path, &containers, &handle, &mut problems);
+        }
+    }
+
+    problems
+}
+
+fn load<T: DeserializeOwned>(path: &str, schema: &Schema, problems: &mut Vec<Problem>) -> Option<T> {
+    let value: Value = match read_to_string(path).map(|a| serde_yaml::from_str(&a)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(error)) => {
+            problems.push(Problem::new(path, None, error.to_string()));
+            return None;
+        }
+        Err(error) => {
+            problems.push(Problem::new(path, None, error.to_string()));
+            return None;
+        }
+    };
+    let config: T = match serde_yaml::from_value(value.clone()) {
+        Ok(config) => config,
+        Err(error) => {
+            problems.push(Problem::new(path, None, error.to_string()));
+            return None;
+        }
+    };
+
+    unknown_keys(path, "", &value, schema, schema, problems);
+    Some(config)
+}
+
+//Keys present in the file, absent from the properties of the corresponding object in the schema, were not recognised.
+fn unknown_keys(path: &str, prefix: &str, value: &Value, schema: &Schema, root: &Schema, problems: &mut Vec<Problem>) {
+    let schemas = resolve(schema, root, value);
+
+    match value {
+        Value::Mapping(value) => {
+            let objects: Vec<_> = schemas
+                .iter()
+                .filter(|a| a.get("properties").is_some() || a.get("additionalProperties").is_some())
+                .collect();
+
+            //Objects admitting arbitrary keys, or otherwise without declared properties, are not checked.
+            if objects.is_empty() || objects.iter().any(|a| a.get("additionalProperties") != Some(&Schema::Bool(false))) {
+                return;
+            }
+
+            for (key, value) in value.iter() {
+                let Some(key) = key.as_str() else {
+                    continue;
+                };
+                let field = match prefix.is_empty() {
+                    true => key.to_string(),
+                    false => format!("{prefix}.{key}"),
+                };
+
+                match objects.iter().find_map(|a| a.get("properties").and_then(|a| a.get(key))) {
+                    Some(schema) => unknown_keys(path, &field, value, schema, root, problems),
+                    None => problems.push(Problem::new(path, Some(&field), "Unknown key.")),
+                }
+            }
+        }
+        Value::Sequence(value) =>
+            for (idx, value) in value.iter().enumerate() {
+                for schema in schemas.iter().filter_map(|a| a.get("items")) {
+                    unknown_keys(path, &format!("{prefix}[{idx}]"), value, schema, root, problems);
+                }
+            },
+        Value::Tagged(value) => unknown_keys(path, prefix, &value.value, schema, root, problems),
+        _ => (),
+    }
+}
+
+//Resolve references and subschemas applicable to the value, whereby each module variant applies solely to
+//values bearing its tag.
+fn resolve<'a>(schema: &'a Schema, root: &'a Schema, value: &Value) -> Vec<&'a Schema> {
+    if !applicable(schema, value) {
+        return Vec::new();
+    }
+
+    let mut schemas = vec![schema];
+
+    if let Some(schema) = schema.get("$ref").and_then(|a| a.as_str()).and_then(|a| root.pointer(a.trim_start_matches('#'))) {
+        schemas.extend(resolve(schema, root, value));
+    }
+
+    for key in ["allOf", "anyOf", "oneOf"] {
+        if let Some(Schema::Array(subschemas)) = schema.get(key) {
+            schemas.extend(subschemas.iter().flat_map(|a| resolve(a, root, value)));
+        }
+    }
+
+    schemas
+}
+
+fn applicable(schema: &Schema, value: &Value) -> bool {
+    let Some(Schema::Object(properties)) = schema.get("properties") else {
+        return true;
+    };
+
+    properties.iter().all(|(key, property)| match property.get("const").and_then(|a| a.as_str()) {
+        Some(tag) => value.get(key.as_str()).and_then(|a| a.as_str()) == Some(tag),
+        None => true,
+    })
+}
+
+fn validate_global(global: &Global, problems: &mut Vec<Problem>) {
+    let path = *CONFIG_FILE;
+    let alpm = global.alpm();
+
+    if alpm.parallel_downloads() == 0 {
+        problems.push(Problem::new(path, Some("alpm.parallel_downloads"), "Value must be greater than zero."));
+    }
+
+    for (field, levels) in [("alpm.sig_level", alpm.sig_level()), ("alpm.sig_level_local", alpm.sig_level_local())] {
+        for level in levels.iter().filter(|a| !is_valid_signature(a)) {
+            problems.push(Problem::new(path, Some(field), format!("Invalid signature level '{level}'.")));
+        }
+    }
+
+    if global.config().dbus_timeout() == 0 {
+        problems.push(Problem::new(path, Some("config.dbus_timeout"), "Value must be greater than zero."));
+    }
+
+    if let Some(exec) = global.config().bwrap_executable() {
+        if !exec.starts_with('/') {
+            problems.push(Problem::new(path, Some("config.bwrap_executable"), format!("'{exec}' is not an absolute path.")));
+        }
+    }
+}
+
+fn validate_container(path: &str, containers: &[String], handle: &ContainerHandle, problems: &mut Vec<Problem>) {
+    let runtime = handle.config();
+
+    for dep in handle.metadata().dependencies() {
+        if dep == handle.vars().instance() {
+            problems.push(Problem::new(path, Some("dependencies"), "Container cannot depend upon itself."));
+        } else if !containers.iter().any(|a| a == dep) {
+            problems.push(Problem::new(path, Some("dependencies"), format!("Dependent container '{dep}' not found.")));
+        }
+    }
+
+    if let Some(quota) = runtime.quota() {
+        if ByteUnit::<i64>::from_str(quota).is_err() {
+            problems.push(Problem::new(path, Some("quota"), format!("Invalid quota '{quota}'.")));
+        }
+    }
+
+    if let Some(cpu) = runtime.limits().cpu() {
+        if cpu.is_nan() || cpu <= 0.0 {
+            problems.push(Problem::new(path, Some("limits.cpu"), "Value must be greater than zero."));
+        }
+    }
+
+    if let Some(memory) = runtime.limits().memory() {
+        if !matches!(ByteUnit::<i64>::from_str(memory), Ok(memory) if memory.val() > 0) {
+            problems.push(Problem::new(path, Some("limits.memory"), format!("Invalid size '{memory}'.")));
+        }
+    }
+
+    if let Some(lock) = runtime.template_lock() {
+        if !matches!(handle.metadata().container_type(), ContainerType::Slice) {
+            problems.push(Problem::new(path, Some("template_lock"), "Only applicable to slice containers."));
+        }
+
+        for pattern in lock.allow().iter().chain(lock.deny()).filter(|a| glob(a).is_none()) {
+            problems.push(Problem::new(path, Some("template_lock"), format!("Invalid pattern '{pattern}'.")));
+        }
+    }
+
+    if let Some(size) = runtime.tmp_size() {
+        if !matches!(ByteUnit::<i64>::from_str(size), Ok(size) if size.val() > 0) {
+            problems.push(Problem::new(path, Some("tmp_size"), format!("Invalid size '{size}'.")));
+        }
+    }
+
+    if let Some(architecture) = runtime.architecture() {
+        if architecture.trim().is_empty() {
+            problems.push(Problem::new(path, Some("architecture"), "Value cannot be empty."));
+        }
+    }
+
+    if let Some(date) = runtime.snapshot_date() {
+        if snapshot_path(date).is_none() {
+            problems.push(Problem::new(path, Some("snapshot_date"), format!("Invalid date '{date}': Expected YYYY-MM-DD.")));
+        }
+
+        match architecture(handle) {
+            ARCHIVE_ARCH => (),
+            arch => problems.push(Problem::new(path, Some("snapshot_date"), format!("Unavailable for architecture '{arch}'."))),
+        }
+    }
+
+    if let Err(error) = exclusion(handle) {
+        problems.push(Problem::new(path, Some("exclude_paths"), error.to_string()));
+    }
+
+    if let Some(dir) = runtime.alpm_hook_dir() {
+        if dir.starts_with('/') && !Path::new(dir).is_dir() {
+            problems.push(Problem::new(path, Some("alpm_hook_dir"), format!("'{dir}' is not a directory.")));
+        }
+    }
+}
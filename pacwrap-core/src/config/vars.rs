@@ -34,6 +34,7 @@ pub struct ContainerVariables {
     home_mount: String,
     pacman_cache: String,
     pacman_gnupg: String,
+    staged: bool,
 }
 
 impl ContainerVariables {
@@ -47,9 +48,20 @@ impl ContainerVariables {
             home_mount: format!("/home/{ins}"),
             user: ins.into(),
             instance: ins.into(),
+            staged: false,
         }
     }
 
+    pub fn staged(mut self) -> Self {
+        self.root = format!("{}/root/.{}.staged", *DATA_DIR, self.instance);
+        self.staged = true;
+        self
+    }
+
+    pub fn is_staged(&self) -> bool {
+        self.staged
+    }
+
     pub fn config(mut self, path: &str) -> Self {
         self.config = path.into();
         self
@@ -25,7 +25,7 @@ use signal_hook::consts::*;
 
 use crate::{
     error,
-    utils::{ansi::*, unix_epoch_time},
+    utils::{ansi::*, unix_epoch_time, verbosity},
     Error,
     ErrorKind,
 };
@@ -59,7 +59,7 @@ macro_rules! to_static_str {
 }
 
 lazy_static! {
-    pub static ref VERBOSE: bool = var("PACWRAP_VERBOSE").is_ok_and(|v| v == "1");
+    pub static ref VERBOSE: u8 = verbose();
     pub static ref UID: u32 = geteuid().as_raw();
     pub static ref GID: u32 = getegid().as_raw();
     pub static ref HOME: &'static str = env("HOME");
@@ -70,7 +70,6 @@ lazy_static! {
     pub static ref COLORTERM: &'static str = env_opt("COLORTERM");
     pub static ref LANG: &'static str = env_default("LANG", "en_US.UTF-8");
     pub static ref WAYLAND_DISPLAY: &'static str = env_opt("WAYLAND_DISPLAY");
-    pub static ref EDITOR: &'static str = env_default("EDITOR", "vi");
     pub static ref X11_DISPLAY: &'static str = env_opt("DISPLAY");
     pub static ref XAUTHORITY: &'static str = env_opt("XAUTHORITY");
     pub static ref LOCK_FILE: &'static str = format_str!("{}/pacwrap.lck", *DATA_DIR);
@@ -83,6 +82,7 @@ lazy_static! {
     pub static ref DBUS_SOCKET: String = format!("/run/user/{}/pacwrap_dbus_{}", *UID, &id());
     pub static ref WAYLAND_SOCKET: String = format!("{}/{}", *XDG_RUNTIME_DIR, *WAYLAND_DISPLAY);
     pub static ref LOG_LOCATION: &'static str = format_str!("{}/pacwrap.log", *DATA_DIR);
+    pub static ref TRACE_LOCATION: &'static str = format_str!("{}/trace.log", *DATA_DIR);
     pub static ref UNIX_TIMESTAMP: u64 = unix_epoch_time().as_secs();
     pub static ref IS_COLOR_TERMINAL: bool = is_color_terminal();
     pub static ref IS_TRUECOLOR_TERMINLAL: bool = is_truecolor_terminal();
@@ -119,3 +119,12 @@ fn env_default(env: &str, default: &'static str) -> &'static str {
 fn env_default_dir(env: &str, default: &str) -> &'static str {
     var(env).map_or_else(|_| format_str!("{}{}", *HOME, default), |var| var.leak())
 }
+
+//Legacy enablement of verbosity corresponds to the verbose alpm output formerly emitted
+fn verbose() -> u8 {
+    match var("PACWRAP_VERBOSE").ok().and_then(|v| v.parse().ok()) {
+        Some(1) => verbosity::ALPM,
+        Some(level) => level.min(verbosity::ARGUMENTS),
+        None => 0,
+    }
+}
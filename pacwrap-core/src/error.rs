@@ -24,10 +24,23 @@ use std::{
     result::Result as StdResult,
 };
 
-use crate::constants::{BOLD_RED, BOLD_YELLOW, RESET};
+use crate::{
+    constants::{BOLD_RED, BOLD_YELLOW, RESET},
+    utils::warnings,
+};
 
 pub type Result<T> = StdResult<T, Error>;
 
+/// Exit statuses returned by [`ErrorTrait::code`]. These values are stable between releases and
+/// documented in the manual, so existing values must never be reassigned.
+pub const EXIT_GENERIC: i32 = 1;
+pub const EXIT_IO: i32 = 2;
+pub const EXIT_LOCKED: i32 = 3;
+pub const EXIT_NOT_FOUND: i32 = 4;
+pub const EXIT_TRANSACTION: i32 = 5;
+pub const EXIT_PRIVILEGED: i32 = 6;
+pub const EXIT_INTERRUPTED: i32 = 130;
+
 #[macro_export]
 macro_rules! err {
     ( $x:expr ) => {
@@ -47,7 +60,7 @@ macro_rules! impl_error {
     ( $x:ident ) => {
         impl ErrorTrait for $x {
             fn code(&self) -> i32 {
-                1
+                $crate::error::EXIT_GENERIC
             }
         }
     };
@@ -105,7 +118,8 @@ impl Error {
     }
 
     pub fn warn(&self) {
-        eprintln!("{}", ErrorType::Warn(self))
+        eprintln!("{}", ErrorType::Warn(self));
+        warnings::record(self.kind.to_string());
     }
 
     #[allow(clippy::borrowed_box)]
@@ -18,17 +18,23 @@
  */
 
 use std::{
+    env::var,
     fmt::{Display, Formatter, Result as FmtResult},
     io::ErrorKind as IOErrorKind,
     os::{fd::AsRawFd, unix::process::ExitStatusExt},
+    path::Path,
     process::{Child, Command, ExitStatus, Stdio},
+    sync::OnceLock,
+    time::Duration,
 };
 
 use command_fds::{CommandFdExt, FdMapping};
 use lazy_static::lazy_static;
+use nix::unistd::{access, AccessFlags};
+use os_pipe::PipeReader;
 
 use crate::{
-    config::{ContainerHandle, ContainerType},
+    config::{global, ContainerHandle, ContainerType},
     constants::{
         BOLD,
         BWRAP_EXECUTABLE,
@@ -42,33 +48,47 @@ use crate::{
         RUNTIME_DIRECTORY,
         RUNTIME_TLS_STORE,
         TERM,
+        TRACE_LOCATION,
         UID,
     },
     err,
     exec::{
         seccomp::{provide_bpf_program, FilterType::*},
-        utils::{agent_params, decode_info_json, wait_on_fakeroot, wait_on_process},
+        utils::{agent_params, decode_info_json, print_command, wait_on_fakeroot, wait_on_process},
+    },
+    sync::{
+        alpm_config,
+        alpm_hook_dir,
+        cache_dir,
+        db_path_override,
+        repo_cachedirs,
+        transaction::{TransactionFlags, TransactionMetadata, TransactionParameters},
+        AGENT_HOOK_DIR,
+        AGENT_VERSION_FD,
     },
-    sync::transaction::{TransactionFlags, TransactionMetadata, TransactionParameters},
     to_static_str,
-    utils::TermControl,
+    utils::{status, unprivileged_userns, verbosity, warnings, TermControl},
     Error,
+    ErrorGeneric,
     ErrorKind,
     ErrorTrait,
     Result,
 };
 
 pub mod args;
+pub mod cgroup;
 pub mod path;
 pub mod seccomp;
 pub mod utils;
 
 lazy_static! {
     static ref ID: (&'static str, &'static str) = (to_static_str!(UID), to_static_str!(GID));
-    static ref DIST_IMG: &'static str = option_env!("PACWRAP_DIST_IMG").unwrap_or(RUNTIME_DIRECTORY);
-    static ref DIST_TLS: &'static str = option_env!("PACWRAP_DIST_TLS").unwrap_or(RUNTIME_TLS_STORE);
+    pub static ref DIST_IMG: &'static str = option_env!("PACWRAP_DIST_IMG").unwrap_or(RUNTIME_DIRECTORY);
+    pub static ref DIST_TLS: &'static str = option_env!("PACWRAP_DIST_TLS").unwrap_or(RUNTIME_TLS_STORE);
 }
 
+static BWRAP: OnceLock<&'static str> = OnceLock::new();
+
 #[derive(Debug, Clone)]
 pub enum ExecutionError {
     InvalidPathVar(String, IOErrorKind),
@@ -77,7 +97,10 @@ pub enum ExecutionError {
     UnabsolutePath(String),
     UnabsoluteExec(String),
     DirectoryNotExecutable(String),
-    SocketTimeout(String),
+    SocketTimeout(String, Duration),
+    UserNamespaceUnavailable,
+    BwrapUnavailable(String),
+    CgroupUnavailable(String),
     Container(i32),
     Bwrap(ExitStatus),
 }
@@ -90,10 +113,19 @@ impl Display for ExecutionError {
             Self::UnabsolutePath(path) => write!(fmter, "'{}': {}PATH{} variable must be absolute", path, *BOLD, *RESET),
             Self::UnabsoluteExec(path) => write!(fmter, "'{}': Executable path must be absolute.", path),
             Self::DirectoryNotExecutable(path) => write!(fmter, "'{}': Directories are not executables.", path),
-            Self::SocketTimeout(socket) => write!(fmter, "Socket '{socket}': timed out."),
+            Self::SocketTimeout(socket, elapsed) =>
+                write!(fmter, "Socket '{socket}': timed out after {}{}ms{}.", *BOLD, elapsed.as_millis(), *RESET),
             Self::Container(status) => write!(fmter, "Container exited with code: {}", status),
             Self::Bwrap(status) => write!(fmter, "bubblewrap exited with {}", status),
             Self::RuntimeArguments => write!(fmter, "Invalid runtime arguments."),
+            Self::BwrapUnavailable(path) =>
+                write!(fmter, "'{path}': Specified {}bwrap{} executable is absent or not executable.", *BOLD, *RESET),
+            Self::CgroupUnavailable(reason) => write!(fmter, "Resource limits not applied: {reason}."),
+            Self::UserNamespaceUnavailable => write!(
+                fmter,
+                "Unprivileged user namespaces are disabled on this system.
+Enable them with `sysctl kernel.unprivileged_userns_clone=1`, or ensure `user.max_user_namespaces` is non-zero."
+            ),
         }
     }
 }
@@ -114,8 +146,18 @@ pub enum ExecutionType {
     NonInteractive,
 }
 
+pub fn check_userns() -> Result<()> {
+    if !unprivileged_userns() {
+        err!(ExecutionError::UserNamespaceUnavailable)?
+    }
+
+    Ok(())
+}
+
 #[rustfmt::skip]
 pub fn fakeroot_container(exec_type: ExecutionType, trap: Option<fn(i32)>, ins: &ContainerHandle, arguments: Vec<&str>) -> Result<()> {
+    check_userns()?;
+
     let term_control = TermControl::new(0);
     let info_pipe = os_pipe::pipe().expect("bwrap pipe");
     let sec_pipe = os_pipe::pipe().expect("eBPF pipe");
@@ -131,7 +173,29 @@ pub fn fakeroot_container(exec_type: ExecutionType, trap: Option<fn(i32)>, ins:
 	        child_fd: info_fd 
 	    },
 	];
-	let mut process = Command::new(BWRAP_EXECUTABLE);
+
+    match fakeroot_command(ins, arguments, sec_fd, info_fd)?
+        .fd_mappings(fd_mappings)
+        .expect("FD Mappings")
+        .spawn() 
+	{
+		Ok(child) => wait_on_fakeroot(exec_type, child, term_control, decode_info_json(info_pipe)?, trap),
+		Err(err) => err!(ErrorKind::ProcessInitFailure(bwrap_executable()?, err.kind())),
+	}
+}
+
+pub fn print_fakeroot_container(ins: &ContainerHandle, arguments: Vec<&str>) -> Result<()> {
+    let info_pipe = os_pipe::pipe().expect("bwrap pipe");
+    let sec_pipe = os_pipe::pipe().expect("eBPF pipe");
+    let sec_fd = provide_bpf_program(vec![Standard, Namespaces], &sec_pipe.0, sec_pipe.1).expect("eBPF program");
+
+    print_command(&fakeroot_command(ins, arguments, sec_fd, info_pipe.1.as_raw_fd())?);
+    Ok(())
+}
+
+#[rustfmt::skip]
+fn fakeroot_command(ins: &ContainerHandle, arguments: Vec<&str>, sec_fd: i32, info_fd: i32) -> Result<Command> {
+	let mut process = Command::new(bwrap_executable()?);
 
 	process.env_clear()
         .arg("--tmpfs").arg("/tmp")
@@ -186,14 +250,28 @@ pub fn fakeroot_container(exec_type: ExecutionType, trap: Option<fn(i32)>, ins:
             .arg("fakeroot").arg("chroot").arg("/mnt/fs")
     };
 
-    match process.args(arguments)
-        .fd_mappings(fd_mappings)
-        .expect("FD Mappings")
-        .spawn() 
-	{
-		Ok(child) => wait_on_fakeroot(exec_type, child, term_control, decode_info_json(info_pipe)?, trap),
-		Err(err) => err!(ErrorKind::ProcessInitFailure(BWRAP_EXECUTABLE, err.kind())),
-	}
+    process.args(arguments);
+    Ok(process)
+}
+
+pub fn bwrap_executable() -> Result<&'static str> {
+    if let Some(exec) = BWRAP.get() {
+        return Ok(exec);
+    }
+
+    let exec = match var("PACWRAP_BWRAP") {
+        Ok(var) => Some(var),
+        Err(_) => global()?.config().bwrap_executable().map(|a| a.into()),
+    };
+    let exec = match exec {
+        Some(exec) if !exec.starts_with('/') => err!(ExecutionError::UnabsoluteExec(exec))?,
+        Some(exec) if !Path::new(&exec).is_file() || access(exec.as_str(), AccessFlags::X_OK).is_err() =>
+            err!(ExecutionError::BwrapUnavailable(exec))?,
+        Some(exec) => exec.leak(),
+        None => BWRAP_EXECUTABLE,
+    };
+
+    Ok(BWRAP.get_or_init(|| exec))
 }
 
 #[rustfmt::skip]
@@ -202,12 +280,18 @@ pub fn transaction_agent(
     flags: &TransactionFlags,
     params: TransactionParameters,
     metadata: &TransactionMetadata,
-) -> Result<Child> {	
+) -> Result<(Child, PipeReader)> {	
+    check_userns()?;
+
     let params_pipe = os_pipe::pipe().expect("params pipe");
     let params_fd = agent_params(&params_pipe.0, &params_pipe.1, &params, metadata)?;	
     let sec_pipe = os_pipe::pipe().expect("eBPF pipe");
     let sec_fd = provide_bpf_program(vec![Standard, Namespaces], &sec_pipe.0, sec_pipe.1).expect("eBPF program");
-    let fd_mappings = vec![
+    let status_pipe = status::relay()?;
+    let warning_pipe = warnings::relay()?;
+    let version_pipe = os_pipe::pipe().prepend_io(|| "Version relay".into())?;
+    let version_fd = version_pipe.1.as_raw_fd();
+    let mut fd_mappings = vec![
         FdMapping { 
             parent_fd: sec_fd, 
             child_fd: sec_fd 
@@ -216,8 +300,12 @@ pub fn transaction_agent(
             parent_fd: params_fd, 
             child_fd: params_fd 
         },
+        FdMapping { 
+            parent_fd: version_fd, 
+            child_fd: version_fd 
+        },
     ]; 
-    let mut process = Command::new(BWRAP_EXECUTABLE);
+    let mut process = Command::new(bwrap_executable()?);
 
     process.arg("--bind").arg(ins.vars().root()).arg("/mnt/fs")
         .arg("--symlink").arg("/mnt/fs/usr").arg("/usr")
@@ -229,7 +317,7 @@ pub fn transaction_agent(
         .arg("--ro-bind").arg(*DIST_TLS).arg("/etc/ssl/certs/ca-certificates.crt")
         .arg("--bind").arg(*LOG_LOCATION).arg("/mnt/share/pacwrap.log") 
         .arg("--bind").arg(ins.vars().pacman_gnupg()).arg("/mnt/share/gnupg")
-        .arg("--bind").arg(ins.vars().pacman_cache()).arg("/mnt/share/cache")
+        .arg("--bind").arg(cache_dir(ins.vars())).arg("/mnt/share/cache")
         .arg("--dev").arg("/dev")
         .arg("--dev").arg("/mnt/fs/dev")
         .arg("--proc").arg("/mnt/fs/proc")
@@ -254,20 +342,65 @@ pub fn transaction_agent(
         .arg(sec_fd.to_string())
         .arg("--ro-bind-data")
         .arg(params_fd.to_string())
-        .arg("/mnt/agent_params");
+        .arg("/mnt/agent_params")
+        .arg("--setenv").arg(AGENT_VERSION_FD).arg(version_fd.to_string());
+
+    if let Some(path) = db_path_override(ins.vars())? {
+        process.arg("--bind").arg(path).arg("/mnt/fs/var/lib/pacman");
+    }
+
+    if let Some(path) = alpm_hook_dir(ins) {
+        process.arg("--ro-bind").arg(path).arg(AGENT_HOOK_DIR);
+    }
+
+    for (repo, path) in repo_cachedirs(global()?, alpm_config()?) {
+        process.arg("--bind-try").arg(path).arg(format!("/mnt/share/repo/{repo}"));
+    }
 
     if flags.contains(TransactionFlags::DEBUG) {
         process.arg("--setenv").arg("RUST_BACKTRACE").arg("full");
     }
 
-    match process.arg("agent")
-        .arg("transact")
-        .fd_mappings(fd_mappings)
+    if flags.contains(TransactionFlags::TRACE) {
+        process.arg("--bind").arg(*TRACE_LOCATION).arg("/mnt/share/trace.log");
+    }
+
+    if let Some(pipe) = &status_pipe {
+        let status_fd = pipe.as_raw_fd();
+
+        fd_mappings.push(FdMapping { 
+            parent_fd: status_fd, 
+            child_fd: status_fd 
+        });
+        process.arg("--setenv").arg(status::STATUS_FD).arg(status_fd.to_string());
+    }
+
+    if let Some(pipe) = &warning_pipe {
+        let warning_fd = pipe.as_raw_fd();
+
+        fd_mappings.push(FdMapping { 
+            parent_fd: warning_fd, 
+            child_fd: warning_fd 
+        });
+        process.arg("--setenv").arg(warnings::WARNING_FD).arg(warning_fd.to_string());
+    }
+
+    if verbosity::level() > 0 {
+        process.arg("--setenv").arg(verbosity::AGENT_VERBOSITY).arg(verbosity::level().to_string());
+    }
+
+    process.arg("agent").arg("transact");
+
+    if verbosity::level() >= verbosity::ARGUMENTS {
+        eprintln!("{process:?}");
+    }
+
+    match process.fd_mappings(fd_mappings)
         .expect("FD Mappings")
         .spawn() 
     {
-        Ok(child) => Ok(child),
-        Err(err) => err!(ErrorKind::ProcessInitFailure(BWRAP_EXECUTABLE, err.kind())),
+        Ok(child) => Ok((child, version_pipe.0)),
+        Err(err) => err!(ErrorKind::ProcessInitFailure(bwrap_executable()?, err.kind())),
     }
 }
 
@@ -34,6 +34,7 @@ pub enum Argument {
     ProcFs,
     NewSession,
     TmpFs,
+    SizedTmpFs(String),
     UnshareAll,
 }
 
@@ -60,6 +61,7 @@ impl Argument {
             Self::ProcFs => vec!["--proc", "/proc"],
             Self::NewSession => vec!["--new-session"],
             Self::TmpFs => vec!["--tmpfs", "/tmp"],
+            Self::SizedTmpFs(size) => vec!["--size", size, "--tmpfs", "/tmp"],
             Self::UnshareAll => vec!["--unshare-all"],
         }
     }
@@ -97,6 +99,12 @@ impl ExecutionArgs {
         self.bind.push(Argument::SymbolicLink(src.into(), dest.into()));
     }
 
+    pub fn tmp_size(&mut self, bytes: u64) {
+        for arg in self.bind.iter_mut().filter(|a| matches!(a, Argument::TmpFs)) {
+            *arg = Argument::SizedTmpFs(bytes.to_string());
+        }
+    }
+
     pub fn env(&mut self, src: &str, dest: &str) {
         self.env.push(Argument::EnvVar(src.into(), dest.into()));
     }
@@ -127,6 +135,14 @@ impl ExecutionArgs {
         })
     }
 
+    pub fn unshare_user(&self) -> bool {
+        self.env.iter().any(|a| matches!(a, Argument::UnshareAll | Argument::DisableNamespaces))
+    }
+
+    pub fn share_net(&self) -> bool {
+        self.env.iter().any(|a| matches!(a, Argument::HostNetworking))
+    }
+
     pub fn arguments(&self) -> Vec<&str> {
         let mut vec = Vec::with_capacity((self.sys.len() + self.bind.len() + self.env.len()) * 4);
 
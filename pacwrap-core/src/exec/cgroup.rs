@@ -0,0 +1,136 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+    fs::{create_dir, read_to_string, remove_dir, write, File, OpenOptions},
+    path::Path,
+    process::id,
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use simplebyteunit::simplebyteunit::*;
+
+use crate::{
+    config::limits::ContainerLimits,
+    err,
+    exec::ExecutionError,
+    utils::print_warning,
+    Error,
+    Result,
+};
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+const CPU_PERIOD: u64 = 100000;
+
+static CGROUP: OnceLock<String> = OnceLock::new();
+
+/// Create a scoped cgroup adjacent to pacwrap's own within the cgroup v2 hierarchy, with the container's
+/// limits applied thereto. The parent cgroup must be delegated to the user, as is the case for processes
+/// launched beneath a systemd user manager.
+pub fn instantiate(instance: &str, limits: &ContainerLimits) -> Result<()> {
+    let parent = match read_to_string("/proc/self/cgroup") {
+        Ok(contents) => match contents.lines().find_map(|a| a.strip_prefix("0::")) {
+            Some(path) => match Path::new(path).parent() {
+                Some(parent) => format!("{CGROUP_ROOT}{}", parent.to_string_lossy().trim_end_matches('/')),
+                None => err!(unavailable("Process resides within the root cgroup"))?,
+            },
+            None => err!(unavailable("cgroup v2 hierarchy is not mounted"))?,
+        },
+        Err(error) => err!(unavailable(&format!("/proc/self/cgroup: {error}")))?,
+    };
+    let available = read_to_string(format!("{parent}/cgroup.controllers")).unwrap_or_default();
+    let enabled = read_to_string(format!("{parent}/cgroup.subtree_control")).unwrap_or_default();
+
+    for controller in limits.controllers() {
+        if !available.split_whitespace().any(|a| a == controller) {
+            err!(unavailable(&format!("'{controller}' controller is not delegated")))?
+        } else if enabled.split_whitespace().any(|a| a == controller) {
+            continue;
+        }
+
+        if let Err(error) = write(format!("{parent}/cgroup.subtree_control"), format!("+{controller}")) {
+            err!(unavailable(&format!("Failed to enable '{controller}' controller: {error}")))?
+        }
+    }
+
+    let path = format!("{parent}/pacwrap-{instance}-{}.scope", id());
+
+    if let Err(error) = create_dir(&path) {
+        err!(unavailable(&format!("'{path}': {error}")))?
+    }
+
+    if let Err(error) = apply(&path, limits) {
+        let _ = remove_dir(&path);
+        return Err(error);
+    }
+
+    CGROUP.get_or_init(|| path);
+    Ok(())
+}
+
+fn apply(path: &str, limits: &ContainerLimits) -> Result<()> {
+    if let Some(cpu) = limits.cpu() {
+        let quota = (cpu * CPU_PERIOD as f64) as u64;
+
+        if let Err(error) = write(format!("{path}/cpu.max"), format!("{quota} {CPU_PERIOD}")) {
+            err!(unavailable(&format!("Failed to apply CPU limit: {error}")))?
+        }
+    }
+
+    if let Some(memory) = limits.memory() {
+        let bytes = match ByteUnit::<i64>::from_str(memory) {
+            Ok(bytes) if bytes.val() > 0 => bytes.val(),
+            _ => err!(unavailable(&format!("Invalid memory limit '{memory}'")))?,
+        };
+
+        if let Err(error) = write(format!("{path}/memory.max"), bytes.to_string()) {
+            err!(unavailable(&format!("Failed to apply memory limit: {error}")))?
+        }
+    }
+
+    Ok(())
+}
+
+/// Open the process list of the scoped cgroup, if one was instantiated. Writing `0` thereto from a child
+/// prior to exec moves it into the cgroup, such that its descendants are confined from the outset.
+pub fn procs() -> Result<Option<File>> {
+    let path = match CGROUP.get() {
+        Some(path) => format!("{path}/cgroup.procs"),
+        None => return Ok(None),
+    };
+
+    match OpenOptions::new().write(true).open(&path) {
+        Ok(file) => Ok(Some(file)),
+        Err(error) => err!(unavailable(&format!("'{path}': {error}"))),
+    }
+}
+
+/// Remove the scoped cgroup, which is retained should any processes persist therein.
+pub fn release() {
+    if let Some(path) = CGROUP.get() {
+        if let Err(error) = remove_dir(path) {
+            print_warning(&format!("Failed to remove cgroup '{path}': {error}"));
+        }
+    }
+}
+
+fn unavailable(reason: &str) -> ExecutionError {
+    ExecutionError::CgroupUnavailable(reason.into())
+}
@@ -18,17 +18,23 @@
  */
 
 use std::{
-    io::Read,
+    fs::{read_dir, read_to_string},
+    io::{Read, Write},
     os::fd::AsRawFd,
     path::Path,
-    process::{exit, Child},
+    process::{exit, Child, Command},
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
+use nix::{
+    sys::signal::{kill, Signal},
+    unistd::Pid,
+};
 use os_pipe::{PipeReader, PipeWriter};
 use serde::Serialize;
 use serde_yaml::Value;
+use sha2::{Digest, Sha256};
 
 use crate::{
     config::global,
@@ -41,7 +47,7 @@ use crate::{
         transaction::{TransactionMetadata, TransactionParameters},
         SyncError,
     },
-    utils::TermControl,
+    utils::{print_warning, TermControl},
     ErrorKind,
 };
 
@@ -96,6 +102,64 @@ pub fn wait_on_container(
     }
 }
 
+/// Tear down the container once its session has remained idle for the duration specified. A session is deemed
+/// idle whilst the process executed within the container persists without any other process present beneath
+/// the bwrap pid, such that background processes working silently are never mistaken for idleness.
+pub fn watch_idle(bwrap_pid: i32, timeout: Duration) {
+    let mut leader = None;
+    let mut idle_since = None;
+
+    while Path::new(&format!("/proc/{bwrap_pid}/")).exists() {
+        let processes: Vec<i32> = descendants(bwrap_pid).into_iter().filter(|pid| !is_bwrap(*pid)).collect();
+
+        if leader.is_none() {
+            leader = processes.first().copied();
+        }
+
+        let idle = leader.is_some_and(|leader| processes == [leader]);
+
+        match (idle, idle_since) {
+            (false, _) => idle_since = None,
+            (true, None) => idle_since = Some(Instant::now()),
+            (true, Some(since)) if since.elapsed() >= timeout => {
+                print_warning(&format!("Session idle for {} seconds: Tearing down container.", timeout.as_secs()));
+                let _ = kill(Pid::from_raw(bwrap_pid), Signal::SIGKILL);
+                return;
+            }
+            (true, Some(_)) => (),
+        }
+
+        thread::sleep(PROCESS_SLEEP_DURATION);
+    }
+}
+
+fn descendants(pid: i32) -> Vec<i32> {
+    let mut processes = Vec::new();
+    let mut queue = vec![pid];
+
+    while let Some(pid) = queue.pop() {
+        let Ok(tasks) = read_dir(format!("/proc/{pid}/task")) else {
+            continue;
+        };
+
+        for task in tasks.flatten() {
+            let children = read_to_string(task.path().join("children")).unwrap_or_default();
+
+            for child in children.split_whitespace().filter_map(|a| a.parse().ok()) {
+                processes.push(child);
+                queue.push(child);
+            }
+        }
+    }
+
+    processes.sort();
+    processes
+}
+
+fn is_bwrap(pid: i32) -> bool {
+    read_to_string(format!("/proc/{pid}/comm")).is_ok_and(|comm| comm.trim_end() == "bwrap")
+}
+
 pub fn wait_on_fakeroot(
     exec_type: ExecutionType,
     mut process: Child,
@@ -133,6 +197,19 @@ pub fn wait_on_fakeroot(
     }
 }
 
+pub fn print_command(command: &Command) {
+    let arguments: Vec<String> = command.get_args().map(|a| shell_quote(&a.to_string_lossy())).collect();
+
+    println!("{} {}", shell_quote(&command.get_program().to_string_lossy()), arguments.join(" "));
+}
+
+fn shell_quote(arg: &str) -> String {
+    match !arg.is_empty() && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c)) {
+        true => arg.into(),
+        false => format!("'{}'", arg.replace('\'', "'\\''")),
+    }
+}
+
 pub fn decode_info_json(mut info_pipe: (PipeReader, PipeWriter)) -> Result<i32> {
     let mut output = String::new();
 
@@ -154,15 +231,25 @@ pub fn agent_params(
     params: &TransactionParameters,
     metadata: &TransactionMetadata,
 ) -> Result<i32> {
-    serialize(params, writer)?;
-    serialize(global()?, writer)?;
-    serialize(alpm_config()?, writer)?;
-    serialize(metadata, writer)?;
+    let mut payload = Vec::new();
+
+    serialize(params, &mut payload)?;
+    serialize(global()?, &mut payload)?;
+    serialize(alpm_config()?, &mut payload)?;
+    serialize(metadata, &mut payload)?;
+
+    let checksum = Sha256::digest(&payload);
+    let mut pipe = writer;
+
+    if let Err(error) = pipe.write_all(&payload).and_then(|_| pipe.write_all(&checksum)) {
+        err!(SyncError::TransactionFailure(format!("Agent data transmission failed: {}", error)))?
+    }
+
     Ok(reader.as_raw_fd())
 }
 
-fn serialize<T: for<'de> Serialize>(input: &T, file: &PipeWriter) -> Result<()> {
-    match bincode::serialize_into::<&PipeWriter, T>(file, input) {
+fn serialize<T: for<'de> Serialize>(input: &T, payload: &mut Vec<u8>) -> Result<()> {
+    match bincode::serialize_into::<&mut Vec<u8>, T>(payload, input) {
         Ok(()) => Ok(()),
         Err(error) => err!(SyncError::TransactionFailure(format!("Agent data serialization failed: {}", error))),
     }
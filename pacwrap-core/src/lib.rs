@@ -42,6 +42,7 @@ pub enum ErrorKind {
     Message(&'static str),
     Termios(nix::errno::Errno),
     InstanceNotFound(String),
+    TagNotFound(String),
     DependencyNotFound(String, String),
     LinkerUninitialized,
     ThreadPoolUninitialized,
@@ -58,11 +59,18 @@ impl Display for ErrorKind {
             Self::ProcessInitFailure(exec, err) => write!(fmter, "Unable to initialize '{exec}': {err}"),
             Self::ProcessWaitFailure(exec, err) => write!(fmter, "Unable to wait on '{exec}': {err}"),
             Self::InstanceNotFound(ins) => write!(fmter, "Container '{}{ins}{}' not found.", *BOLD, *RESET),
+            Self::TagNotFound(tag) => write!(fmter, "Tag '{}{tag}{}' matches no transactable containers.", *BOLD, *RESET),
             Self::IOError(ins, error) => write!(fmter, "'{ins}': {error}"),
             Self::ThreadPoolUninitialized => write!(fmter, "Threadpool uninitialized"),
             Self::LinkerUninitialized => write!(fmter, "Filesystem synchronization structure is uninitialized."),
             Self::Termios(errno) => write!(fmter, "Failed to restore termios parameters: {errno}."),
-            Self::ElevatedPrivileges => write!(fmter, "Execution with elevated privileges is not supported."),
+            Self::ElevatedPrivileges => write!(
+                fmter,
+                "Execution with elevated privileges is not supported.
+pacwrap is designed for unprivileged use: containers are sandboxed within unprivileged user namespaces, and
+running as root would grant the sandbox, and every container it hosts, the privileges of the superuser.
+Invoke pacwrap as an unprivileged user, without the use of sudo, doas, or su."
+            ),
         }?;
 
         if let Self::Message(_) = self {
@@ -76,8 +84,10 @@ impl Display for ErrorKind {
 impl ErrorTrait for ErrorKind {
     fn code(&self) -> i32 {
         match self {
-            ErrorKind::IOError(..) => 2,
-            _ => 1,
+            ErrorKind::IOError(..) => EXIT_IO,
+            ErrorKind::InstanceNotFound(..) | ErrorKind::TagNotFound(..) | ErrorKind::DependencyNotFound(..) => EXIT_NOT_FOUND,
+            ErrorKind::ElevatedPrivileges => EXIT_PRIVILEGED,
+            _ => EXIT_GENERIC,
         }
     }
 }
@@ -24,7 +24,15 @@ use std::{
     path::Path,
 };
 
-use crate::{constants::LOCK_FILE, err, impl_error, Error, ErrorGeneric, ErrorTrait, Result};
+use crate::{
+    constants::LOCK_FILE,
+    err,
+    error::{EXIT_GENERIC, EXIT_LOCKED},
+    Error,
+    ErrorGeneric,
+    ErrorTrait,
+    Result,
+};
 
 #[derive(Debug)]
 pub enum LockError {
@@ -47,7 +55,14 @@ impl Display for LockError {
     }
 }
 
-impl_error!(LockError);
+impl ErrorTrait for LockError {
+    fn code(&self) -> i32 {
+        match self {
+            Self::Locked(_) => EXIT_LOCKED,
+            Self::NotAcquired => EXIT_GENERIC,
+        }
+    }
+}
 
 pub struct Lock {
     lock: &'static str,
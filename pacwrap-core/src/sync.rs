@@ -17,8 +17,9 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 use std::{
+    env::consts::ARCH,
     fmt::{Display, Formatter, Result as FmtResult},
-    fs::{create_dir, create_dir_all},
+    fs::{copy, create_dir, create_dir_all, read_link},
     os::unix::fs::symlink,
     path::Path,
     sync::OnceLock,
@@ -27,6 +28,8 @@ use std::{
 use alpm::{Alpm, LogLevel, SigLevel, Usage};
 use pacmanconf::{self, Config, Repository};
 use serde::{Deserialize, Serialize};
+use time::{macros::format_description, Date};
+use walkdir::WalkDir;
 
 use crate::{
     config::{
@@ -36,17 +39,19 @@ use crate::{
         ContainerVariables,
         Global,
     },
-    constants::{ARROW_RED, BAR_GREEN, BOLD, CACHE_DIR, CONFIG_DIR, DATA_DIR, RESET, UNIX_TIMESTAMP, VERBOSE},
+    constants::{ARROW_RED, BAR_GREEN, BOLD, CONFIG_DIR, DATA_DIR, RESET, UNIX_TIMESTAMP},
     err,
+    error::{EXIT_GENERIC, EXIT_INTERRUPTED, EXIT_NOT_FOUND, EXIT_TRANSACTION},
     exec::pacwrap_key,
     sync::{
         event::download::{self, DownloadEvent},
         filesystem::{create_blank_state, create_hard_link},
         transaction::{TransactionAggregator, TransactionFlags},
     },
-    utils::{prompt::PromptError, unix_epoch_time},
+    utils::{print_warning, prompt::PromptError, unix_epoch_time, verbosity},
     Error,
     ErrorGeneric,
+    ErrorKind,
     ErrorTrait,
     Result,
 };
@@ -56,12 +61,33 @@ pub mod filesystem;
 pub mod schema;
 pub mod transaction;
 pub mod utils;
+pub mod verify;
 
 mod resolver;
 mod resolver_local;
 
+pub const AGENT_HOOK_DIR: &str = "/mnt/share/hooks/";
+/// Environment variable by which the agent is informed of the file descriptor to report its version upon.
+pub const AGENT_VERSION_FD: &str = "PACWRAP_AGENT_VERSION_FD";
+pub const ARCHIVE_ARCH: &str = "x86_64";
+
+const ARCHIVE_URL: &str = "https://archive.archlinux.org/repos";
+const ARCHIVE_REPOS: [&str; 8] = [
+    "core",
+    "core-testing",
+    "extra",
+    "extra-testing",
+    "multilib",
+    "multilib-testing",
+    "gnome-unstable",
+    "kde-unstable",
+];
+
 static PACMAN_CONFIG: OnceLock<pacmanconf::Config> = OnceLock::new();
 static ALPM_CONFIG_DATA: OnceLock<AlpmConfigData> = OnceLock::new();
+static CACHE_DIR_OVERRIDE: OnceLock<String> = OnceLock::new();
+static DB_PATH_OVERRIDE: OnceLock<String> = OnceLock::new();
+static DOWNLOAD_RATE_OVERRIDE: OnceLock<u64> = OnceLock::new();
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum SyncError {
@@ -70,12 +96,29 @@ pub enum SyncError {
     ParameterAcquisitionFailure,
     DeserializationFailure,
     InvalidMagicNumber,
+    ChecksumMismatch,
     SignalInterrupt,
-    AgentVersionMismatch,
+    AgentVersionMismatch(Option<String>),
     NothingToDo,
+    ContainerFrozen(String),
+    HookFailure(String, String, String),
+    HookAborted(String, String, String),
+    LocaleGenAbsent(String),
+    LocaleGenFailure(String, String),
+    QuotaExceeded(String, String, String),
+    InvalidQuota(String, String),
+    InvalidSnapshot(String, String),
+    SnapshotUnavailable(String, String, String),
+    SnapshotUnsupported(String, String),
+    NotAvailableOffline(Vec<String>),
+    VerificationFailure(String, usize),
+    ContainersFailed(usize, usize),
     DependentContainerMissing(String),
+    OrderingCycle(Vec<String>),
+    OrderingUnsatisfiable(String, String),
     RecursionDepthExceeded(isize),
     TargetUpstream(String),
+    TemplateLocked(Vec<String>),
     TargetNotInstalled(String),
     TargetNotAvailable(String),
     PreparationFailure(String),
@@ -92,22 +135,106 @@ impl Display for SyncError {
         match self {
             Self::DependentContainerMissing(u) =>
                 write!(fmter, "Dependent container '{}{u}{}' is misconfigured or otherwise is missing.", *BOLD, *RESET),
+            Self::ContainerFrozen(ins) => write!(
+                fmter,
+                "Container {}{ins}{} is frozen: Package transactions are refused.
+Specify '--unfreeze' to override for this invocation.",
+                *BOLD,
+                *RESET
+            ),
+            Self::HookFailure(ins, exec, err) => write!(fmter, "Container {}{ins}{}: Hook '{exec}' failed: {err}", *BOLD, *RESET),
+            Self::HookAborted(ins, exec, err) =>
+                write!(fmter, "Container {}{ins}{}: Transaction aborted by hook '{exec}': {err}", *BOLD, *RESET),
+            Self::LocaleGenAbsent(ins) =>
+                write!(fmter, "Container {}{ins}{}: Unable to generate locales: 'locale-gen' is not present.", *BOLD, *RESET),
+            Self::LocaleGenFailure(ins, err) =>
+                write!(fmter, "Container {}{ins}{}: Failed to generate locales: {err}", *BOLD, *RESET),
+            Self::QuotaExceeded(ins, size, quota) => write!(
+                fmter,
+                "Container {}{ins}{}: Projected size of {size} exceeds quota of {quota}.
+Specify '--force' to override for this invocation.",
+                *BOLD,
+                *RESET
+            ),
+            Self::InvalidQuota(ins, quota) => write!(fmter, "Container {}{ins}{}: Invalid quota '{quota}'.", *BOLD, *RESET),
+            Self::InvalidSnapshot(ins, date) =>
+                write!(fmter, "Container {}{ins}{}: Invalid snapshot date '{date}': Expected YYYY-MM-DD.", *BOLD, *RESET),
+            Self::SnapshotUnavailable(ins, date, err) =>
+                write!(fmter, "Container {}{ins}{}: Snapshot '{date}' is unavailable: {err}", *BOLD, *RESET),
+            Self::SnapshotUnsupported(ins, arch) =>
+                write!(fmter, "Container {}{ins}{}: Snapshots are unavailable for architecture '{arch}'.", *BOLD, *RESET),
+            Self::VerificationFailure(ins, count) => write!(
+                fmter,
+                "Container {}{ins}{}: {}{count}{} package{} failed verification.",
+                *BOLD,
+                *RESET,
+                *BOLD,
+                *RESET,
+                if *count > 1 { "s" } else { "" }
+            ),
+            Self::NotAvailableOffline(pkgs) => write!(
+                fmter,
+                "Target package{} not available offline: {}{}{}",
+                if pkgs.len() > 1 { "s" } else { "" },
+                *BOLD,
+                pkgs.join(", "),
+                *RESET
+            ),
+            Self::ContainersFailed(failed, skipped) => write!(
+                fmter,
+                "{}{failed}{} container{} failed and {}{skipped}{} dependent{} skipped.",
+                *BOLD,
+                *RESET,
+                if *failed == 1 { "" } else { "s" },
+                *BOLD,
+                *RESET,
+                if *skipped == 1 { " was" } else { "s were" }
+            ),
             Self::TargetNotAvailable(pkg) =>
                 write!(fmter, "Target package {}{pkg}{}: Not available in sync databases.", *BOLD, *RESET),
+            Self::TemplateLocked(pkgs) => write!(
+                fmter,
+                "Package{} {}{}{}: Not permitted by template lock.
+Specify '--force' to override for this invocation.",
+                if pkgs.len() > 1 { "s" } else { "" },
+                *BOLD,
+                pkgs.join(", "),
+                *RESET
+            ),
             Self::TargetUpstream(pkg) =>
                 write!(fmter, "Target package {}{pkg}{}: Installed in upstream container.", *BOLD, *RESET),
             Self::TransactionAgentError | Self::TransactionAgentFailure =>
                 write!(fmter, "Agent process terminated due to upstream error."),
+            Self::OrderingCycle(cycle) =>
+                write!(fmter, "Ordering constraints form a cycle: {}{}{}", *BOLD, cycle.join(" -> "), *RESET),
+            Self::OrderingUnsatisfiable(ins, after) => write!(
+                fmter,
+                "Container {}{ins}{} cannot be ordered after aggregate container {}{after}{}.",
+                *BOLD,
+                *RESET,
+                *BOLD,
+                *RESET
+            ),
             Self::RecursionDepthExceeded(u) => write!(fmter, "Recursion depth exceeded maximum of {}{u}{}.", *BOLD, *RESET),
             Self::NoCompatibleContainers => write!(fmter, "No compatible containers available to synchronize remote database."),
             Self::InvalidMagicNumber => write!(fmter, "Deserialization of input parameters failed: Invalid magic number."),
+            Self::ChecksumMismatch => write!(fmter, "Deserialization of input parameters failed: Checksum mismatch."),
             Self::TargetNotInstalled(pkg) => write!(fmter, "Target package {}{pkg}{}: Not installed.", *BOLD, *RESET),
             Self::InitializationFailure(msg) => write!(fmter, "Failure to initialize transaction: {msg}"),
             Self::PreparationFailure(msg) => write!(fmter, "Failure to prepare transaction: {msg}"),
             Self::TransactionFailure(msg) => write!(fmter, "Failure to commit transaction: {msg}"),
             Self::DeserializationFailure => write!(fmter, "Deserialization of input parameters failed."),
             Self::ParameterAcquisitionFailure => write!(fmter, "Failure to acquire agent runtime parameters."),
-            Self::AgentVersionMismatch => write!(fmter, "Agent binary mismatch."),
+            Self::AgentVersionMismatch(agent) => write!(
+                fmter,
+                "{}pacwrap-agent {}{} is incompatible with {}pacwrap v{}{}: Ensure both are of the same release.",
+                *BOLD,
+                agent.as_ref().map_or("of unknown version".into(), |agent| format!("v{agent}")),
+                *RESET,
+                *BOLD,
+                env!("CARGO_PKG_VERSION"),
+                *RESET
+            ),
             Self::InternalError(msg) => write!(fmter, "Internal failure: {msg}"),
             Self::SignalInterrupt => write!(fmter, "Signal interrupt was triggered."),
             Self::UnableToLocateKeyrings => write!(fmter, "Unable to locate pacman keyrings."),
@@ -125,7 +252,17 @@ impl ErrorTrait for SyncError {
             _ => eprintln!("{} Transaction failed.", *ARROW_RED),
         }
 
-        1
+        match self {
+            Self::SignalInterrupt => EXIT_INTERRUPTED,
+            Self::DependentContainerMissing(_) => EXIT_NOT_FOUND,
+            Self::TransactionAgentError
+            | Self::TransactionAgentFailure
+            | Self::InitializationFailure(_)
+            | Self::PreparationFailure(_)
+            | Self::TransactionFailure(_)
+            | Self::ContainersFailed(..) => EXIT_TRANSACTION,
+            _ => EXIT_GENERIC,
+        }
     }
 }
 
@@ -205,14 +342,120 @@ fn pacman_conf() -> Result<&'static Config> {
     })
 }
 
-pub fn instantiate_alpm_agent(config: &Global, remotes: &AlpmConfigData, transflags: &TransactionFlags) -> Alpm {
+pub fn override_paths(cachedir: Option<&str>, dbpath: Option<&str>) -> Result<()> {
+    for (path, cell) in [(cachedir, &CACHE_DIR_OVERRIDE), (dbpath, &DB_PATH_OVERRIDE)] {
+        if let Some(path) = path {
+            if !path.starts_with('/') {
+                err!(ErrorKind::Message("Path overrides must be absolute."))?
+            }
+
+            create_dir_all(path).prepend_io(|| path.into())?;
+            cell.get_or_init(|| path.into());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn override_download_rate(rate: u64) {
+    DOWNLOAD_RATE_OVERRIDE.get_or_init(|| rate);
+}
+
+/// Maximum download rate in KiB/s, wherein 0 denotes unthrottled downloads.
+pub fn download_rate() -> Result<u64> {
+    Ok(match DOWNLOAD_RATE_OVERRIDE.get() {
+        Some(rate) => *rate,
+        None => global()?.alpm().max_download_rate(),
+    })
+}
+
+pub fn cache_dir(insvars: &ContainerVariables) -> &str {
+    CACHE_DIR_OVERRIDE.get().map_or(insvars.pacman_cache(), |a| a.as_str())
+}
+
+/// Resolve the container's supplementary alpm hook directory, which is relative to the container root
+/// unless an absolute path on the host is specified.
+pub fn alpm_hook_dir(inshandle: &ContainerHandle) -> Option<String> {
+    let path = inshandle.config().alpm_hook_dir()?;
+    let path = match path.starts_with('/') {
+        true => path.into(),
+        false => format!("{}/{}", inshandle.vars().root(), path),
+    };
+
+    if !Path::new(&path).is_dir() {
+        print_warning(&format!("Container {}: Hook directory '{path}' not found; skipping.", inshandle.vars().instance()));
+        return None;
+    }
+
+    Some(path)
+}
+
+pub fn architecture<'a>(inshandle: &'a ContainerHandle) -> &'a str {
+    inshandle.config().architecture().unwrap_or(ARCH)
+}
+
+/// Parse a snapshot date, in the form of YYYY-MM-DD, into its path upon the Arch Linux Archive.
+pub fn snapshot_path(date: &str) -> Option<String> {
+    let date = Date::parse(date, format_description!("[year]-[month]-[day]")).ok()?;
+
+    Some(format!("{:04}/{:02}/{:02}", date.year(), date.month() as u8, date.day()))
+}
+
+/// Resolve the Arch Linux Archive snapshot the container is pinned to, if any.
+pub fn snapshot(inshandle: &ContainerHandle) -> Result<Option<String>> {
+    match inshandle.config().snapshot_date() {
+        Some(_) if architecture(inshandle) != ARCHIVE_ARCH =>
+            err!(SyncError::SnapshotUnsupported(inshandle.vars().instance().into(), architecture(inshandle).into())),
+        Some(date) => match snapshot_path(date) {
+            Some(path) => Ok(Some(path)),
+            None => err!(SyncError::InvalidSnapshot(inshandle.vars().instance().into(), date.into())),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Resolve the database path of the container beneath the overridden base directory, if any, such that
+/// each container retains a database of its own.
+pub fn db_path_override(insvars: &ContainerVariables) -> Result<Option<String>> {
+    let path = match DB_PATH_OVERRIDE.get() {
+        Some(path) => format!("{path}/{}", insvars.instance()),
+        None => return Ok(None),
+    };
+
+    create_dir_all(&path).prepend_io(|| path.clone())?;
+    Ok(Some(path))
+}
+
+fn container_db_path(insvars: &ContainerVariables) -> Result<String> {
+    Ok(match db_path_override(insvars)? {
+        Some(path) => format!("{path}/"),
+        None => format!("{}/var/lib/pacman/", insvars.root()),
+    })
+}
+
+pub fn repo_cachedirs<'a>(config: &'a Global, remotes: &'a AlpmConfigData) -> Vec<(&'a str, &'a str)> {
+    remotes
+        .repos
+        .iter()
+        .filter_map(|a| config.alpm().repo_cache(a.name()).map(|path| (a.name(), path)))
+        .collect()
+}
+
+pub fn instantiate_alpm_agent(
+    config: &Global,
+    remotes: &AlpmConfigData,
+    transflags: &TransactionFlags,
+    arch: &str,
+    snapshot: Option<&str>,
+) -> Alpm {
     let mut handle = Alpm::new("/mnt/fs", "/mnt/fs/var/lib/pacman/").expect("Unable to acquire ALPM handle");
-    let hook_dirs = ["/mnt/fs/usr/share/libalpm/hooks/", "/mnt/fs/etc/pacman.d/hooks/"];
+    let mut hook_dirs = vec!["/mnt/fs/usr/share/libalpm/hooks/", "/mnt/fs/etc/pacman.d/hooks/"];
     let debug = transflags.intersects(TransactionFlags::DEBUG);
     let disable_sandbox = config.alpm().disable_sandbox() || transflags.intersects(TransactionFlags::NO_ALPM_SANDBOX);
+    let mut cache_dirs = vec!["/mnt/share/cache".to_string()];
 
-    if debug {
-        handle.set_log_cb(*UNIX_TIMESTAMP as usize, alpm_log_callback);
+    if let Some(threshold) = log_threshold(debug) {
+        handle.set_log_cb((*UNIX_TIMESTAMP as usize, threshold), alpm_log_callback);
     }
 
     if disable_sandbox {
@@ -220,19 +463,28 @@ pub fn instantiate_alpm_agent(config: &Global, remotes: &AlpmConfigData, transfl
         handle.set_sandbox_user(None::<&str>).expect("set sandbox user");
     }
 
+    if Path::new(AGENT_HOOK_DIR).is_dir() {
+        hook_dirs.push(AGENT_HOOK_DIR);
+    }
+
+    cache_dirs.extend(repo_cachedirs(config, remotes).iter().map(|a| format!("/mnt/share/repo/{}", a.0)));
+    handle.add_architecture(arch).expect("set architecture");
     handle.set_logfile("/mnt/share/pacwrap.log").expect("set logfile");
     handle.set_hookdirs(hook_dirs.iter()).expect("set hookdirs");
     handle.set_gpgdir("/mnt/share/gnupg").expect("set gpgdir");
-    handle.set_cachedirs(["/mnt/share/cache"].iter()).expect("set cachedirs");
+    handle.set_cachedirs(cache_dirs.iter()).expect("set cachedirs");
     handle.set_parallel_downloads(config.alpm().parallel_downloads());
     handle.set_disable_dl_timeout(config.alpm().download_timeout());
     handle.set_check_space(false);
-    handle = register_remote(handle, remotes);
+    handle = register_remote(handle, remotes, transflags.contains(TransactionFlags::OFFLINE), arch, snapshot);
     handle
 }
 
 pub fn instantiate_alpm(inshandle: &ContainerHandle, transflags: &TransactionFlags) -> Result<Alpm> {
-    alpm_handle(inshandle.vars(), alpm_config()?, transflags, format!("{}/var/lib/pacman/", inshandle.vars().root()))
+    let db_path = container_db_path(inshandle.vars())?;
+    let snapshot = snapshot(inshandle)?;
+
+    alpm_handle(inshandle.vars(), alpm_config()?, transflags, db_path, architecture(inshandle), snapshot.as_deref())
 }
 
 fn alpm_handle(
@@ -240,14 +492,17 @@ fn alpm_handle(
     remotes: &AlpmConfigData,
     transflags: &TransactionFlags,
     db_path: String,
+    arch: &str,
+    snapshot: Option<&str>,
 ) -> Result<Alpm> {
     let config = global()?;
     let mut handle = Alpm::new(insvars.root(), &db_path).expect("Unable to acquire ALPM handle");
     let debug = transflags.intersects(TransactionFlags::DEBUG);
     let disable_sandbox = config.alpm().disable_sandbox() || transflags.intersects(TransactionFlags::NO_ALPM_SANDBOX);
+    let mut cache_dirs = vec![cache_dir(insvars).to_string()];
 
-    if debug {
-        handle.set_log_cb(*UNIX_TIMESTAMP as usize, alpm_log_callback);
+    if let Some(threshold) = log_threshold(debug) {
+        handle.set_log_cb((*UNIX_TIMESTAMP as usize, threshold), alpm_log_callback);
     }
 
     if disable_sandbox {
@@ -255,24 +510,34 @@ fn alpm_handle(
         handle.set_sandbox_user(None::<&str>).expect("set sandbox user");
     }
 
+    cache_dirs.extend(repo_cachedirs(config, remotes).iter().map(|a| a.1.to_string()));
+    handle.add_architecture(arch).expect("set architecture");
     handle.set_logfile(format!("{}/pacwrap.log", *DATA_DIR)).expect("set logfile");
     handle.set_gpgdir(format!("{}/pacman/gnupg", *DATA_DIR)).expect("set gpgdir");
-    handle.set_cachedirs([format!("{}/pkg", *CACHE_DIR)].iter()).expect("set cachedirs");
+    handle.set_cachedirs(cache_dirs.iter()).expect("set cachedirs");
     handle.set_parallel_downloads(config.alpm().parallel_downloads());
     handle.set_disable_dl_timeout(config.alpm().download_timeout());
     handle.set_check_space(global()?.alpm().check_space());
-    handle = register_remote(handle, remotes);
+    handle = register_remote(handle, remotes, transflags.contains(TransactionFlags::OFFLINE), arch, snapshot);
     Ok(handle)
 }
 
-fn alpm_log_callback(level: LogLevel, msg: &str, counter: &mut usize) {
+fn log_threshold(debug: bool) -> Option<u32> {
+    match verbosity::level() {
+        verbosity::ALPM .. => Some(3),
+        _ if debug => Some(2),
+        verbosity::WARNINGS => Some(1),
+        _ => None,
+    }
+}
+
+fn alpm_log_callback(level: LogLevel, msg: &str, (counter, threshold): &mut (usize, u32)) {
     let now = unix_epoch_time();
     let time = now.as_secs() as usize - *counter;
     let nano = now.subsec_nanos().to_string();
     let log_level = level.bits() / 4;
-    let verbosity = if *VERBOSE { 3 } else { 2 };
 
-    if log_level < verbosity {
+    if log_level < *threshold {
         eprint!("[{}.{:.6}] [ALPM] {}", time, nano, msg);
     }
 }
@@ -294,6 +559,8 @@ pub fn instantiate_container<'a>(handle: &'a ContainerHandle<'a>) -> Result<()>
         if !Path::new(home).exists() {
             create_dir(home).prepend_io(|| home.into())?;
         }
+
+        copy_skeleton(home)?;
     }
 
     if let Base | Slice = container_type {
@@ -307,6 +574,43 @@ pub fn instantiate_container<'a>(handle: &'a ContainerHandle<'a>) -> Result<()>
     handle.save()
 }
 
+/// Populate the container's home directory from the configured skeleton, akin to /etc/skel, leaving
+/// files already present untouched.
+fn copy_skeleton(home: &str) -> Result<()> {
+    let Some(skel) = global()?.config().home_skeleton() else {
+        return Ok(());
+    };
+    let skel = match skel.starts_with('/') {
+        true => skel.into(),
+        false => format!("{}/{skel}", *CONFIG_DIR),
+    };
+
+    if !Path::new(&skel).is_dir() {
+        print_warning(&format!("Home skeleton '{skel}' not found; skipping."));
+        return Ok(());
+    }
+
+    for entry in WalkDir::new(&skel).min_depth(1).into_iter().filter_map(|a| a.ok()) {
+        let Ok(relative) = entry.path().strip_prefix(&skel) else {
+            continue;
+        };
+        let dest = format!("{home}/{}", relative.to_string_lossy());
+
+        if Path::new(&dest).symlink_metadata().is_ok() {
+            continue;
+        }
+
+        match entry.file_type() {
+            file if file.is_dir() => create_dir(&dest),
+            file if file.is_symlink() => read_link(entry.path()).and_then(|target| symlink(target, &dest)),
+            _ => copy(entry.path(), &dest).map(|_| ()),
+        }
+        .prepend_io(|| dest.clone())?;
+    }
+
+    Ok(())
+}
+
 pub fn instantiate_trust() -> Result<()> {
     let path = &format!("{}/pacman/gnupg/", *DATA_DIR);
 
@@ -325,12 +629,39 @@ pub fn instantiate_trust() -> Result<()> {
     pacwrap_key(vec!["--populate"])
 }
 
-fn register_remote(mut handle: Alpm, config: &AlpmConfigData) -> Alpm {
+pub fn refresh_trust() -> Result<()> {
+    let instantiated = Path::new(&format!("{}/pacman/gnupg/", *DATA_DIR)).exists();
+
+    instantiate_trust()?;
+    println!("{} {}Refreshing package trust database...{}", *BAR_GREEN, *BOLD, *RESET);
+
+    if !Path::new("/usr/share/pacman/keyrings").exists() {
+        err!(SyncError::UnableToLocateKeyrings)?
+    }
+
+    //The keyring is otherwise populated upon instantiation
+    if instantiated {
+        pacwrap_key(vec!["--populate"])?;
+    }
+
+    pacwrap_key(vec!["--refresh-keys"])
+}
+
+fn register_remote(mut handle: Alpm, config: &AlpmConfigData, offline: bool, arch: &str, snapshot: Option<&str>) -> Alpm {
     for repo in &config.repos {
         let core = handle.register_syncdb_mut(repo.name(), repo.signature()).expect("Invalid syncdb");
 
-        for server in repo.mirrors() {
-            core.add_server(server).expect("Invalid mirror");
+        if !offline {
+            //Repositories absent from the Arch Linux Archive continue to be served by their mirrors.
+            let mirrors = match snapshot {
+                Some(path) if ARCHIVE_REPOS.contains(&repo.name()) =>
+                    vec![format!("{ARCHIVE_URL}/{path}/{}/os/{arch}", repo.name())],
+                _ => repo.mirrors().iter().map(|a| a.to_string()).collect(),
+            };
+
+            for server in mirrors {
+                core.add_server(server).expect("Invalid mirror");
+            }
         }
 
         core.set_usage(Usage::ALL).unwrap();
@@ -346,11 +677,11 @@ fn synchronize_database(ag: &mut TransactionAggregator, force: bool) -> Result<(
     };
     let flags = ag.flags();
     let db_path = format!("{}/pacman/", *DATA_DIR);
-    let mut handle = alpm_handle(handle.vars(), alpm_config()?, flags, db_path)?;
+    let mut handle = alpm_handle(handle.vars(), alpm_config()?, flags, db_path, architecture(handle), None)?;
 
     ag.lock()?.assert()?;
     println!("{} {}Synchronizing package databases...{}", *BAR_GREEN, *BOLD, *RESET);
-    handle.set_dl_cb(DownloadEvent::new().style(&ProgressKind::Verbose), download::event);
+    handle.set_dl_cb(DownloadEvent::new().style(&ProgressKind::Verbose).throttle(download_rate()?), download::event);
 
     if let Err(err) = handle.syncdbs_mut().update(force) {
         err!(SyncError::InitializationFailure(err.to_string()))?
@@ -360,9 +691,14 @@ fn synchronize_database(ag: &mut TransactionAggregator, force: bool) -> Result<(
     ag.lock()?.assert()?;
 
     for handle in ag.cache().filter_handle(vec![Base, Slice, Aggregate]).iter() {
+        if let Some(snapshot) = snapshot(handle)? {
+            synchronize_snapshot(ag, handle, &snapshot, force)?;
+            continue;
+        }
+
         for repo in pacman_conf()?.repos.iter() {
             let src = &format!("{}/pacman/sync/{}.db", *DATA_DIR, repo.name);
-            let dest = &format!("{}/var/lib/pacman/sync/{}.db", handle.vars().root(), repo.name);
+            let dest = &format!("{}sync/{}.db", container_db_path(handle.vars())?, repo.name);
 
             if let Err(error) = create_hard_link(src, dest).prepend(|| format!("Failed to hardlink db '{}'", dest)) {
                 error.warn();
@@ -373,6 +709,24 @@ fn synchronize_database(ag: &mut TransactionAggregator, force: bool) -> Result<(
     Ok(())
 }
 
+/// Synchronize the databases of a container pinned to an Arch Linux Archive snapshot against the snapshot,
+/// in lieu of the databases shared amongst containers synchronized against live mirrors.
+fn synchronize_snapshot(ag: &TransactionAggregator, inshandle: &ContainerHandle, snapshot: &str, force: bool) -> Result<()> {
+    let instance = inshandle.vars().instance();
+    let date = inshandle.config().snapshot_date().unwrap_or_default();
+    let db_path = container_db_path(inshandle.vars())?;
+    let mut handle = alpm_handle(inshandle.vars(), alpm_config()?, ag.flags(), db_path, architecture(inshandle), Some(snapshot))?;
+
+    println!("{} {}Synchronizing {instance}'s package databases against snapshot {date}...{}", *BAR_GREEN, *BOLD, *RESET);
+    handle.set_dl_cb(DownloadEvent::new().style(&ProgressKind::Verbose).throttle(download_rate()?), download::event);
+
+    if let Err(err) = handle.syncdbs_mut().update(force) {
+        err!(SyncError::SnapshotUnavailable(instance.into(), date.into(), err.to_string()))?
+    }
+
+    handle.release().generic()
+}
+
 fn signature(sigs: &Vec<String>, default: SigLevel) -> SigLevel {
     if sigs.is_empty() {
         return default;
@@ -381,21 +735,30 @@ fn signature(sigs: &Vec<String>, default: SigLevel) -> SigLevel {
     let mut sig = SigLevel::empty();
 
     for level in sigs {
-        sig |= match level.as_ref() {
-            "TrustAll" => SigLevel::DATABASE_UNKNOWN_OK | SigLevel::PACKAGE_UNKNOWN_OK,
-            "DatabaseTrustAll" => SigLevel::DATABASE_UNKNOWN_OK | SigLevel::PACKAGE_MARGINAL_OK,
-            "PackageTrustAll" => SigLevel::PACKAGE_UNKNOWN_OK | SigLevel::DATABASE_MARGINAL_OK,
-            "DatabaseRequired" | "DatabaseTrustedOnly" => SigLevel::DATABASE,
-            "PackageRequired" | "Required" => SigLevel::PACKAGE,
-            "PackageOptional" => SigLevel::PACKAGE_OPTIONAL,
-            "DatabaseOptional" => SigLevel::DATABASE_OPTIONAL,
-            _ => SigLevel::empty(),
-        }
+        sig |= signature_level(level).unwrap_or(SigLevel::empty())
     }
 
     sig
 }
 
+fn signature_level(level: &str) -> Option<SigLevel> {
+    Some(match level {
+        "TrustAll" => SigLevel::DATABASE_UNKNOWN_OK | SigLevel::PACKAGE_UNKNOWN_OK,
+        "DatabaseTrustAll" => SigLevel::DATABASE_UNKNOWN_OK | SigLevel::PACKAGE_MARGINAL_OK,
+        "PackageTrustAll" => SigLevel::PACKAGE_UNKNOWN_OK | SigLevel::DATABASE_MARGINAL_OK,
+        "DatabaseRequired" | "DatabaseTrustedOnly" => SigLevel::DATABASE,
+        "PackageRequired" | "Required" => SigLevel::PACKAGE,
+        "PackageOptional" => SigLevel::PACKAGE_OPTIONAL,
+        "DatabaseOptional" => SigLevel::DATABASE_OPTIONAL,
+        "Optional" | "Never" | "PackageNever" | "DatabaseNever" | "TrustedOnly" | "PackageTrustedOnly" => SigLevel::empty(),
+        _ => None?,
+    })
+}
+
+pub fn is_valid_signature(level: &str) -> bool {
+    signature_level(level).is_some()
+}
+
 fn default_signature() -> SigLevel {
     signature(&global().expect("pacwrap.yml").alpm().sig_level(), SigLevel::PACKAGE | SigLevel::DATABASE_OPTIONAL)
 }
@@ -416,3 +779,23 @@ fn load_pacman_conf() -> Result<Config> {
         }
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn snapshot_path_valid() {
+        assert_eq!(snapshot_path("2024-03-05").as_deref(), Some("2024/03/05"));
+        assert_eq!(snapshot_path("2023-12-31").as_deref(), Some("2023/12/31"));
+    }
+
+    #[test]
+    fn snapshot_path_invalid() {
+        assert_eq!(snapshot_path("2024-02-30"), None);
+        assert_eq!(snapshot_path("2024-13-01"), None);
+        assert_eq!(snapshot_path("20240305"), None);
+        assert_eq!(snapshot_path("2024/03/05"), None);
+        assert_eq!(snapshot_path(""), None);
+    }
+}
@@ -21,6 +21,7 @@ pub mod download;
 pub mod progress;
 pub mod query;
 pub mod summary;
+pub mod trace;
 
 fn whitespace(total: usize, current: usize) -> String {
     " ".repeat(log10(total) - log10(current))
@@ -17,7 +17,11 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    thread::sleep,
+    time::{Duration, Instant},
+};
 
 use alpm::{AnyDownloadEvent, DownloadEvent as Event, DownloadResult};
 use dialoguer::console::Term;
@@ -29,9 +33,10 @@ use crate::{
     config::global::ProgressKind,
     constants::{ARROW_CYAN, BOLD, RESET},
     sync::transaction::TransactionMode,
+    utils::status::{self, Status},
 };
 
-use super::whitespace;
+use super::{trace, whitespace};
 
 lazy_static! {
     static ref INIT: ProgressStyle = ProgressStyle::with_template(" {spinner:.green} {msg}").unwrap();
@@ -45,6 +50,15 @@ lazy_static! {
         .tick_strings(&[" ", "✓"]);
 }
 
+/// Paces downloads to a maximum rate by stalling the download callback, which libalpm invokes synchronously
+/// from its downloader, whereupon the transfer itself is held back.
+struct Throttle {
+    rate: u64,
+    start: Instant,
+    transferred: u64,
+    received: HashMap<String, u64>,
+}
+
 pub struct DownloadEvent {
     total: usize,
     position: usize,
@@ -53,6 +67,7 @@ pub struct DownloadEvent {
     progress: MultiProgress,
     bars: HashMap<String, ProgressBar>,
     style: Option<ProgressStyle>,
+    throttle: Option<Throttle>,
 }
 
 impl Default for DownloadEvent {
@@ -71,9 +86,24 @@ impl DownloadEvent {
             progress: MultiProgress::new(),
             bars: HashMap::new(),
             style: None,
+            throttle: None,
         }
     }
 
+    /// Maximum download rate in KiB/s, shared amongst parallel downloads; a rate of 0 leaves downloads unthrottled.
+    pub fn throttle(mut self, rate: u64) -> Self {
+        self.throttle = match rate {
+            0 => None,
+            rate => Some(Throttle {
+                rate: rate * 1024,
+                start: Instant::now(),
+                transferred: 0,
+                received: HashMap::new(),
+            }),
+        };
+        self
+    }
+
     pub fn style(mut self, kind: &ProgressKind) -> Self {
         self.style = match kind {
             ProgressKind::Simple => None,
@@ -163,13 +193,50 @@ impl DownloadEvent {
         pb.set_message(message(file));
         self.bars.insert(file.to_owned(), pb);
     }
+
+    fn pace(&mut self, file: &str, download: &AnyDownloadEvent) {
+        let throttle = match self.throttle.as_mut() {
+            Some(throttle) => throttle,
+            None => return,
+        };
+
+        match download.event() {
+            Event::Progress(progress) => {
+                let downloaded = progress.downloaded.unsigned_abs();
+                let received = throttle.received.insert(file.to_owned(), downloaded).unwrap_or(0);
+
+                if throttle.transferred == 0 {
+                    throttle.start = Instant::now();
+                }
+
+                throttle.transferred += downloaded.saturating_sub(received);
+            }
+            Event::Completed(_) | Event::Retry(_) => {
+                throttle.received.remove(file);
+                return;
+            }
+            Event::Init(_) => return,
+        }
+
+        let expected = Duration::from_secs_f64(throttle.transferred as f64 / throttle.rate as f64);
+        let elapsed = throttle.start.elapsed();
+
+        if expected > elapsed {
+            sleep(expected - elapsed);
+        }
+    }
 }
 
 pub fn simple(file: &str, download: AnyDownloadEvent, this: &mut DownloadEvent) {
+    trace::record("download", format_args!("{:?} '{file}'", download.event()));
+    this.pace(file, &download);
+
     if file.ends_with(".sig") {
         return;
     }
 
+    report(file, &download);
+
     if let Event::Completed(progress) = download.event() {
         this.position += 1;
 
@@ -187,13 +254,19 @@ pub fn simple(file: &str, download: AnyDownloadEvent, this: &mut DownloadEvent)
 }
 
 pub fn event(file: &str, download: AnyDownloadEvent, this: &mut DownloadEvent) {
+    trace::record("download", format_args!("{:?} '{file}'", download.event()));
+    this.pace(file, &download);
+
     if file.ends_with(".sig") {
         return;
     }
 
+    report(file, &download);
+
     match download.event() {
         Event::Progress(progress) =>
             if let Some(pb) = this.bars.get_mut(file) {
+                //Progress of a resumed download is inclusive of the partial retained, as reported by libalpm.
                 if pb.length().unwrap() == 0 {
                     pb.set_length(progress.total.unsigned_abs());
                     pb.set_style(this.style.as_ref().unwrap().clone());
@@ -238,6 +311,22 @@ pub fn callback(progress: &ProgressKind) -> for<'a, 'b, 'c> fn(file: &'a str, An
     }
 }
 
+fn report(file: &str, download: &AnyDownloadEvent) {
+    if let Event::Completed(progress) = download.event() {
+        let result = match progress.result {
+            DownloadResult::Success => "success",
+            DownloadResult::UpToDate => "up_to_date",
+            DownloadResult::Failed => "failed",
+        };
+
+        status::emit(Status::PackageDownloaded {
+            file,
+            bytes: progress.total.unsigned_abs(),
+            result,
+        });
+    }
+}
+
 fn message(filename: &str) -> String {
     let name: Vec<&str> = filename.split(".pkg.tar.").collect();
     let mut msg_name: String = name[0].to_string();
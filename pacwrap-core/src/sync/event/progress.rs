@@ -27,7 +27,7 @@ use crate::{
     config::global::ProgressKind,
     constants::{ARROW_CYAN, BOLD, RESET},
     sync::{
-        event::whitespace,
+        event::{trace, whitespace},
         transaction::{TransactionMode, TransactionType},
     },
 };
@@ -94,6 +94,11 @@ impl ProgressEvent {
 }
 
 pub fn event(event: Event, pkgname: &str, percent: i32, howmany: usize, current: usize, this: &mut ProgressEvent) {
+    trace_event(event, pkgname, percent, howmany, current);
+    advance(event, pkgname, percent, howmany, current, this)
+}
+
+fn advance(event: Event, pkgname: &str, percent: i32, howmany: usize, current: usize, this: &mut ProgressEvent) {
     let ident = ident(event, pkgname);
     let progress = match this.bars.get(ident) {
         Some(progress) => progress,
@@ -112,7 +117,9 @@ pub fn event(event: Event, pkgname: &str, percent: i32, howmany: usize, current:
     }
 }
 
-pub fn simple(kind: Event, pkgname: &str, _: i32, howmany: usize, current: usize, this: &mut ProgressEvent) {
+pub fn simple(kind: Event, pkgname: &str, percent: i32, howmany: usize, current: usize, this: &mut ProgressEvent) {
+    trace_event(kind, pkgname, percent, howmany, current);
+
     if let Some(pkg) = this.current.as_deref() {
         if ident(kind, pkgname) != pkg {
             this.current = None;
@@ -131,6 +138,8 @@ pub fn simple(kind: Event, pkgname: &str, _: i32, howmany: usize, current: usize
 }
 
 pub fn condensed(kind: Event, pkgname: &str, percent: i32, howmany: usize, current: usize, this: &mut ProgressEvent) {
+    trace_event(kind, pkgname, percent, howmany, current);
+
     if let Event::AddStart | Event::RemoveStart | Event::UpgradeStart = kind {
         let pos = current + this.offset;
         let total = howmany + this.offset;
@@ -160,10 +169,14 @@ pub fn condensed(kind: Event, pkgname: &str, percent: i32, howmany: usize, curre
             progress.finish();
         }
     } else {
-        event(kind, pkgname, percent, howmany, current, this)
+        advance(kind, pkgname, percent, howmany, current, this)
     }
 }
 
+fn trace_event(kind: Event, pkgname: &str, percent: i32, howmany: usize, current: usize) {
+    trace::record("progress", format_args!("{kind:?} '{pkgname}' {percent}% ({current}/{howmany})"));
+}
+
 fn name(progress: Event, pkgname: &str) -> String {
     match progress {
         Event::UpgradeStart => format!("Upgrading {}", pkgname),
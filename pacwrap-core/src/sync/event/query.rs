@@ -21,18 +21,19 @@ use std::path::Path;
 
 use alpm::{AnyQuestion, Question::*};
 
-use crate::{utils::prompt::prompt, ErrorGeneric};
+use crate::{sync::event::trace, utils::prompt::prompt, ErrorGeneric};
 
 pub fn callback(question: AnyQuestion, _: &mut ()) {
+    trace::record("question", format_args!("{:?}", question.question_type()));
+
     match question.question() {
         Conflict(mut x) => {
             let pkg_a = x.conflict().package1().name();
             let pkg_b = x.conflict().package2().name();
             let prompt_string = format!("Conflict between {pkg_a} and {pkg_b}; Remove {pkg_b}?");
 
-            match prompt("->", prompt_string, false).generic() {
-                Ok(bool) => x.set_remove(bool),
-                Err(err) => err.error(),
+            if let Some(answer) = answer(prompt_string) {
+                x.set_remove(answer);
             }
         }
         Replace(x) => {
@@ -40,9 +41,8 @@ pub fn callback(question: AnyQuestion, _: &mut ()) {
             let new = x.newpkg().name();
             let prompt_string = format!("Replace package {old} with {new}?");
 
-            match prompt("->", prompt_string, false).generic() {
-                Ok(bool) => x.set_replace(bool),
-                Err(err) => err.error(),
+            if let Some(answer) = answer(prompt_string) {
+                x.set_replace(answer);
             }
         }
         Corrupted(mut x) => {
@@ -51,9 +51,8 @@ pub fn callback(question: AnyQuestion, _: &mut ()) {
             let reason = x.reason();
             let prompt_string = format!("'{filename}': {reason}. Remove package?");
 
-            match prompt("->", prompt_string, false).generic() {
-                Ok(bool) => x.set_remove(bool),
-                Err(err) => err.error(),
+            if let Some(answer) = answer(prompt_string) {
+                x.set_remove(answer);
             }
         }
         ImportKey(mut x) => {
@@ -61,11 +60,23 @@ pub fn callback(question: AnyQuestion, _: &mut ()) {
             let name = x.uid();
             let prompt_string = format!("Import key {fingerprint}, \"{name}\" to keyring?");
 
-            match prompt("->", prompt_string, false).generic() {
-                Ok(bool) => x.set_import(bool),
-                Err(err) => err.error(),
+            if let Some(answer) = answer(prompt_string) {
+                x.set_import(answer);
             }
         }
         _ => (),
     }
 }
+
+fn answer(prompt_string: String) -> Option<bool> {
+    match prompt("->", prompt_string, false).generic() {
+        Ok(answer) => {
+            trace::record("question", format_args!("Answered {answer}"));
+            Some(answer)
+        }
+        Err(err) => {
+            err.error();
+            None
+        }
+    }
+}
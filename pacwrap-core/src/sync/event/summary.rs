@@ -17,20 +17,34 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use std::fmt::{Display, Error as FmtError, Formatter};
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::OnceLock,
+};
 
-use alpm::Alpm;
+use alpm::{vercmp, Alpm};
 use dialoguer::console::Term;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use simplebyteunit::simplebyteunit::*;
 
 use crate::{
     constants::{BOLD, DIM, RESET},
+    err,
     sync::transaction::TransactionMode,
-    utils::table::{ColumnAttribute, Table},
+    utils::{
+        arguments::InvalidArgument,
+        porcelain::{self, Record},
+        table::{ColumnAttribute, Table},
+    },
+    Error,
 };
 
-#[derive(Copy, Clone, Serialize, Deserialize)]
+static SUMMARY_OVERRIDE: OnceLock<SummaryKind> = OnceLock::new();
+
+#[derive(Copy, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum SummaryKind {
     Sum,
     Basic,
@@ -38,6 +52,8 @@ pub enum SummaryKind {
     SumForeign,
     BasicForeign,
     TableForeign,
+    Json,
+    JsonForeign,
 }
 
 pub struct Summary {
@@ -48,9 +64,11 @@ pub struct Summary {
     elements: Vec<(String, String, String, i64, i64)>,
     installed: i64,
     net_installed: i64,
+    marginal: i64,
     removed: i64,
     download_size: i64,
     download_files: u64,
+    downstream: Vec<String>,
     kind: SummaryKind,
 }
 
@@ -82,6 +100,8 @@ impl Summary {
             download_size: 0,
             download_files: 0,
             net_installed: 0,
+            marginal: 0,
+            downstream: Vec::new(),
             kind: SummaryKind::default(),
         }
     }
@@ -91,12 +111,20 @@ impl Summary {
         self
     }
 
+    pub fn downstream(mut self, containers: Vec<&str>) -> Self {
+        self.downstream = containers.iter().map(|a| a.to_string()).collect();
+        self
+    }
+
     pub fn kind(mut self, kind: &SummaryKind, database_only: bool) -> Self {
+        let kind = SUMMARY_OVERRIDE.get().unwrap_or(kind);
+
         self.kind = match database_only {
             true => match kind {
                 SummaryKind::Table => SummaryKind::TableForeign,
                 SummaryKind::Basic => SummaryKind::BasicForeign,
                 SummaryKind::Sum => SummaryKind::SumForeign,
+                SummaryKind::Json => SummaryKind::JsonForeign,
                 _ => *kind,
             },
             false => *kind,
@@ -113,6 +141,7 @@ impl Summary {
             let removed = pkg.isize();
 
             self.removed += removed;
+            self.marginal -= removed;
             self.elements.push((pkg.name().into(), pkg.version().to_string(), "".into(), removed, 0));
         }
 
@@ -135,6 +164,10 @@ impl Summary {
 
             self.installed += installed_old;
             self.net_installed += this_net;
+            self.marginal += match is_installed {
+                true => this_net,
+                false => installed_old,
+            };
             self.elements.push((
                 pkg_this.name().into(),
                 match is_installed {
@@ -159,6 +192,28 @@ impl Summary {
         self
     }
 
+    pub fn porcelain(&self, instance: &str) {
+        if let TransactionMode::Foreign = self.mode {
+            return;
+        }
+
+        for (name, old, new, ..) in self.elements.iter() {
+            porcelain::emit(match (old.as_str(), new.as_str()) {
+                ("", new) => Record::Install(instance, name, new),
+                (old, "") => Record::Remove(instance, name, old),
+                (old, new) => match vercmp(old, new) {
+                    Ordering::Less => Record::Upgrade(instance, name, old, new),
+                    Ordering::Greater => Record::Downgrade(instance, name, old, new),
+                    Ordering::Equal => Record::Reinstall(instance, name, new),
+                },
+            });
+        }
+    }
+
+    pub fn marginal(&self) -> i64 {
+        self.marginal
+    }
+
     pub fn download(&self) -> (u64, u64) {
         (self.download_size as u64, self.download_files)
     }
@@ -243,8 +298,39 @@ impl Summary {
         self.footer(fmt)
     }
 
+    fn json(&self, fmt: &mut Formatter<'_>) -> Result<(), FmtError> {
+        let packages: Vec<_> = self
+            .elements
+            .iter()
+            .map(|(name, old, new, net, dnl)| {
+                json!({
+                    "name": name,
+                    "old_version": old,
+                    "new_version": new,
+                    "net_change": net,
+                    "download_size": dnl,
+                })
+            })
+            .collect();
+        let summary = json!({
+            "packages": packages,
+            "installed_size": self.installed,
+            "removed_size": self.removed,
+            "download_size": self.download_size,
+            "net_upgrade_size": self.net_installed,
+            "downstream": self.downstream,
+        });
+
+        writeln!(fmt, "{summary}")
+    }
+
     fn footer(&self, fmt: &mut Formatter<'_>) -> Result<(), FmtError> {
-        if self.installed != 0 || self.removed != 0 || self.download_size != 0 || self.net_installed != 0 {
+        if self.installed != 0
+            || self.removed != 0
+            || self.download_size != 0
+            || self.net_installed != 0
+            || !self.downstream.is_empty()
+        {
             writeln!(fmt)?;
         }
 
@@ -264,6 +350,10 @@ impl Summary {
             writeln!(fmt, "{}Net Upgrade Size{}: {}", *BOLD, *RESET, self.net_installed.to_byteunit(IEC))?;
         }
 
+        if !self.downstream.is_empty() {
+            writeln!(fmt, "{}Relinked Downstream ({}){}: {}", *BOLD, self.downstream.len(), *RESET, self.downstream.join(" "))?;
+        }
+
         Ok(())
     }
 }
@@ -274,6 +364,7 @@ impl Display for Summary {
             (SummaryKind::Basic, TransactionMode::Local) | (SummaryKind::BasicForeign, _) => self.basic(fmt),
             (SummaryKind::Table, TransactionMode::Local) | (SummaryKind::TableForeign, _) => self.table(fmt),
             (SummaryKind::Sum, TransactionMode::Local) | (SummaryKind::SumForeign, _) => self.footer(fmt),
+            (SummaryKind::Json, TransactionMode::Local) | (SummaryKind::JsonForeign, _) => self.json(fmt),
             (_, TransactionMode::Foreign) => Ok(()),
         }
     }
@@ -285,6 +376,36 @@ impl Default for SummaryKind {
     }
 }
 
+impl SummaryKind {
+    fn from_arg(format: &str) -> Option<Self> {
+        Some(match format {
+            "sum" => Self::Sum,
+            "basic" => Self::Basic,
+            "table" => Self::Table,
+            "json" => Self::Json,
+            "sum-foreign" => Self::SumForeign,
+            "basic-foreign" => Self::BasicForeign,
+            "table-foreign" => Self::TableForeign,
+            "json-foreign" => Self::JsonForeign,
+            _ => return None,
+        })
+    }
+}
+
+/// Override the configured summary kind for the duration of this invocation.
+pub fn override_summary(format: &str) -> crate::Result<()> {
+    match SummaryKind::from_arg(format) {
+        Some(kind) => match SUMMARY_OVERRIDE.set(kind) {
+            Ok(()) => Ok(()),
+            Err(_) => err!(InvalidArgument::UnsuppliedOperand("--summary-format", "Summary format specified more than once.")),
+        },
+        None => err!(InvalidArgument::UnsuppliedOperand(
+            "--summary-format",
+            "Valid formats are 'sum', 'basic', 'table', 'json', 'sum-foreign', 'basic-foreign', 'table-foreign', and 'json-foreign'."
+        )),
+    }
+}
+
 impl TableColumns {
     fn header<'a>(&self, preface: &'a str) -> Vec<&'a str> {
         match self {
@@ -0,0 +1,56 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+    fmt::Arguments,
+    fs::{File, OpenOptions},
+    io::Write,
+    sync::{Mutex, OnceLock},
+};
+
+use crate::{utils::unix_epoch_time, ErrorGeneric, Result};
+
+static TRACE: OnceLock<Mutex<File>> = OnceLock::new();
+
+/// Open the trace file, to which every alpm callback event is recorded hereafter. The file is truncated
+/// by the host upon each traced invocation, and appended to by the agent thereafter.
+pub fn instantiate(path: &str, truncate: bool) -> Result<()> {
+    let file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(!truncate)
+        .truncate(truncate)
+        .open(path)
+        .prepend_io(|| path.into())?;
+
+    TRACE.get_or_init(|| Mutex::new(file));
+    Ok(())
+}
+
+pub fn record(kind: &str, args: Arguments) {
+    let file = match TRACE.get() {
+        Some(file) => file,
+        None => return,
+    };
+    let now = unix_epoch_time();
+
+    if let Ok(mut file) = file.lock() {
+        let _ = writeln!(file, "[{}.{:06}] [{kind}] {args}", now.as_secs(), now.subsec_micros());
+    }
+}
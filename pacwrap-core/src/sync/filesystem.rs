@@ -28,13 +28,18 @@ use std::{
         mpsc::{self, Receiver, Sender},
         Arc,
     },
+    thread,
+    time::Duration,
 };
 
 use bincode::Options;
 use dialoguer::console::Term;
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use lazy_static::lazy_static;
+use nix::errno::Errno;
 use rayon::{prelude::*, ThreadPool, ThreadPoolBuilder};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use signal_hook::iterator::Signals;
@@ -43,7 +48,7 @@ use zstd::{Decoder, Encoder};
 
 use crate::{
     config::{ContainerCache, ContainerHandle, ContainerType::*},
-    constants::{BAR_GREEN, BOLD, DATA_DIR, RESET, SIGNAL_LIST},
+    constants::{ARROW_GREEN, BAR_GREEN, BOLD, DATA_DIR, RESET, SIGNAL_LIST},
     err,
     impl_error,
     lock::{Lock, LockError},
@@ -51,7 +56,12 @@ use crate::{
         transaction::aggregator::{BAR_CYAN_STYLE, BAR_GREEN_STYLE},
         SyncError,
     },
-    utils::bytebuffer::ByteBuffer,
+    utils::{
+        bytebuffer::ByteBuffer,
+        porcelain::{self, is_porcelain, Record},
+        print_warning,
+        status::{self, Status},
+    },
     Error,
     ErrorGeneric,
     ErrorKind,
@@ -59,18 +69,43 @@ use crate::{
     Result,
 };
 
-const VERSION: u32 = 2;
+const VERSION: u32 = 3;
 const MAGIC_NUMBER: u32 = 408948530;
 const BYTE_LIMIT: u64 = 134217728;
+const LINK_RETRIES: u32 = 3;
+const LINK_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+lazy_static! {
+    static ref SECURITY_LABEL: Option<&'static str> = security_label();
+}
 
 #[derive(Serialize, Deserialize, Clone)]
 struct FileSystemState {
     files: IndexMap<Arc<str>, (FileType, Arc<str>)>,
+    labels: IndexMap<Arc<str>, Arc<[u8]>>,
+}
+
+/// Filesystem state data prior to version 3, absent of security labels.
+#[derive(Deserialize)]
+struct LegacyState {
+    files: IndexMap<Arc<str>, (FileType, Arc<str>)>,
 }
 
 impl FileSystemState {
     fn new() -> Self {
-        Self { files: IndexMap::new() }
+        Self {
+            files: IndexMap::new(),
+            labels: IndexMap::new(),
+        }
+    }
+}
+
+impl From<LegacyState> for FileSystemState {
+    fn from(state: LegacyState) -> Self {
+        Self {
+            files: state.files,
+            labels: IndexMap::new(),
+        }
     }
 }
 
@@ -81,6 +116,7 @@ pub enum FilesystemSyncError {
     UnsupportedVersion(String, u32),
     DeserializationFailure(String, String),
     SerializationFailure(String, String),
+    InvalidExclusion(String, String),
 }
 
 impl_error!(FilesystemSyncError);
@@ -95,6 +131,7 @@ impl Display for FilesystemSyncError {
                 write!(fmter, "Deserialization failure occurred with '{}{file}{}.dat': {err}", *BOLD, *RESET),
             Self::ChecksumMismatch(file) => write!(fmter, "'{file}': Checksum mismatch"),
             Self::MagicMismatch(file, magic) => write!(fmter, "'{file}': Magic number mismatch ({MAGIC_NUMBER} != {magic})"),
+            Self::InvalidExclusion(ins, err) => write!(fmter, "Invalid path exclusion for '{}{ins}{}': {err}", *BOLD, *RESET),
         }
     }
 }
@@ -111,6 +148,14 @@ enum FileType {
 pub enum SyncType {
     Filesystem,
     RefreshState,
+    Preview,
+}
+
+#[derive(Clone, Copy, Default)]
+struct LinkPreview {
+    created: usize,
+    relinked: usize,
+    deleted: usize,
 }
 
 impl From<i8> for FileType {
@@ -136,8 +181,29 @@ impl From<Metadata> for FileType {
     }
 }
 
+pub struct Divergent {
+    path: String,
+    identical: bool,
+    relinked: bool,
+}
+
+impl Divergent {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn identical(&self) -> bool {
+        self.identical
+    }
+
+    pub fn relinked(&self) -> bool {
+        self.relinked
+    }
+}
+
 enum SyncMessage {
-    LinkComplete(Arc<str>),
+    LinkComplete(Arc<str>, usize),
+    LinkPreview(Arc<str>, LinkPreview),
     SaveState(Arc<str>, FileSystemState),
 }
 
@@ -146,6 +212,10 @@ pub struct FilesystemSync<'a> {
     state_map_prev: HashMap<Arc<str>, Option<FileSystemState>>,
     linked: HashSet<Arc<str>>,
     queued: HashSet<&'a str>,
+    excluded: HashSet<&'a str>,
+    weights: HashMap<Arc<str>, u64>,
+    previews: HashMap<Arc<str>, LinkPreview>,
+    failures: usize,
     progress: Option<ProgressBar>,
     cache: &'a ContainerCache<'a>,
     pool: Option<ThreadPool>,
@@ -163,6 +233,10 @@ impl<'a> FilesystemSync<'a> {
             state_map: HashMap::new(),
             state_map_prev: HashMap::new(),
             queued: HashSet::new(),
+            excluded: HashSet::new(),
+            weights: HashMap::new(),
+            previews: HashMap::new(),
+            failures: 0,
             linked: HashSet::new(),
             cache: inscache,
             max_chars: 0,
@@ -180,6 +254,24 @@ impl<'a> FilesystemSync<'a> {
         self.sync_type = SyncType::Filesystem;
     }
 
+    /// Compute the changes a filesystem synchronization would effect upon each container, without
+    /// modifying any container filesystem or state data.
+    pub fn preview(&mut self) {
+        self.sync_type = SyncType::Preview;
+    }
+
+    /// Exclude aggregate containers from being relinked. Upstream containers cannot be excluded, as their
+    /// state must nonetheless be obtained in order for their dependents to be linked.
+    pub fn exclude(&mut self, containers: &HashSet<&'a str>) {
+        self.excluded.extend(containers.iter().filter(|ins| {
+            self.cache.get_instance_option(ins).is_some_and(|a| matches!(a.metadata().container_type(), Aggregate))
+        }));
+    }
+
+    pub fn is_excluded(&self, ins: &str) -> bool {
+        self.excluded.contains(ins)
+    }
+
     pub fn assert_lock(mut self, lock: Option<&'a Lock>) -> Self {
         self.lock = lock;
         self
@@ -188,12 +280,26 @@ impl<'a> FilesystemSync<'a> {
     pub fn engage(&mut self, containers: &Vec<&'a str>) -> Result<()> {
         self.lock()?.assert()?;
 
+        let total = match self.progress.is_some() && !is_porcelain() {
+            true => self.weigh(containers),
+            false => None,
+        };
+
+        if let (Some(progress), Some(total)) = (self.progress.as_ref(), total) {
+            progress.set_style(progress_style(true));
+            progress.set_length(total);
+        }
+
         let (tx, rx) = self.link(containers, mpsc::channel())?;
 
         drop(tx);
         while let Ok(()) = rx.recv() {}
         self.signal()?;
-        self.place_state()
+
+        match self.sync_type {
+            SyncType::Preview => Ok(()),
+            _ => self.place_state(),
+        }
     }
 
     fn link(
@@ -204,7 +310,7 @@ impl<'a> FilesystemSync<'a> {
         let (tx, rx): (Sender<SyncMessage>, Receiver<SyncMessage>) = mpsc::channel();
 
         for ins in containers {
-            if self.queued.contains(ins) {
+            if self.queued.contains(ins) || self.excluded.contains(ins) {
                 continue;
             }
 
@@ -234,15 +340,30 @@ impl<'a> FilesystemSync<'a> {
     fn wait(&mut self, mut queue: HashSet<&'a str>, rx: Receiver<SyncMessage>, write_chan: &(Sender<()>, Receiver<()>)) {
         while let Ok(recv) = rx.recv() {
             match recv {
-                SyncMessage::LinkComplete(ins) => {
+                SyncMessage::LinkComplete(ins, failures) => {
                     if let Some(progress) = &self.progress {
                         progress.set_message(queue_status(&self.sync_type, &queue, ins.as_ref(), self.max_chars as usize));
-                        progress.inc(1);
+                        progress.inc(self.weights.get(&ins).copied().unwrap_or(1));
+                    }
+
+                    if let (SyncType::Filesystem, Some(Aggregate)) =
+                        (&self.sync_type, self.cache.get_instance_option(&ins).map(|a| a.metadata().container_type()))
+                    {
+                        porcelain::emit(Record::Link(&ins));
                     }
 
                     queue.remove(ins.as_ref());
+                    status::emit(Status::LinkProgress {
+                        container: &ins,
+                        linked: self.queued.len() - queue.len(),
+                        total: self.queued.len(),
+                    });
+                    self.failures += failures;
                     self.linked.insert(ins);
                 }
+                SyncMessage::LinkPreview(ins, preview) => {
+                    self.previews.insert(ins, preview);
+                }
                 SyncMessage::SaveState(container, fs_state) => {
                     if self.state_map.contains_key(&container) {
                         continue;
@@ -252,10 +373,14 @@ impl<'a> FilesystemSync<'a> {
                         continue;
                     }
 
-                    if let SyncType::Filesystem = self.sync_type {
+                    if let SyncType::Filesystem | SyncType::Preview = self.sync_type {
                         self.state_map.insert(container.clone(), fs_state.clone());
                     }
 
+                    if let SyncType::Preview = self.sync_type {
+                        continue;
+                    }
+
                     let tx = write_chan.0.clone();
 
                     self.pool().unwrap().spawn(move || {
@@ -269,51 +394,89 @@ impl<'a> FilesystemSync<'a> {
         }
     }
 
-    fn previous_state(&mut self, instance: &Arc<str>) -> Result<Option<FileSystemState>> {
-        if let Some(st) = self.state_map_prev.get(instance) {
-            return Ok(st.clone());
-        }
+    /// Weigh each container to be linked by the sum of the file sizes enumerated in its upstream state data,
+    /// such that progress reflects the volume of work rather than the count of containers. Yields `None`
+    /// where the state of any upstream container is unavailable, whereupon progress is counted instead.
+    fn weigh(&mut self, containers: &[&'a str]) -> Option<u64> {
+        let mut sizes = HashMap::new();
+        let mut weights = HashMap::new();
+
+        for ins in self.closure(containers) {
+            let handle = self.cache.get_instance_option(ins)?;
+            let weight = match handle.metadata().container_type() {
+                Base | Slice => self.state_size(ins, &mut sizes)?,
+                Aggregate => {
+                    let mut weight = 0;
+
+                    for dep in handle.metadata().dependencies() {
+                        weight += self.state_size(dep, &mut sizes)?;
+                    }
 
-        let path = &format!("{}/state/{}.dat", *DATA_DIR, instance);
-        let mut header = ByteBuffer::with_capacity(8).read();
-        let mut file = match File::open(path) {
-            Ok(file) => file,
-            Err(err) =>
-                if let IOErrorKind::NotFound = err.kind() {
-                    return Ok(None);
-                } else {
-                    return Err(err).prepend_io(|| path.into());
-                },
-        };
+                    weight
+                }
+                Symbolic => continue,
+            };
 
-        file.read_exact(header.as_slice_mut()).prepend_io(|| path.into())?;
+            weights.insert(Arc::from(ins), weight);
+        }
 
-        let magic = header.read_le_32();
-        let version = header.read_le_32();
+        let total = weights.values().sum();
 
-        if magic != MAGIC_NUMBER {
-            err!(FilesystemSyncError::MagicMismatch(path.into(), magic))?
-        } else if version != VERSION {
-            let state = match version {
-                1 => deserialize::<File, FileSystemState>(instance, file)?,
-                _ => err!(FilesystemSyncError::UnsupportedVersion(path.into(), version))?,
-            };
+        match total {
+            0 => None,
+            _ => {
+                self.weights = weights;
+                Some(total)
+            }
+        }
+    }
 
-            self.state_map_prev.insert(instance.clone(), Some(state.clone()));
-            Ok(Some(state))
-        } else {
-            let (state_buffer, checksum_valid) = decode_state(file).prepend_io(|| path.into())?;
+    fn closure(&self, containers: &[&'a str]) -> IndexSet<&'a str> {
+        let mut closure = IndexSet::new();
 
-            if !checksum_valid {
-                err!(FilesystemSyncError::ChecksumMismatch(path.into()))?
+        for ins in containers.iter().filter(|a| !self.excluded.contains(*a)) {
+            if let Some(handle) = self.cache.get_instance_option(ins) {
+                closure.extend(self.closure(&handle.metadata().dependencies()));
+                closure.insert(*ins);
             }
+        }
 
-            let buf_reader = BufReader::new(state_buffer.as_slice());
-            let state = deserialize::<BufReader<&[u8]>, FileSystemState>(instance, buf_reader)?;
+        closure
+    }
+
+    fn state_size(&mut self, ins: &str, sizes: &mut HashMap<Arc<str>, u64>) -> Option<u64> {
+        let instance: Arc<str> = ins.into();
 
-            self.state_map_prev.insert(instance.clone(), Some(state.clone()));
-            Ok(Some(state))
+        if let Some(size) = sizes.get(&instance) {
+            return Some(*size);
         }
+
+        let size = self
+            .previous_state(&instance)
+            .ok()??
+            .files
+            .par_iter()
+            .filter(|a| a.1 .0 == FileType::HardLink)
+            .filter_map(|a| fs::symlink_metadata(a.1 .1.as_ref()).ok())
+            .map(|a| a.len())
+            .sum();
+
+        sizes.insert(instance, size);
+        Some(size)
+    }
+
+    fn previous_state(&mut self, instance: &Arc<str>) -> Result<Option<FileSystemState>> {
+        if let Some(st) = self.state_map_prev.get(instance) {
+            return Ok(st.clone());
+        }
+
+        let state = read_state(instance)?;
+
+        if state.is_some() {
+            self.state_map_prev.insert(instance.clone(), state.clone());
+        }
+
+        Ok(state)
     }
 
     fn blank_state(&mut self, instance: &Arc<str>) -> Option<FileSystemState> {
@@ -324,6 +487,7 @@ impl<'a> FilesystemSync<'a> {
     fn obtain_slice(&mut self, inshandle: &ContainerHandle, tx: Sender<SyncMessage>) -> Result<()> {
         let instance: Arc<str> = inshandle.vars().instance().into();
         let root = inshandle.vars().root().into();
+        let exclude = exclusion(inshandle)?;
 
         if let Err(err) = self.previous_state(&instance) {
             self.blank_state(&instance);
@@ -333,10 +497,10 @@ impl<'a> FilesystemSync<'a> {
         self.pool()?.spawn(move || {
             let mut state = FileSystemState::new();
 
-            obtain_state(root, &mut state);
+            obtain_state(root, &mut state, exclude.as_ref());
 
             tx.send(SyncMessage::SaveState(instance.clone(), state)).unwrap();
-            tx.send(SyncMessage::LinkComplete(instance)).unwrap();
+            tx.send(SyncMessage::LinkComplete(instance, 0)).unwrap();
         });
         Ok(())
     }
@@ -346,6 +510,8 @@ impl<'a> FilesystemSync<'a> {
         let mut prev = Vec::new();
         let instance: Arc<str> = handle.vars().instance().into();
         let root: Arc<str> = handle.vars().root().into();
+        let exclude = exclusion(handle)?;
+        let sync_type = self.sync_type;
         let state = FileSystemState::new();
 
         for dep in handle.metadata().dependencies() {
@@ -361,18 +527,29 @@ impl<'a> FilesystemSync<'a> {
             };
 
             prev.push(prev_state);
-            map.push((dephandle.vars().root().into(), state));
+            map.push((dephandle.vars().root().into(), state, exclusion(dephandle)?));
         }
 
         self.pool()?.spawn(move || {
-            let state = filesystem_state(state, map);
-            let state_prev = previous_state(prev);
+            let mut state = filesystem_state(state, map);
+            let mut state_prev = previous_state(prev);
+
+            if let Some(exclude) = exclude {
+                state.files.retain(|path, _| !exclude.is_match(path));
+                state_prev.files.retain(|path, _| !exclude.is_match(path));
+            }
+
+            if let SyncType::Preview = sync_type {
+                tx.send(SyncMessage::LinkPreview(instance.clone(), preview_filesystem(&state, &state_prev, &root))).unwrap();
+                tx.send(SyncMessage::LinkComplete(instance, 0)).unwrap();
+                return;
+            }
 
             delete_files(&state, &state_prev, &root);
             delete_directories(&state, &state_prev, &root);
-            link_filesystem(&state, &root);
+            let failures = link_filesystem(&state, &root);
 
-            tx.send(SyncMessage::LinkComplete(instance)).unwrap();
+            tx.send(SyncMessage::LinkComplete(instance, failures)).unwrap();
         });
         Ok(())
     }
@@ -419,29 +596,92 @@ impl<'a> FilesystemSync<'a> {
         Ok(())
     }
 
+    pub fn verify(&mut self, handle: &ContainerHandle, repair: bool) -> Result<Vec<Divergent>> {
+        let mut prev = Vec::new();
+        let root = handle.vars().root();
+        let exclude = exclusion(handle)?;
+
+        if repair {
+            self.lock()?.assert()?;
+        }
+
+        for dep in handle.metadata().dependencies() {
+            prev.push(self.previous_state(&Arc::from(dep))?);
+        }
+
+        Ok(previous_state(prev)
+            .files
+            .par_iter()
+            .filter(|a| a.1 .0 == FileType::HardLink && !exclude.as_ref().is_some_and(|e| e.is_match(a.0)))
+            .filter_map(|(path, (_, src))| verify_file(src, &format!("{root}{path}"), repair))
+            .collect())
+    }
+
+    /// Enumerate paths, relative to the container root, of files local to the specified container: those
+    /// absent from the state of its upstream containers, or otherwise diverged from their upstream source.
+    pub fn local_files(&mut self, handle: &ContainerHandle) -> Result<Vec<String>> {
+        let mut prev = Vec::new();
+        let root = handle.vars().root();
+
+        for dep in handle.metadata().dependencies() {
+            prev.push(self.previous_state(&Arc::from(dep))?);
+        }
+
+        let state = previous_state(prev);
+
+        Ok(WalkDir::new(root)
+            .min_depth(1)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.path().to_str().map(|a| a.split_at(root.len()).1.to_string()))
+            .filter(|path| match state.files.get(path.as_str()) {
+                Some((FileType::Directory, _)) => false,
+                Some((kind, src)) => pending_link(kind, src, &format!("{root}{path}")).is_some(),
+                None => true,
+            })
+            .collect())
+    }
+
+    pub fn clear(&mut self, handle: &ContainerHandle) -> Result<()> {
+        let mut prev = Vec::new();
+        let root = handle.vars().root();
+        let exclude = exclusion(handle)?;
+
+        self.lock()?.assert()?;
+
+        for dep in handle.metadata().dependencies() {
+            prev.push(self.previous_state(&Arc::from(dep))?);
+        }
+
+        let mut state = previous_state(prev);
+
+        if let Some(exclude) = exclude {
+            state.files.retain(|path, _| !exclude.is_match(path));
+        }
+
+        delete_files(&FileSystemState::new(), &state, root);
+        Ok(())
+    }
+
     pub fn sync_type(&self) -> SyncType {
         self.sync_type
     }
 
     pub fn prepare(&mut self, length: usize, primary: Option<&ProgressBar>) {
-        let size = Term::size(&Term::stdout());
-        let column_half = size.1 / 2;
-        let style = ProgressStyle::with_template(
-            &(" {spinner:.green} {msg:<".to_owned() + column_half.to_string().as_str() + "} [{wide_bar}] {percent:<3}%"),
-        )
-        .unwrap()
-        .progress_chars("#-")
-        .tick_strings(&[">", "✓"]);
-        let progress = ProgressBar::new(0).with_style(style);
+        let column_half = Term::size(&Term::stdout()).1 / 2;
+        let progress = ProgressBar::new(0).with_style(progress_style(false));
 
         if let Some(progress) = primary {
             progress.set_style(BAR_GREEN_STYLE.clone());
             progress.set_message(format!("{}{}{}", *BOLD, self.sync_type.prepare(), *RESET));
-        } else {
+        } else if !is_porcelain() {
             println!("{} {}{}...{} ", *BAR_GREEN, *BOLD, self.sync_type.prepare(), *RESET);
         }
 
-        progress.set_draw_target(ProgressDrawTarget::stdout());
+        progress.set_draw_target(match is_porcelain() {
+            true => ProgressDrawTarget::hidden(),
+            false => ProgressDrawTarget::stdout(),
+        });
         progress.set_message(self.sync_type.progress());
         progress.set_position(0);
         progress.set_length(length.try_into().unwrap_or(0));
@@ -463,13 +703,46 @@ impl<'a> FilesystemSync<'a> {
             progress.finish();
         }
 
+        if let (SyncType::Preview, false) = (self.sync_type, is_porcelain()) {
+            self.print_preview();
+        }
+
+        if self.failures > 0 {
+            print_warning(&format!(
+                "{}{}{} file{} failed to link; invoke `pacwrap -Sf` to resynchronize.",
+                *BOLD,
+                self.failures,
+                *RESET,
+                if self.failures > 1 { "s" } else { "" }
+            ));
+        }
+
         self.queued.drain();
+        self.weights.drain();
+        self.previews.drain();
+        self.failures = 0;
         self.linked.drain();
         self.pool = None;
         self.progress = None;
         self.max_chars = 0;
     }
 
+    fn print_preview(&self) {
+        let mut previews: Vec<_> = self.previews.iter().collect();
+
+        previews.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (ins, preview) in previews {
+            match preview.created + preview.relinked + preview.deleted {
+                0 => println!("{} {}{ins}{} is synchronized.", *ARROW_GREEN, *BOLD, *RESET),
+                _ => println!(
+                    "{} {}{ins}{}: {} to create, {} to relink, {} to delete.",
+                    *ARROW_GREEN, *BOLD, *RESET, preview.created, preview.relinked, preview.deleted
+                ),
+            }
+        }
+    }
+
     pub fn release(self) {
         drop(self);
     }
@@ -480,6 +753,7 @@ impl SyncType {
         match self {
             Self::Filesystem => "Synchronizing container filesystems",
             Self::RefreshState => "Refreshing filesystem state data",
+            Self::Preview => "Previewing container filesystem synchronization",
         }
     }
 
@@ -487,6 +761,7 @@ impl SyncType {
         match self {
             Self::Filesystem => "Synchronizing filesystems..",
             Self::RefreshState => "Refreshing state..",
+            Self::Preview => "Computing changes..",
         }
     }
 
@@ -494,10 +769,24 @@ impl SyncType {
         match self {
             Self::Filesystem => "Synchronization complete.",
             Self::RefreshState => "Refresh complete.",
+            Self::Preview => "Preview complete.",
         }
     }
 }
 
+fn progress_style(bytes: bool) -> ProgressStyle {
+    let column_half = Term::size(&Term::stdout()).1 / 2;
+    let template = match bytes {
+        true => " {spinner:.green} {msg:<COLUMN} [{wide_bar}] {percent:<3}% {eta:<4}",
+        false => " {spinner:.green} {msg:<COLUMN} [{wide_bar}] {percent:<3}%",
+    };
+
+    ProgressStyle::with_template(&template.replace("COLUMN", &column_half.to_string()))
+        .unwrap()
+        .progress_chars("#-")
+        .tick_strings(&[">", "✓"])
+}
+
 pub fn validate_fs_states<'a>(instances: &'a Vec<&'a str>) -> bool {
     for ins in instances {
         if !match check(ins) {
@@ -518,6 +807,89 @@ pub fn create_blank_state(container: &str) -> Result<()> {
     serialize(&format!("{}/state/{}.dat", *DATA_DIR, container), FileSystemState::new())
 }
 
+/// Rewrite the stored state of the specified container, discarding entries whose source path no
+/// longer exists and which are linked upon none of the specified downstream roots. Returns the size
+/// of the state file prior to and subsequent to compaction.
+pub fn compact_state(instance: &str, downstream: &[&str]) -> Result<Option<(u64, u64)>> {
+    let path = &format!("{}/state/{}.dat", *DATA_DIR, instance);
+    let path_new = &format!("{path}.new");
+    let mut state = match read_state(instance)? {
+        Some(state) => state,
+        None => return Ok(None),
+    };
+    let size = metadata(path).prepend_io(|| path.into())?.len();
+
+    //Entries remain so long as a downstream link persists, lest it be orphaned upon the next synchronization
+    state.files.retain(|path, (_, src)| {
+        Path::new(src.as_ref()).symlink_metadata().is_ok()
+            || downstream.iter().any(|root| Path::new(&format!("{root}{path}")).symlink_metadata().is_ok())
+    });
+    state.labels.retain(|path, _| state.files.contains_key(path));
+    serialize(path_new, state)?;
+    rename(path_new, path).prepend_io(|| path_new.into())?;
+    Ok(Some((size, metadata(path).prepend_io(|| path.into())?.len())))
+}
+
+pub fn marginal_size(root: &str) -> u64 {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|meta| meta.is_file() && meta.nlink() == 1)
+        .map(|meta| meta.len())
+        .sum()
+}
+
+/// Sum the sizes of the distinct files present upon the specified roots, such that files deduplicated
+/// amongst them are counted but once.
+pub fn unique_size(roots: &[&str]) -> u64 {
+    roots
+        .par_iter()
+        .map(|root| file_sizes(root))
+        .reduce(HashMap::new, |mut a, b| {
+            a.extend(b);
+            a
+        })
+        .values()
+        .sum()
+}
+
+/// Sizes of the files present upon the specified root, keyed by device and inode.
+pub fn file_sizes(root: &str) -> HashMap<(u64, u64), u64> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|meta| meta.is_file())
+        .map(|meta| ((meta.dev(), meta.ino()), meta.len()))
+        .collect()
+}
+
+/// Measure the disk usage of the specified directory, returning the bytes it consumes on disk alongside its
+/// apparent total. Hardlinked inodes, such as files shared with upstream slices, are counted but once, and
+/// apportioned by their link count towards the former.
+pub fn disk_usage(path: &str) -> Result<(u64, u64)> {
+    let mut inodes = HashSet::new();
+    let mut on_disk = 0;
+    let mut total = 0;
+
+    for entry in WalkDir::new(path) {
+        let entry = entry.prepend(|| format!("Failure acquiring entry in '{path}'"))?;
+        let meta = entry
+            .metadata()
+            .prepend(|| format!("Failure to acquire metadata of '{}'", entry.path().display()))?;
+
+        if meta.is_dir() || !inodes.insert((meta.dev(), meta.ino())) {
+            continue;
+        }
+
+        on_disk += meta.len() / meta.nlink();
+        total += meta.len();
+    }
+
+    Ok((on_disk, total))
+}
+
 fn deserialize<R: Read, T: for<'de> Deserialize<'de>>(instance: &str, reader: R) -> Result<T> {
     match bincode::options()
         .with_fixint_encoding()
@@ -548,6 +920,46 @@ fn serialize(path: &str, ds: FileSystemState) -> Result<()> {
     Ok(())
 }
 
+fn read_state(instance: &str) -> Result<Option<FileSystemState>> {
+    let path = &format!("{}/state/{}.dat", *DATA_DIR, instance);
+    let mut header = ByteBuffer::with_capacity(8).read();
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) =>
+            if let IOErrorKind::NotFound = err.kind() {
+                return Ok(None);
+            } else {
+                return Err(err).prepend_io(|| path.into());
+            },
+    };
+
+    file.read_exact(header.as_slice_mut()).prepend_io(|| path.into())?;
+
+    let magic = header.read_le_32();
+    let version = header.read_le_32();
+
+    if magic != MAGIC_NUMBER {
+        err!(FilesystemSyncError::MagicMismatch(path.into(), magic))?
+    } else if version == 1 {
+        Ok(Some(deserialize::<File, LegacyState>(instance, file)?.into()))
+    } else if version != 2 && version != VERSION {
+        err!(FilesystemSyncError::UnsupportedVersion(path.into(), version))?
+    } else {
+        let (state_buffer, checksum_valid) = decode_state(file).prepend_io(|| path.into())?;
+
+        if !checksum_valid {
+            err!(FilesystemSyncError::ChecksumMismatch(path.into()))?
+        }
+
+        let buf_reader = BufReader::new(state_buffer.as_slice());
+
+        Ok(Some(match version {
+            2 => deserialize::<BufReader<&[u8]>, LegacyState>(instance, buf_reader)?.into(),
+            _ => deserialize::<BufReader<&[u8]>, FileSystemState>(instance, buf_reader)?,
+        }))
+    }
+}
+
 fn decode_state<R: Read>(mut stream: R) -> IOResult<(Vec<u8>, bool)> {
     let mut header_buffer = ByteBuffer::with_capacity(10).read();
 
@@ -609,24 +1021,26 @@ fn previous_state(map: Vec<Option<FileSystemState>>) -> FileSystemState {
 
     for ins_state in map.into_iter().flatten() {
         state.files.extend(ins_state.files);
+        state.labels.extend(ins_state.labels);
     }
 
     state
 }
 
-fn filesystem_state(mut state: FileSystemState, map: Vec<(Arc<str>, FileSystemState)>) -> FileSystemState {
+fn filesystem_state(mut state: FileSystemState, map: Vec<(Arc<str>, FileSystemState, Option<Regex>)>) -> FileSystemState {
     for ins_state in map {
         if ins_state.1.files.is_empty() {
-            obtain_state(ins_state.0, &mut state);
+            obtain_state(ins_state.0, &mut state, ins_state.2.as_ref());
         } else {
             state.files.extend(ins_state.1.files);
+            state.labels.extend(ins_state.1.labels);
         }
     }
 
     state
 }
 
-fn obtain_state(root: Arc<str>, state: &mut FileSystemState) {
+fn obtain_state(root: Arc<str>, state: &mut FileSystemState, exclude: Option<&Regex>) {
     let len = root.len();
     let entries = WalkDir::new(root.as_ref()).into_iter().filter_map(|e| e.ok());
 
@@ -642,29 +1056,174 @@ fn obtain_state(root: Arc<str>, state: &mut FileSystemState) {
             continue;
         }
 
+        if exclude.is_some_and(|a| a.is_match(&src_tr)) {
+            continue;
+        }
+
         let metadata = match entry.metadata() {
             Ok(meta) => meta,
             Err(_) => continue,
         };
 
-        state.files.insert(src_tr, (FileType::from(metadata), src));
+        let file_type = FileType::from(metadata);
+
+        //Hardlinks share the inode, and therefore the label, of their source
+        if file_type == FileType::SymLink {
+            if let Some(label) = SECURITY_LABEL.and_then(|attr| xattr::get(src.as_ref(), attr).ok().flatten()) {
+                state.labels.insert(src_tr.clone(), label.into());
+            }
+        }
+
+        state.files.insert(src_tr, (file_type, src));
     }
 }
 
-fn link_filesystem(state: &FileSystemState, root: &str) {
-    state.files.par_iter().filter(|a| a.1 .0 != FileType::Directory).for_each(|file| {
-        let path = &format!("{}{}", root, file.0);
+/// Determine the extended attribute by which the active mandatory access control framework, if any,
+/// labels files. Security labels are neither obtained nor applied in absence thereof.
+fn security_label() -> Option<&'static str> {
+    if Path::new("/sys/fs/selinux/enforce").exists() {
+        Some("security.selinux")
+    } else if fs::read_to_string("/sys/module/apparmor/parameters/enabled").is_ok_and(|a| a.trim() == "Y") {
+        Some("security.apparmor")
+    } else {
+        None
+    }
+}
 
-        if let FileType::SymLink = file.1 .0 {
-            if let Err(error) = create_soft_link(&file.1 .1, path).prepend(|| format!("Failed to symlink '{path}'")) {
-                error.warn();
+fn apply_label(path: &str, label: &[u8]) -> IOResult<()> {
+    let attr = match *SECURITY_LABEL {
+        Some(attr) => attr,
+        None => return Ok(()),
+    };
+
+    let result = match xattr::get(path, attr)? {
+        Some(current) if current == label => Ok(()),
+        _ => xattr::set(path, attr, label),
+    };
+
+    match result {
+        Err(error) if error.raw_os_error() == Some(Errno::EPERM as i32) => Ok(()),
+        result => result,
+    }
+}
+
+/// Compile the path exclusions configured upon the specified container into a single expression.
+pub fn exclusion(handle: &ContainerHandle) -> Result<Option<Regex>> {
+    let patterns = handle.config().exclude_paths();
+
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let patterns: Vec<String> = patterns.iter().map(|a| glob_pattern(a.trim_start_matches('/'))).collect();
+
+    match Regex::new(&format!("^/?(?:{})(?:/.*)?$", patterns.join("|"))) {
+        Ok(regex) => Ok(Some(regex)),
+        Err(err) => err!(FilesystemSyncError::InvalidExclusion(handle.vars().instance().into(), err.to_string())),
+    }
+}
+
+fn glob_pattern(glob: &str) -> String {
+    let mut pattern = String::new();
+    let mut chars = glob.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                pattern.push_str(".*");
             }
-        } else if let FileType::HardLink = file.1 .0 {
-            if let Err(error) = create_hard_link(&file.1 .1, path).prepend(|| format!("Failed to hardlink '{path}'")) {
-                error.warn();
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            _ => pattern.push_str(&regex::escape(&char.to_string())),
+        }
+    }
+
+    pattern
+}
+
+fn link_filesystem(state: &FileSystemState, root: &str) -> usize {
+    state
+        .files
+        .par_iter()
+        .filter(|a| a.1 .0 != FileType::Directory)
+        .filter(|file| {
+            let path = &format!("{}{}", root, file.0);
+            let result = match file.1 .0 {
+                FileType::SymLink => with_retry(|| create_soft_link(&file.1 .1, path))
+                    .prepend(|| format!("Failed to symlink '{path}'"))
+                    .and_then(|_| match state.labels.get(file.0) {
+                        Some(label) => apply_label(path, label).prepend(|| format!("Failed to label '{path}'")),
+                        None => Ok(()),
+                    }),
+                FileType::HardLink =>
+                    with_retry(|| create_hard_link(&file.1 .1, path)).prepend(|| format!("Failed to hardlink '{path}'")),
+                _ => Ok(()),
+            };
+
+            result.map_err(|error| error.warn()).is_err()
+        })
+        .count()
+}
+
+/// Count the files which `delete_files`, `delete_directories`, and `link_filesystem` would otherwise
+/// create, relink, or delete, without modifying the filesystem.
+fn preview_filesystem(state: &FileSystemState, state_prev: &FileSystemState, root: &str) -> LinkPreview {
+    let deleted = state_prev
+        .files
+        .par_iter()
+        .filter(|a| !state.files.contains_key(a.0))
+        .filter(|a| Path::new(&format!("{root}{}", a.0)).symlink_metadata().is_ok())
+        .count();
+    let (created, relinked) = state
+        .files
+        .par_iter()
+        .filter(|a| a.1 .0 != FileType::Directory)
+        .filter_map(|(path, (kind, src))| pending_link(kind, src, &format!("{root}{path}")))
+        .map(|relink| (!relink as usize, relink as usize))
+        .reduce(|| (0, 0), |a, b| (a.0 + b.0, a.1 + b.1));
+
+    LinkPreview {
+        created,
+        relinked,
+        deleted,
+    }
+}
+
+/// Yields `None` where the destination is already linked to its source, otherwise whether an extant
+/// destination would be relinked, rather than created.
+fn pending_link(kind: &FileType, src: &str, dest: &str) -> Option<bool> {
+    let dest_meta = match fs::symlink_metadata(dest) {
+        Ok(meta) => meta,
+        Err(_) => return Some(false),
+    };
+    let linked = match kind {
+        FileType::SymLink => fs::read_link(src).ok().is_some_and(|a| fs::read_link(dest).ok().is_some_and(|b| a == b)),
+        _ => metadata(src).ok().is_some_and(|a| !dest_meta.is_symlink() && a.ino() == dest_meta.ino()),
+    };
+
+    (!linked).then_some(true)
+}
+
+fn with_retry<F: Fn() -> IOResult<()>>(link: F) -> IOResult<()> {
+    let mut attempt = 0;
+
+    loop {
+        match link() {
+            Err(error) if attempt < LINK_RETRIES && is_transient(&error) => {
+                attempt += 1;
+                thread::sleep(LINK_RETRY_DELAY * attempt);
             }
+            result => return result,
         }
-    });
+    }
+}
+
+fn is_transient(error: &IOError) -> bool {
+    error.raw_os_error().is_some_and(|errno| {
+        [Errno::ENOSPC, Errno::EDQUOT, Errno::EAGAIN, Errno::EBUSY, Errno::EEXIST, Errno::EINTR, Errno::ETXTBSY]
+            .contains(&Errno::from_i32(errno))
+    })
 }
 
 fn delete_files(state: &FileSystemState, state_res: &FileSystemState, root: &str) {
@@ -782,6 +1341,43 @@ pub fn create_hard_link(src: &str, dest: &str) -> IOResult<()> {
     }
 }
 
+fn verify_file(src: &str, dest: &str, repair: bool) -> Option<Divergent> {
+    let (meta_src, meta_dest) = match (metadata(src), metadata(dest)) {
+        (Ok(src), Ok(dest)) => (src, dest),
+        _ => return None,
+    };
+
+    if meta_src.ino() == meta_dest.ino() && meta_src.dev() == meta_dest.dev() {
+        return None;
+    }
+
+    let identical = match (hash_file(src), hash_file(dest)) {
+        (Ok(src), Ok(dest)) => src == dest,
+        _ => return None,
+    };
+    let relinked = repair
+        && match create_hard_link(src, dest).prepend(|| format!("Failed to hardlink '{dest}'")) {
+            Ok(_) => true,
+            Err(error) => {
+                error.warn();
+                false
+            }
+        };
+
+    Some(Divergent {
+        path: dest.into(),
+        identical,
+        relinked,
+    })
+}
+
+fn hash_file(path: &str) -> IOResult<Vec<u8>> {
+    let mut hasher = Sha256::new();
+
+    copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().to_vec())
+}
+
 #[inline]
 fn remove_symlink(path: &Path) -> IOResult<()> {
     if fs::read_link(path).is_ok() {
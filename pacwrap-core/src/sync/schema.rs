@@ -48,6 +48,9 @@ static SCHEMA_STATE: OnceLock<SchemaState> = OnceLock::new();
 const MAGIC_NUMBER: u32 = 659933704;
 const ARCHIVE_PATH: &str = env!("PACWRAP_DIST_FS");
 const SCHEMA_META: &str = ".container_schema";
+const MINIMAL_SCHEMA: [&str; 3] = ["usr/share/libalpm/hooks", "usr/share/libalpm/scripts", "usr/bin/pacwrap-key"];
+
+pub const MINIMAL_PACKAGES: [&str; 4] = ["filesystem", "glibc", "bash", "coreutils"];
 
 pub enum SchemaStatus {
     UpToDate,
@@ -87,6 +90,10 @@ impl SchemaState {
             files: IndexSet::new(),
         }
     }
+
+    pub fn version(&self) -> String {
+        format!("{}.{}.{}", self.major, self.minor, self.patch)
+    }
 }
 
 impl Hash for SchemaNode {
@@ -118,6 +125,7 @@ impl From<EntryType> for NodeType {
 pub fn extract(inshandle: &ContainerHandle, old_schema: &Option<SchemaState>) -> Result<()> {
     let meta_path = format!("{}/{}", inshandle.vars().root(), SCHEMA_META);
     let schema_state = get_schema_state()?;
+    let minimal = inshandle.metadata().minimal();
 
     if let Some(schema) = old_schema {
         for file in schema
@@ -145,6 +153,10 @@ pub fn extract(inshandle: &ContainerHandle, old_schema: &Option<SchemaState>) ->
         let path = entry.path().prepend_io(|| ARCHIVE_PATH.into())?.to_string_lossy().to_string();
         let dest_path = format!("{}/{}", inshandle.vars().root(), path);
 
+        if minimal && !is_minimal(&path) {
+            continue;
+        }
+
         if let Err(err) = entry.unpack(&dest_path).prepend_io(|| ARCHIVE_PATH.into()) {
             err.warn();
         }
@@ -193,6 +205,29 @@ pub fn version(inshandle: &ContainerHandle) -> Result<SchemaStatus> {
     }
 }
 
+pub fn apply(inshandle: &ContainerHandle) -> Result<()> {
+    match version(inshandle)? {
+        UpToDate => extract(inshandle, &None),
+        OutOfDate(schema) => extract(inshandle, &schema),
+    }
+}
+
+pub fn current_version() -> String {
+    format!("{}.{}.{}", *VERSION_MAJOR, *VERSION_MINOR, *VERSION_PATCH)
+}
+
+pub fn warn_outdated(inshandle: &ContainerHandle) {
+    let instance = inshandle.vars().instance();
+    let built = match version(inshandle) {
+        Ok(OutOfDate(Some(schema))) => schema.version(),
+        Ok(OutOfDate(None)) => "an unknown version".into(),
+        _ => return,
+    };
+
+    print_warning(&format!("Container {instance} was built with schema {built}; the present schema is {}.", current_version()));
+    print_warning(&format!("Invoke `pacwrap -U --apply-schema -t {instance}` to bring its schema up to date."));
+}
+
 pub fn serialize_path(from: &str, dest: &str) {
     let mut schema = SchemaState::new();
     let file = File::create(dest).unwrap();
@@ -208,6 +243,14 @@ pub fn serialize_path(from: &str, dest: &str) {
     bincode::serialize_into(file, &schema).unwrap();
 }
 
+fn is_minimal(path: &str) -> bool {
+    let path = path.trim_end_matches('/');
+
+    MINIMAL_SCHEMA
+        .iter()
+        .any(|node| path == *node || path.starts_with(&format!("{node}/")) || node.starts_with(&format!("{path}/")))
+}
+
 fn get_schema_state() -> Result<&'static SchemaState> {
     Ok(match SCHEMA_STATE.get() {
         Some(f) => f,
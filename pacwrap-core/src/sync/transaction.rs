@@ -26,7 +26,12 @@ use serde::{Deserialize, Serialize};
 
 use self::{SyncState::*, TransactionMode::*, TransactionType::*};
 use crate::{
-    config::{global, ContainerHandle, Global},
+    config::{
+        global,
+        template::{LockMode, TemplateLock},
+        ContainerHandle,
+        Global,
+    },
     constants::{ARROW_CYAN, BAR_CYAN, BOLD, BOLD_GREEN, BOLD_YELLOW, RESET},
     err,
     log::{Level, Logger},
@@ -38,7 +43,7 @@ use crate::{
         utils::AlpmUtils,
         SyncError,
     },
-    utils::{print_warning, prompt::prompt},
+    utils::{porcelain::is_porcelain, print_warning, prompt::prompt},
     Error,
 };
 
@@ -100,7 +105,7 @@ pub trait Transaction {
 }
 
 bitflags! {
-    pub struct TransactionFlags: u16 {
+    pub struct TransactionFlags: u32 {
         const NONE = 0;
         const TARGET_ONLY = 0b0000000001;
         const PREVIEW = 0b0000000010;
@@ -112,6 +117,17 @@ bitflags! {
         const LAZY_LOAD_DB = 0b0010000000;
         const DEBUG = 0b0100000000;
         const NO_ALPM_SANDBOX = 0b1000000000;
+        const UNFREEZE = 0b10000000000;
+        const REINSTALL = 0b100000000000;
+        const FORCE = 0b1000000000000;
+        const NO_FILESYSTEM_SYNC = 0b10000000000000;
+        const OFFLINE = 0b100000000000000;
+        const NO_HOOKS = 0b1000000000000000;
+        const CONTINUE_ON_ERROR = 0b10000000000000000;
+        const ONLY_OUTDATED = 0b100000000000000000;
+        const SYSUPGRADE_ONLY = 0b1000000000000000000;
+        const VERIFY_PACKAGES = 0b10000000000000000000;
+        const TRACE = 0b100000000000000000000;
     }
 }
 
@@ -130,9 +146,11 @@ pub struct TransactionMetadata<'a> {
     resident_pkgs: HashSet<String>,
     ignored_pkgs: HashSet<String>,
     held_pkgs: HashSet<String>,
+    treated_pkgs: HashSet<String>,
+    template_lock: Option<TemplateLock>,
     queue: Vec<Cow<'a, str>>,
     mode: TransactionMode,
-    flags: (u16, u32),
+    flags: (u32, u32),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -145,6 +163,9 @@ pub struct TransactionParameters {
     files: u64,
     action: TransactionType,
     mode: TransactionMode,
+    arch: String,
+    snapshot: Option<String>,
+    rate: u64,
 }
 
 impl TransactionMode {
@@ -224,7 +245,7 @@ impl TransactionType {
             progress.inc(1);
             progress.set_message(format!("{}{}{} ", *BOLD, message, *RESET));
             progress.tick();
-        } else {
+        } else if !is_porcelain() {
             println!("{} {}{}{}", *BAR_CYAN, *BOLD, message, *RESET);
         }
     }
@@ -237,12 +258,22 @@ impl<'a> TransactionMetadata<'a> {
             resident_pkgs: HashSet::new(),
             held_pkgs: HashSet::new(),
             ignored_pkgs: HashSet::new(),
+            treated_pkgs: HashSet::new(),
+            template_lock: None,
             mode: Local,
             queue: queue.iter().map(|q| (*q).into()).collect::<Vec<_>>(),
             flags: (0, 0),
         }
     }
 
+    pub fn treat_resident(&mut self, pkgs: &HashSet<&str>) {
+        self.treated_pkgs.extend(pkgs.iter().map(|p| (*p).into()));
+    }
+
+    pub fn template_lock(&mut self, lock: Option<&TemplateLock>) {
+        self.template_lock = lock.cloned();
+    }
+
     pub fn set_flags(&mut self, flags: &TransactionFlags, flags_alpm: &TransFlag) {
         self.flags = (flags.bits(), flags_alpm.bits());
     }
@@ -350,6 +381,16 @@ impl<'a> TransactionHandle<'a> {
         }
 
         for pkg in foreign {
+            if let (Local, true) = (self.meta.mode, self.meta.treated_pkgs.contains(pkg)) {
+                alpm.remove_ignorepkg(pkg.as_bytes()).expect("Unable to unignore treated pkg");
+
+                if let Some(logger) = log {
+                    logger.log(Level::Debug, &format!("Treated package {}", pkg)).expect("logger");
+                }
+
+                continue;
+            }
+
             alpm.add_ignorepkg(pkg.as_bytes()).expect("Unable to ignore foreign pkg");
 
             if let Some(logger) = log {
@@ -408,7 +449,10 @@ impl<'a> TransactionHandle<'a> {
         };
 
         if let Local = self.meta.mode {
-            let upstream = queue.iter().copied().find(|a| ignored.contains(*a));
+            let upstream = queue
+                .iter()
+                .copied()
+                .find(|a| ignored.contains(*a) && !self.meta.treated_pkgs.contains(*a));
             let forced = flags.contains(TransactionFlags::FORCE_DATABASE);
 
             if let (false, Some(upstream)) = (forced, upstream) {
@@ -450,8 +494,65 @@ impl<'a> TransactionHandle<'a> {
                     err!(SyncError::TargetNotAvailable(not_available.into()))?
                 }
 
+                if let (false, true, Local) = (self.agent, flags.contains(TransactionFlags::REINSTALL), self.meta.mode) {
+                    for pkg in queue
+                        .iter()
+                        .filter_map(|a| alpm.get_local_package(a))
+                        .filter(|a| a.sync_new_version(alpm.syncdbs()).is_none())
+                    {
+                        print_warning(&format!(
+                            "{}{}-{}{}: Package is up-to-date -- reinstalling",
+                            *BOLD,
+                            pkg.name(),
+                            pkg.version(),
+                            *RESET
+                        ));
+                    }
+                }
+
+                let queue = match (flags.contains(TransactionFlags::REINSTALL), self.meta.mode) {
+                    (false, Local) => queue
+                        .into_iter()
+                        .filter(|a| match alpm.get_local_package(a) {
+                            Some(pkg) if pkg.sync_new_version(alpm.syncdbs()).is_none() => {
+                                if !self.agent {
+                                    print_warning(&format!(
+                                        "{}{}-{}{}: Package is up-to-date -- skipping",
+                                        *BOLD,
+                                        pkg.name(),
+                                        pkg.version(),
+                                        *RESET
+                                    ));
+                                }
+
+                                false
+                            }
+                            _ => true,
+                        })
+                        .collect(),
+                    _ => queue,
+                };
+
                 let (deps, packages) = DependencyResolver::new(alpm, ignored).enumerate(&queue)?;
 
+                //Dependencies resolved from the targets are equally subject to the template lock.
+                if let (false, Local, Some(lock)) = (self.agent, self.meta.mode, self.meta.template_lock.as_ref()) {
+                    let locked: Vec<String> =
+                        packages.iter().map(|a| a.name()).filter(|a| !lock.permits(a)).map(|a| a.to_string()).collect();
+
+                    match (locked.is_empty(), flags.contains(TransactionFlags::FORCE), lock.mode()) {
+                        (true, ..) => (),
+                        (false, false, LockMode::Refuse) => err!(SyncError::TemplateLocked(locked))?,
+                        (false, ..) => print_warning(&format!(
+                            "Package{} {}{}{} not permitted by template lock.",
+                            if locked.len() > 1 { "s" } else { "" },
+                            *BOLD,
+                            locked.join(", "),
+                            *RESET
+                        )),
+                    }
+                }
+
                 for pkg in packages
                     .iter()
                     .filter(|a| !self.meta.ignored_pkgs.contains(a.name()))
@@ -561,7 +662,14 @@ impl<'a> TransactionHandle<'a> {
 }
 
 impl TransactionParameters {
-    fn new(t_type: TransactionType, t_mode: TransactionMode, download: (u64, u64)) -> Self {
+    fn new(
+        t_type: TransactionType,
+        t_mode: TransactionMode,
+        download: (u64, u64),
+        arch: &str,
+        snapshot: Option<String>,
+        rate: u64,
+    ) -> Self {
         Self {
             magic: MAGIC_NUMBER,
             ver_major: env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap(),
@@ -571,6 +679,9 @@ impl TransactionParameters {
             files: download.1,
             action: t_type,
             mode: t_mode,
+            arch: arch.into(),
+            snapshot,
+            rate,
         }
     }
 
@@ -589,4 +700,16 @@ impl TransactionParameters {
     pub fn action(&self) -> TransactionType {
         self.action
     }
+
+    pub fn arch(&self) -> &str {
+        &self.arch
+    }
+
+    pub fn snapshot(&self) -> Option<&str> {
+        self.snapshot.as_deref()
+    }
+
+    pub fn download_rate(&self) -> u64 {
+        self.rate
+    }
 }
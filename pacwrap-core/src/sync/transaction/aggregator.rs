@@ -17,16 +17,24 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{remove_file, write},
+    io::{ErrorKind::NotFound, Result as IOResult},
+    path::Path,
+    sync::{Arc, Mutex},
+    thread::Builder,
+};
 
 use alpm::Alpm;
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
 use lazy_static::lazy_static;
-use signal_hook::iterator::Signals;
+use signal_hook::{consts::SIGUSR1, iterator::Signals};
+use simplebyteunit::simplebyteunit::*;
 
 use crate::{
     config::{cache::ContainerCache, ContainerHandle, ContainerType::*},
-    constants::{ARROW_GREEN, IS_COLOR_TERMINAL, SIGNAL_LIST, UNIX_TIMESTAMP, VERBOSE},
+    constants::{ARROW_CYAN, ARROW_GREEN, ARROW_RED, BOLD, IS_COLOR_TERMINAL, RESET, SIGNAL_LIST, TRACE_LOCATION, UNIX_TIMESTAMP},
     err,
     error,
     exec::{fakeroot_container, ExecutionType::NonInteractive},
@@ -34,7 +42,8 @@ use crate::{
     log::{Level, Logger},
     sync::{
         self,
-        filesystem::{validate_fs_states, FilesystemSync},
+        event::trace,
+        filesystem::{file_sizes, unique_size, validate_fs_states, FilesystemSync},
         transaction::{
             Transaction,
             TransactionFlags,
@@ -46,7 +55,14 @@ use crate::{
         utils::signal_trap,
         SyncError,
     },
-    utils::arguments::InvalidArgument,
+    utils::{
+        arguments::InvalidArgument,
+        porcelain::is_porcelain,
+        print_warning,
+        status,
+        verbosity,
+        warnings,
+    },
     Error,
     Result,
 };
@@ -60,9 +76,20 @@ lazy_static! {
         .tick_strings(&["::", ":.", ".:", "::"]);
 }
 
+struct Status {
+    stage: &'static str,
+    container: Option<String>,
+    processed: usize,
+    total: usize,
+}
+
 pub struct TransactionAggregator<'a> {
     queried: HashSet<&'a str>,
     updated: HashSet<&'a str>,
+    ignored: HashSet<&'a str>,
+    treated: HashSet<&'a str>,
+    failed: Vec<(&'a str, String)>,
+    skipped: Vec<(&'a str, &'a str)>,
     pkg_queue: HashMap<&'a str, Vec<&'a str>>,
     action: TransactionType,
     cache: &'a ContainerCache<'a>,
@@ -71,17 +98,28 @@ pub struct TransactionAggregator<'a> {
     logger: &'a mut Logger,
     flags: TransactionFlags,
     targets: Option<Vec<&'a str>>,
+    relink: Vec<&'a str>,
+    after: HashMap<&'a str, Vec<&'a str>>,
+    usage: HashMap<String, HashMap<(u64, u64), u64>>,
     lock: Option<&'a Lock>,
     progress: Option<ProgressBar>,
     signals: Signals,
+    status: Arc<Mutex<Status>>,
 }
 
 impl<'a> TransactionAggregator<'a> {
     pub fn new(inscache: &'a ContainerCache, log: &'a mut Logger, action_type: TransactionType) -> Self {
         Self {
             targets: None,
+            relink: Vec::new(),
+            after: HashMap::new(),
+            usage: HashMap::new(),
             queried: HashSet::new(),
             updated: HashSet::new(),
+            ignored: HashSet::new(),
+            treated: HashSet::new(),
+            failed: Vec::new(),
+            skipped: Vec::new(),
             pkg_queue: HashMap::new(),
             action: action_type,
             cache: inscache,
@@ -92,11 +130,23 @@ impl<'a> TransactionAggregator<'a> {
             lock: None,
             progress: None,
             signals: Signals::new(SIGNAL_LIST).unwrap(),
+            status: Arc::new(Mutex::new(Status {
+                stage: "Preparing",
+                container: None,
+                processed: 0,
+                total: 0,
+            })),
         }
     }
 
     pub fn progress(mut self) -> Self {
-        if let (.., Remove(..)) | (.., Upgrade(false, true, ..)) | (false, ..) = (*IS_COLOR_TERMINAL && !*VERBOSE, self.action) {
+        if is_porcelain() {
+            return self;
+        }
+
+        let interactive = *IS_COLOR_TERMINAL && verbosity::level() == 0;
+
+        if let (.., Remove(..)) | (.., Upgrade(false, true, ..)) | (false, ..) = (interactive, self.action) {
             return self;
         }
 
@@ -123,6 +173,26 @@ impl<'a> TransactionAggregator<'a> {
         self
     }
 
+    pub fn relink(mut self, containers: Vec<&'a str>) -> Self {
+        self.relink = containers;
+        self
+    }
+
+    pub fn after(mut self, constraints: HashMap<&'a str, Vec<&'a str>>) -> Self {
+        self.after = constraints;
+        self
+    }
+
+    pub fn ignore(mut self, containers: Vec<&'a str>) -> Self {
+        self.ignored.extend(containers);
+        self
+    }
+
+    pub fn treat_resident(mut self, packages: Vec<&'a str>) -> Self {
+        self.treated.extend(packages);
+        self
+    }
+
     pub fn assert_lock(mut self, lock: &'a Lock) -> Result<Self> {
         lock.assert()?;
         self.lock = Some(lock);
@@ -131,11 +201,20 @@ impl<'a> TransactionAggregator<'a> {
 
     pub fn aggregate(mut self) -> Result<()> {
         self.lock()?;
+        self.assert_ordering()?;
+
+        if self.flags.contains(TransactionFlags::TRACE) {
+            trace::instantiate(*TRACE_LOCATION, true)?;
+        }
+
         signal_trap();
+        self.status_trap();
 
         let _timestamp = *UNIX_TIMESTAMP;
         let preview = self.flags.intersects(TransactionFlags::PREVIEW);
         let filesystem_sync = self.flags.intersects(TransactionFlags::FILESYSTEM_SYNC | TransactionFlags::CREATE);
+        let skip_sync = self.flags.contains(TransactionFlags::NO_FILESYSTEM_SYNC) && !filesystem_sync;
+        let target_only = matches!(self.action, Remove(..)) && self.flags.contains(TransactionFlags::TARGET_ONLY);
         let transact = match self.action {
             Upgrade(upgrade, refresh, force) => {
                 if !upgrade
@@ -145,7 +224,9 @@ impl<'a> TransactionAggregator<'a> {
                     err!(InvalidArgument::OperationUnspecified)?
                 }
 
-                if refresh {
+                if refresh && self.flags.contains(TransactionFlags::OFFLINE) {
+                    print_warning("Database synchronization skipped in offline mode.");
+                } else if refresh {
                     sync::synchronize_database(&mut self, force)?;
                 }
 
@@ -153,6 +234,7 @@ impl<'a> TransactionAggregator<'a> {
             }
             Remove(..) => self.targets.is_some(),
         };
+        let upgrade = matches!(self.action, Upgrade(true, ..));
         let upstream = match self.targets.as_ref() {
             Some(targets) => self.cache.filter_target(targets, vec![Base, Slice]),
             None => self.cache.filter(vec![Base, Slice]),
@@ -162,7 +244,15 @@ impl<'a> TransactionAggregator<'a> {
             None => self.cache.filter(vec![Aggregate]),
         };
         let are_downstream = self.cache.count(vec![Aggregate]) > 0;
-        let target_amount = (downstream.len() + upstream.len()) as u64;
+        let frozen = match transact && !self.flags.intersects(TransactionFlags::UNFREEZE) {
+            true => self.skip_frozen(&[upstream.as_slice(), downstream.as_slice()].concat(), upgrade)?,
+            false => 0,
+        };
+        let current = match upgrade && self.flags.contains(TransactionFlags::ONLY_OUTDATED) {
+            true => self.ignore_current(&[upstream.as_slice(), downstream.as_slice()].concat())?,
+            false => 0,
+        };
+        let target_amount = (downstream.len() + upstream.len() - current - frozen) as u64;
         let mut linker = FilesystemSync::new(self.cache).assert_lock(self.lock);
 
         if upstream.is_empty() && downstream.is_empty() {
@@ -174,7 +264,12 @@ impl<'a> TransactionAggregator<'a> {
             progress.set_length(target_amount);
         }
 
-        if !validate_fs_states(&upstream) && !preview && are_downstream {
+        if let Ok(mut status) = self.status.lock() {
+            status.total = target_amount as usize;
+        }
+
+        if !validate_fs_states(&upstream) && !preview && !target_only && are_downstream {
+            self.set_status("Synchronizing filesystems", None);
             linker.refresh_state();
             linker.prepare(upstream.len(), self.progress.as_ref());
             linker.engage(&upstream)?;
@@ -186,10 +281,41 @@ impl<'a> TransactionAggregator<'a> {
         }
 
         if are_downstream {
-            if !preview && (filesystem_sync || !self.updated.is_empty()) {
-                linker.filesystem_state();
-                linker.prepare(self.cache.registered().len(), self.progress.as_ref());
-                linker.engage(&self.cache.registered())?;
+            if skip_sync && !self.updated.is_empty() {
+                print_warning("Filesystem synchronization skipped by `--no-filesystem-sync`.");
+                print_warning("Aggregate containers may be out of sync until the next filesystem synchronization.");
+            } else if target_only && !self.updated.is_empty() {
+                print_warning("Filesystem synchronization skipped by `--target-only`.");
+                print_warning("Aggregate containers may reference removed packages until the next filesystem synchronization.");
+            } else if filesystem_sync || (!preview && !self.updated.is_empty()) {
+                self.set_status("Synchronizing filesystems", None);
+
+                if !transact {
+                    linker.exclude(&self.ignored);
+                }
+
+                for target in &self.relink {
+                    if preview || linker.is_excluded(target) {
+                        continue;
+                    }
+
+                    linker.clear(self.cache.get_instance(target)?)?;
+                    self.logger.log(Level::Info, &format!("Cleared linked filesystem of {target}."))?;
+                }
+
+                let containers: Vec<&str> = self.cache.registered().into_iter().filter(|a| !linker.is_excluded(a)).collect();
+
+                for ins in self.cache.registered().iter().filter(|a| linker.is_excluded(a)) {
+                    self.logger.log(Level::Info, &format!("Container {ins} is excluded: Filesystem synchronization skipped"))?;
+                }
+
+                match preview {
+                    true => linker.preview(),
+                    false => linker.filesystem_state(),
+                }
+
+                linker.prepare(containers.len(), self.progress.as_ref());
+                linker.engage(&containers)?;
                 linker.finish(self.progress.as_ref());
             }
 
@@ -200,13 +326,149 @@ impl<'a> TransactionAggregator<'a> {
             self.transaction(&downstream)?;
         }
 
+        if !self.updated.is_empty() && !self.usage.is_empty() {
+            self.report_disk_usage();
+        }
+
+        status::emit(status::Status::Complete {
+            failed: self.failed.len() + self.skipped.len(),
+        });
+
+        if !self.failed.is_empty() || !self.skipped.is_empty() {
+            return self.print_failures();
+        }
+
         self.print_complete(filesystem_sync, target_amount, upstream.last().or_else(|| downstream.last()));
         Ok(())
     }
 
+    fn ignore_current(&mut self, containers: &[&'a str]) -> Result<usize> {
+        let mut outdated = HashMap::new();
+        let mut current = 0;
+
+        for ins in containers.iter() {
+            if self.ignored.contains(ins) {
+                continue;
+            } else if !self.outdated(ins, &mut outdated)? {
+                self.logger.log(Level::Info, &format!("Container {ins} is up-to-date: Transaction skipped"))?;
+                self.ignored.insert(ins);
+                current += 1;
+            }
+        }
+
+        Ok(current)
+    }
+
+    fn outdated(&self, ins: &'a str, outdated: &mut HashMap<&'a str, bool>) -> Result<bool> {
+        if let Some(state) = outdated.get(ins) {
+            return Ok(*state);
+        }
+
+        let inshandle = match self.cache.get_instance_option(ins) {
+            Some(handle) => handle,
+            None => return Ok(true),
+        };
+        let mut state = self.pkg_queue.contains_key(ins) || {
+            let alpm = sync::instantiate_alpm(inshandle, self.flags())?;
+            let syncdbs = alpm.syncdbs();
+
+            alpm.localdb().pkgs().iter().any(|pkg| pkg.sync_new_version(syncdbs).is_some())
+        };
+
+        for dep in inshandle.metadata().dependencies() {
+            if state {
+                break;
+            }
+
+            state = self.outdated(dep, outdated)?;
+        }
+
+        outdated.insert(ins, state);
+        Ok(state)
+    }
+
+    /// Ensure ordering constraints can be satisfied prior to any transaction. Dependencies and ordering
+    /// constraints are both traversed, such that a cycle spanning either is refused.
+    fn assert_ordering(&self) -> Result<()> {
+        let aggregate = |ins: &str| {
+            matches!(self.cache.get_instance_option(ins).map(|a| a.metadata().container_type()), Some(Aggregate))
+        };
+
+        for (ins, after) in self.after.iter() {
+            if let Some(after) = after.iter().find(|a| aggregate(a) && !aggregate(ins)) {
+                err!(SyncError::OrderingUnsatisfiable(ins.to_string(), after.to_string()))?
+            }
+        }
+
+        let mut visited = HashSet::new();
+
+        for ins in self.after.keys() {
+            self.ordering_cycle(ins, &mut Vec::new(), &mut visited)?;
+        }
+
+        Ok(())
+    }
+
+    fn ordering_cycle(&self, ins: &'a str, path: &mut Vec<&'a str>, visited: &mut HashSet<&'a str>) -> Result<()> {
+        if let Some(pos) = path.iter().position(|a| *a == ins) {
+            let mut cycle: Vec<String> = path[pos ..].iter().map(|a| a.to_string()).collect();
+
+            cycle.push(ins.into());
+            err!(SyncError::OrderingCycle(cycle))?
+        } else if !visited.insert(ins) {
+            return Ok(());
+        }
+
+        path.push(ins);
+
+        for next in self.predecessors(ins) {
+            self.ordering_cycle(next, path, visited)?;
+        }
+
+        path.pop();
+        Ok(())
+    }
+
+    fn predecessors(&self, ins: &'a str) -> Vec<&'a str> {
+        let mut predecessors = match self.cache.get_instance_option(ins) {
+            Some(handle) => handle.metadata().dependencies(),
+            None => Vec::new(),
+        };
+
+        if let Some(after) = self.after.get(ins) {
+            predecessors.extend(after.iter().copied());
+        }
+
+        predecessors
+    }
+
+    fn skip_frozen(&mut self, containers: &[&'a str], upgrade: bool) -> Result<usize> {
+        let mut skipped = 0;
+
+        for ins in containers.iter() {
+            let frozen = match self.cache.get_instance_option(ins) {
+                Some(handle) => handle.metadata().frozen(),
+                None => continue,
+            };
+
+            if !frozen || self.ignored.contains(ins) {
+                continue;
+            } else if self.pkg_queue.contains_key(ins) {
+                err!(SyncError::ContainerFrozen(ins.to_string()))?
+            } else if upgrade {
+                print_warning(&format!("Container {}{ins}{} is frozen: Upgrade skipped.", *BOLD, *RESET));
+                self.logger.log(Level::Info, &format!("Container {ins} is frozen: Transaction skipped"))?;
+                self.ignored.insert(ins);
+                skipped += 1;
+            }
+        }
+
+        Ok(skipped)
+    }
+
     pub fn transaction(&mut self, containers: &[&'a str]) -> Result<()> {
         for ins in containers.iter() {
-            if self.queried.contains(ins) {
+            if self.queried.contains(ins) || self.ignored.contains(ins) {
                 continue;
             }
 
@@ -217,21 +479,59 @@ impl<'a> TransactionAggregator<'a> {
 
             self.signal(&mut None)?;
             self.queried.insert(ins);
+            self.set_status("Transacting", Some(ins));
             self.transaction(
-                &inshandle
-                    .metadata()
-                    .dependencies()
+                &self
+                    .predecessors(ins)
                     .iter()
                     .filter(|a| containers.contains(a))
                     .copied()
                     .collect::<Vec<&str>>(),
             )?;
-            self.transact(inshandle)?;
+
+            if let Some(dep) = self.failed_dependency(inshandle) {
+                self.logger().log(Level::Warn, &format!("Transaction skipped on {ins}: Dependency {dep} failed"))?;
+                self.skipped.push((ins, dep));
+                continue;
+            }
+
+            warnings::context(Some(ins));
+            status::emit(status::Status::ContainerStarted { container: ins });
+
+            let result = self.transact(inshandle);
+
+            warnings::context(None);
+            status::emit(match &result {
+                Ok(_) => status::Status::ContainerComplete {
+                    container: ins,
+                    updated: self.updated.contains(ins),
+                },
+                Err(err) => status::Status::ContainerFailed {
+                    container: ins,
+                    error: err.to_string(),
+                },
+            });
+
+            match result {
+                Err(err) if self.flags.contains(TransactionFlags::CONTINUE_ON_ERROR) => match err.downcast::<SyncError>() {
+                    Ok(SyncError::SignalInterrupt) => Err(err)?,
+                    _ => self.failed.push((ins, err.to_string())),
+                },
+                result => result?,
+            }
         }
 
         Ok(())
     }
 
+    fn failed_dependency(&self, inshandle: &'a ContainerHandle) -> Option<&'a str> {
+        inshandle
+            .metadata()
+            .dependencies()
+            .into_iter()
+            .find(|dep| self.failed.iter().any(|(a, _)| a == dep) || self.skipped.iter().any(|(a, _)| a == dep))
+    }
+
     fn transact(&mut self, inshandle: &'a ContainerHandle) -> Result<()> {
         if let Err(err) = self.lock()?.assert() {
             err!(SyncError::from(&err))?
@@ -244,6 +544,13 @@ impl<'a> TransactionAggregator<'a> {
 
         let alpm = sync::instantiate_alpm(inshandle, self.flags())?;
         let mut meta = TransactionMetadata::new(queue);
+
+        meta.treat_resident(&self.treated);
+
+        if let Slice = inshandle.metadata().container_type() {
+            meta.template_lock(inshandle.config().template_lock());
+        }
+
         let mut handle = TransactionHandle::new(&mut meta).alpm_handle(alpm);
         let mut act: Box<dyn Transaction> = Prepare.from(self);
 
@@ -272,6 +579,11 @@ impl<'a> TransactionAggregator<'a> {
                         self.logger().log(Level::Debug, &format!("Transaction state: {}", act.debug()))?;
                         self.tracted = !updated;
                         handle.release();
+
+                        if updated {
+                            self.post_transaction(inshandle)?;
+                        }
+
                         return Ok(());
                     } else if let UpdateSchema(_) = state {
                         self.updated.insert(inshandle.vars().instance());
@@ -297,7 +609,7 @@ impl<'a> TransactionAggregator<'a> {
                             self.logger().log(Level::Fatal, &format!("Transaction error: {}", err))?;
                             err.fatal()
                         }
-                        SyncError::AgentVersionMismatch | SyncError::InvalidMagicNumber => {
+                        SyncError::AgentVersionMismatch(..) | SyncError::InvalidMagicNumber => {
                             self.logger().log(Level::Error, &format!("Transaction error: {}", err))?;
                             err.error()
                         }
@@ -313,6 +625,10 @@ impl<'a> TransactionAggregator<'a> {
     }
 
     fn print_complete(&mut self, filesystem_sync: bool, target_amount: u64, target: Option<&&str>) {
+        if is_porcelain() {
+            return;
+        }
+
         if self.progress.is_some() {
             let are_multiple = target_amount > 1;
             let flagged = self.flags.intersects(TransactionFlags::PREVIEW | TransactionFlags::CREATE);
@@ -338,6 +654,52 @@ impl<'a> TransactionAggregator<'a> {
         }
     }
 
+    fn print_failures(&self) -> Result<()> {
+        for (ins, err) in self.failed.iter() {
+            eprintln!("{} {}{ins}{}: {err}", *ARROW_RED, *BOLD, *RESET);
+        }
+
+        for (ins, dep) in self.skipped.iter() {
+            eprintln!("{} {}{ins}{}: Skipped due to failure of dependency '{dep}'.", *ARROW_CYAN, *BOLD, *RESET);
+        }
+
+        err!(SyncError::ContainersFailed(self.failed.len(), self.skipped.len()))
+    }
+
+    fn status_trap(&self) {
+        let mut signals = Signals::new([SIGUSR1]).unwrap();
+        let status = self.status.clone();
+
+        Builder::new()
+            .name("pacwrap-status".to_string())
+            .spawn(move || {
+                for _ in signals.forever() {
+                    if let Ok(status) = status.lock() {
+                        match status.container.as_ref() {
+                            Some(ins) => eprintln!(
+                                "{} {}: {ins} ({} of {} containers queried, {} remaining)",
+                                *ARROW_CYAN,
+                                status.stage,
+                                status.processed,
+                                status.total,
+                                status.total.saturating_sub(status.processed)
+                            ),
+                            None => eprintln!("{} {} ({} containers queued)", *ARROW_CYAN, status.stage, status.total),
+                        }
+                    }
+                }
+            })
+            .unwrap();
+    }
+
+    fn set_status(&self, stage: &'static str, container: Option<&str>) {
+        if let Ok(mut status) = self.status.lock() {
+            status.stage = stage;
+            status.container = container.map(|a| a.into());
+            status.processed = self.queried.len();
+        }
+    }
+
     fn signal(&mut self, handle: &mut Option<Alpm>) -> Result<()> {
         for _ in self.signals.pending() {
             if let Some(handle) = handle {
@@ -350,6 +712,84 @@ impl<'a> TransactionAggregator<'a> {
         Ok(())
     }
 
+    pub fn pre_transaction(&mut self, inshandle: &ContainerHandle) -> Result<()> {
+        if self.flags.intersects(TransactionFlags::PREVIEW) {
+            return Ok(());
+        }
+
+        let instance = inshandle.vars().instance();
+
+        for hook in inshandle.config().hooks().pre_transaction() {
+            self.logger().log(Level::Info, &format!("Executing pre-transaction hook '{}' on {instance}", hook.exec()))?;
+
+            if let Err(err) = fakeroot_container(NonInteractive, None, inshandle, hook.arguments()) {
+                err!(SyncError::HookAborted(instance.into(), hook.exec().into(), err.to_string()))?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn post_transaction(&mut self, inshandle: &ContainerHandle) -> Result<()> {
+        if self.flags.intersects(TransactionFlags::PREVIEW) {
+            return Ok(());
+        }
+
+        let instance = inshandle.vars().instance();
+
+        for hook in inshandle.config().hooks().post_transaction() {
+            self.logger().log(Level::Info, &format!("Executing post-transaction hook '{}' on {instance}", hook.exec()))?;
+
+            if let Err(err) = fakeroot_container(NonInteractive, None, inshandle, hook.arguments()) {
+                let err = error!(SyncError::HookFailure(instance.into(), hook.exec().into(), err.to_string()));
+
+                self.logger().log(Level::Error, &err.to_string())?;
+
+                match hook.fatal() {
+                    true => Err(err)?,
+                    false => err.warn(),
+                }
+            }
+        }
+
+        self.generate_locales(inshandle)
+    }
+
+    fn generate_locales(&mut self, inshandle: &ContainerHandle) -> Result<()> {
+        let locales = inshandle.config().locales();
+
+        if locales.is_empty() {
+            return Ok(());
+        }
+
+        let instance = inshandle.vars().instance();
+        let root = inshandle.vars().root();
+
+        if !Path::new(&format!("{root}/usr/bin/locale-gen")).exists() {
+            let err = error!(SyncError::LocaleGenAbsent(instance.into()));
+
+            self.logger().log(Level::Warn, &err.to_string())?;
+            err.warn();
+            return Ok(());
+        }
+
+        self.logger().log(Level::Info, &format!("Generating locales {} on {instance}", locales.join(", ")))?;
+
+        let result = match write_locale_gen(root, locales) {
+            Ok(()) => fakeroot_container(NonInteractive, None, inshandle, vec!["/usr/bin/locale-gen"]).map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+
+        if let Err(err) = result {
+            let err = error!(SyncError::LocaleGenFailure(instance.into(), err));
+
+            self.logger().log(Level::Error, &err.to_string())?;
+            err.warn();
+        }
+
+        Ok(())
+    }
+
     pub fn keyring_update(&mut self, inshandle: &ContainerHandle) -> Result<()> {
         fakeroot_container(NonInteractive, None, inshandle, vec!["/usr/bin/pacwrap-key", "--populate", "archlinux"])?;
         self.keyring = true;
@@ -376,6 +816,34 @@ impl<'a> TransactionAggregator<'a> {
         self.updated.contains(&inshandle.vars().instance())
     }
 
+    /// Measure the files present upon the container's root prior to its first commit, in order to report
+    /// the net disk usage of the containers updated upon completion.
+    pub fn measure_usage(&mut self, inshandle: &ContainerHandle) {
+        if is_porcelain() || self.usage.contains_key(inshandle.vars().instance()) {
+            return;
+        }
+
+        self.usage.insert(inshandle.vars().instance().into(), file_sizes(inshandle.vars().root()));
+    }
+
+    //Usage is measured solely upon the containers updated, from their state prior to their first commit.
+    fn report_disk_usage(&self) {
+        let before: HashMap<_, _> = self
+            .usage
+            .iter()
+            .filter(|(ins, _)| self.updated.contains(&ins.as_str()))
+            .flat_map(|(_, sizes)| sizes.iter())
+            .collect();
+        let roots: Vec<&str> = self
+            .updated
+            .iter()
+            .filter_map(|a| self.cache.get_instance_option(a))
+            .map(|a| a.vars().root())
+            .collect();
+
+        print_disk_usage(before.values().copied().sum(), unique_size(&roots), roots.len());
+    }
+
     pub fn is_keyring_synced(&self) -> bool {
         self.keyring
     }
@@ -388,3 +856,40 @@ impl<'a> TransactionAggregator<'a> {
         self.logger
     }
 }
+
+fn print_disk_usage(before: u64, after: u64, containers: usize) {
+    let delta = after as i64 - before as i64;
+    let sign = if delta > 0 { "+" } else { "" };
+
+    println!(
+        "{} Net disk usage of {}{containers}{} container{}: {sign}{} ({} after deduplication)",
+        *ARROW_GREEN,
+        *BOLD,
+        *RESET,
+        if containers > 1 { "s" } else { "" },
+        delta.to_byteunit(IEC),
+        (after as i64).to_byteunit(IEC)
+    );
+}
+
+/// Write the container's `locale.gen`, unlinking it and any existing locale archive beforehand, such that
+/// files hardlinked from an upstream container are replaced rather than written through.
+fn write_locale_gen(root: &str, locales: &[String]) -> IOResult<()> {
+    let entries: Vec<String> = locales
+        .iter()
+        .map(|locale| match (locale.contains(char::is_whitespace), locale.split_once('.')) {
+            (true, _) => locale.clone(),
+            (false, Some((_, charset))) => format!("{locale} {charset}"),
+            (false, None) => format!("{locale} UTF-8"),
+        })
+        .collect();
+
+    for path in ["/etc/locale.gen", "/usr/lib/locale/locale-archive"] {
+        match remove_file(format!("{root}{path}")) {
+            Err(err) if err.kind() != NotFound => Err(err)?,
+            _ => (),
+        }
+    }
+
+    write(format!("{root}/etc/locale.gen"), format!("# Generated by pacwrap\n{}\n", entries.join("\n")))
+}
@@ -17,20 +17,33 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use std::{os::unix::process::ExitStatusExt, process::Child};
+use std::{
+    fs::{metadata, remove_file, rename, File},
+    io::{copy, Read},
+    os::unix::process::ExitStatusExt,
+    path::Path,
+    process::Child,
+    str::FromStr,
+};
+
+use os_pipe::PipeReader;
+use sha2::{Digest, Sha256};
+use simplebyteunit::simplebyteunit::*;
 
 use crate::{
     config::{
         global::{global, Global},
         ContainerHandle,
     },
-    constants::{BOLD, RESET},
+    constants::{ARROW_GREEN, BOLD, RESET},
     err,
+    error,
     exec::transaction_agent,
     log::Level::Info,
     sync::{
         self,
         event::summary::Summary,
+        filesystem::marginal_size,
         transaction::{
             SyncState,
             Transaction,
@@ -43,16 +56,17 @@ use crate::{
             TransactionType::{self, *},
         },
         utils::erroneous_preparation,
+        verify::verify_packages,
         SyncError,
     },
-    utils::prompt::prompt,
+    utils::{porcelain::is_porcelain, print_warning, prompt::prompt},
     Error,
     ErrorGeneric,
     Result,
 };
 
 enum State {
-    Commit((u64, u64)),
+    Commit(Summary, Vec<String>),
     Next(TransactionState),
 }
 
@@ -87,19 +101,34 @@ impl Transaction for Commit {
             });
         }
 
-        if let Err(error) = handle.alpm_mut().trans_prepare() {
-            erroneous_preparation(error)?
+        if let Err(error) = prepare(handle) {
+            if !ag.flags().contains(TransactionFlags::PREVIEW) {
+                return Err(error);
+            }
+
+            error.warn();
+            handle.alpm_mut().trans_release().generic()?;
+            return Ok(next_state(ag.action(), &self.state, false));
         }
 
-        let trans_state = match confirm(&self.state, ag, handle, global()?)? {
+        let (sum, transacted) = match confirm(&self.state, ag, handle, inshandle, global()?)? {
             State::Next(state) => return Ok(state),
-            State::Commit(values) => values,
+            State::Commit(sum, transacted) => (sum, transacted),
         };
-        let params = TransactionParameters::new(*ag.action(), *handle.get_mode(), trans_state);
+        let params = TransactionParameters::new(
+            *ag.action(),
+            *handle.get_mode(),
+            sum.download(),
+            sync::architecture(inshandle),
+            sync::snapshot(inshandle)?,
+            sync::download_rate()?,
+        );
 
         handle.set_alpm(None);
         ag.lock()?.assert()?;
+        ag.measure_usage(inshandle);
         wait_on_agent(transaction_agent(inshandle, ag.flags(), params, handle.meta)?)?;
+        sum.porcelain(instance);
 
         if self.keyring {
             ag.keyring_update(inshandle)?;
@@ -108,6 +137,7 @@ impl Transaction for Commit {
         handle.set_alpm(Some(sync::instantiate_alpm(inshandle, ag.flags())?));
         handle.apply_configuration(inshandle, ag.flags().intersects(TransactionFlags::CREATE))?;
         ag.logger().log(Info, &format!("container {instance}'s {state} transaction complete"))?;
+        verify(handle, inshandle, &transacted)?;
         Ok(next_state(ag.action(), &self.state, true))
     }
 
@@ -116,10 +146,18 @@ impl Transaction for Commit {
     }
 }
 
+fn prepare(handle: &mut TransactionHandle) -> Result<()> {
+    match handle.alpm_mut().trans_prepare() {
+        Ok(_) => Ok(()),
+        Err(error) => erroneous_preparation(error),
+    }
+}
+
 fn confirm(
     state: &TransactionState,
     ag: &TransactionAggregator,
     handle: &mut TransactionHandle,
+    inshandle: &ContainerHandle,
     global: &'static Global,
 ) -> Result<State> {
     let database = ag.flags().intersects(TransactionFlags::DATABASE_ONLY | TransactionFlags::FORCE_DATABASE);
@@ -129,15 +167,42 @@ fn confirm(
         TransactionMode::Local => false,
     };
     let confirm = foreign || database && !create;
+    let relink = !ag.flags().intersects(TransactionFlags::TARGET_ONLY | TransactionFlags::NO_FILESYSTEM_SYNC);
+    let downstream = match relink {
+        true => ag.cache().downstream(inshandle.vars().instance()),
+        false => Vec::new(),
+    };
     let sum = Summary::new()
         .kind(global.config().summary(), confirm)
         .mode(handle.get_mode())
+        .downstream(downstream)
         .generate(handle.alpm());
 
+    if ag.flags().contains(TransactionFlags::OFFLINE) {
+        if let Err(error) = check_offline(handle) {
+            handle.alpm_mut().trans_release().generic()?;
+            return Err(error);
+        }
+    }
+
+    if let TransactionMode::Local = handle.get_mode() {
+        if let Err(error) = check_quota(ag, inshandle, &sum) {
+            handle.alpm_mut().trans_release().generic()?;
+            return Err(error);
+        }
+    }
+
     if confirm {
-        println!("{}", sum);
+        if !is_porcelain() {
+            println!("{}", sum);
+        }
 
         if ag.flags().contains(TransactionFlags::PREVIEW) {
+            if !is_porcelain() {
+                println!("{} No dependency conflicts detected; file conflicts are unchecked.", *ARROW_GREEN);
+            }
+
+            sum.porcelain(inshandle.vars().instance());
             handle.alpm_mut().trans_release().generic()?;
             return Ok(State::Next(next_state(ag.action(), state, false)));
         }
@@ -153,8 +218,123 @@ fn confirm(
         }
     }
 
+    let transacted = match ag.flags().contains(TransactionFlags::VERIFY_PACKAGES) {
+        true => handle.alpm().trans_add().iter().map(|a| a.name().to_string()).collect(),
+        false => Vec::new(),
+    };
+
+    discard_partials(handle);
     handle.alpm_mut().trans_release().generic()?;
-    Ok(State::Commit(sum.download()))
+    Ok(State::Commit(sum, transacted))
+}
+
+fn verify(handle: &TransactionHandle, inshandle: &ContainerHandle, transacted: &[String]) -> Result<()> {
+    if transacted.is_empty() || matches!(handle.get_mode(), TransactionMode::Foreign) {
+        return Ok(());
+    }
+
+    let mismatches = verify_packages(handle.alpm(), inshandle.vars().root(), transacted);
+
+    for mismatch in &mismatches {
+        mismatch.print();
+    }
+
+    match mismatches.is_empty() {
+        true => Ok(()),
+        false => err!(SyncError::VerificationFailure(inshandle.vars().instance().into(), mismatches.len())),
+    }
+}
+
+/// Validate partial downloads retained in the package cache from an interrupted transaction, prior to
+/// libalpm resuming them. Partials exceeding the expected package size, or otherwise complete but with
+/// a mismatched checksum, are discarded in order to be downloaded anew; complete and valid partials
+/// are moved into place.
+fn discard_partials(handle: &TransactionHandle) {
+    let alpm = handle.alpm();
+
+    for pkg in alpm.trans_add() {
+        let (Some(file), Some(checksum)) = (pkg.filename(), pkg.sha256sum()) else {
+            continue;
+        };
+
+        for dir in alpm.cachedirs() {
+            let path = format!("{dir}/{file}.part");
+            let Ok(meta) = metadata(&path) else {
+                continue;
+            };
+            let size = meta.len();
+            let expected = pkg.size().max(0) as u64;
+            let valid = match size {
+                size if expected == 0 || size < expected => continue,
+                size if size > expected => false,
+                _ => sha256(&path).is_some_and(|sum| sum == checksum),
+            };
+
+            if valid {
+                if let Err(err) = rename(&path, format!("{dir}/{file}")) {
+                    print_warning(&format!("Failed to recover '{path}': {err}"));
+                }
+            } else if let Err(err) = remove_file(&path) {
+                print_warning(&format!("Failed to discard '{path}': {err}"));
+            } else {
+                print_warning(&format!("Discarded corrupt partial download '{file}.part'."));
+            }
+        }
+    }
+}
+
+fn sha256(path: &str) -> Option<String> {
+    let mut hasher = Sha256::new();
+
+    copy(&mut File::open(path).ok()?, &mut hasher).ok()?;
+    Some(hasher.finalize().iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+fn check_offline(handle: &TransactionHandle) -> Result<()> {
+    let alpm = handle.alpm();
+    let missing: Vec<String> = alpm
+        .trans_add()
+        .iter()
+        .filter(|pkg| match pkg.filename() {
+            Some(file) => !alpm.cachedirs().iter().any(|dir| Path::new(&format!("{dir}/{file}")).is_file()),
+            None => false,
+        })
+        .map(|pkg| format!("{}-{}", pkg.name(), pkg.version()))
+        .collect();
+
+    match missing.is_empty() {
+        true => Ok(()),
+        false => err!(SyncError::NotAvailableOffline(missing)),
+    }
+}
+
+fn check_quota(ag: &TransactionAggregator, inshandle: &ContainerHandle, sum: &Summary) -> Result<()> {
+    let instance = inshandle.vars().instance();
+    let quota = match inshandle.config().quota() {
+        Some(quota) => match ByteUnit::<i64>::from_str(quota) {
+            Ok(quota) => quota.val(),
+            Err(_) => err!(SyncError::InvalidQuota(instance.into(), quota.into()))?,
+        },
+        None => return Ok(()),
+    };
+    let projected = marginal_size(inshandle.vars().root()) as i64 + sum.marginal();
+
+    if sum.marginal() <= 0 || projected <= quota {
+        return Ok(());
+    }
+
+    let projected = projected.to_byteunit(IEC).to_string();
+    let quota = quota.to_byteunit(IEC).to_string();
+
+    if ag.flags().contains(TransactionFlags::FORCE) {
+        print_warning(&format!("Container {instance}: Quota of {quota} overridden by `--force`; projected size is {projected}."));
+        Ok(())
+    } else if ag.flags().contains(TransactionFlags::PREVIEW) {
+        error!(SyncError::QuotaExceeded(instance.into(), projected, quota)).warn();
+        Ok(())
+    } else {
+        err!(SyncError::QuotaExceeded(instance.into(), projected, quota))
+    }
 }
 
 fn next_state(action: &TransactionType, state: &TransactionState, updated: bool) -> TransactionState {
@@ -187,7 +367,7 @@ fn ready_state(action: &TransactionType, state: &TransactionState) -> Option<Tra
     }
 }
 
-fn wait_on_agent(mut agent: Child) -> Result<()> {
+fn wait_on_agent((mut agent, mut version): (Child, PipeReader)) -> Result<()> {
     match agent.wait() {
         Ok(status) => match status.code().unwrap_or(-1) {
             0 => Ok(()),
@@ -196,7 +376,16 @@ fn wait_on_agent(mut agent: Child) -> Result<()> {
             3 => err!(SyncError::ParameterAcquisitionFailure),
             4 => err!(SyncError::DeserializationFailure),
             5 => err!(SyncError::InvalidMagicNumber),
-            6 => err!(SyncError::AgentVersionMismatch),
+            6 => {
+                let mut agent = String::new();
+
+                //Agents predating the version relay report nothing thereupon
+                match version.read_to_string(&mut agent) {
+                    Ok(_) if !agent.trim().is_empty() => err!(SyncError::AgentVersionMismatch(Some(agent.trim().into()))),
+                    _ => err!(SyncError::AgentVersionMismatch(None)),
+                }
+            }
+            7 => err!(SyncError::ChecksumMismatch),
             _ =>
                 if let Some(code) = status.code() {
                     err!(SyncError::TransactionFailure(format!("General agent fault: Exit code {}", code)))
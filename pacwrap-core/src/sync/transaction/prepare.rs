@@ -18,7 +18,7 @@
  */
 use crate::{
     config::{ContainerHandle, ContainerType},
-    constants::UNIX_TIMESTAMP,
+    constants::{BOLD, RESET, UNIX_TIMESTAMP},
     err,
     sync::{
         self,
@@ -35,6 +35,7 @@ use crate::{
         },
         SyncError,
     },
+    utils::print_warning,
     Error,
     Result,
 };
@@ -85,6 +86,12 @@ impl Transaction for Prepare {
                     }
                 }
 
+                if ag.flags().contains(TransactionFlags::SYSUPGRADE_ONLY) {
+                    for pkg in handle.meta.queue.drain(..) {
+                        print_warning(&format!("{}{pkg}{}: Target ignored by `--sysupgrade-only`.", *BOLD, *RESET));
+                    }
+                }
+
                 if let Upgrade(upgrade, ..) = action {
                     if !upgrade && handle.meta.queue.is_empty() {
                         err!(SyncError::NothingToDo)?
@@ -99,7 +106,9 @@ impl Transaction for Prepare {
                     }
                 }
 
-                if let Remove(..) = action {
+                ag.pre_transaction(inshandle)?;
+
+                if let Remove(..) = ag.action() {
                     Ok(Stage)
                 } else if let ContainerType::Base = instype {
                     Ok(Stage)
@@ -61,6 +61,10 @@ impl Transaction for Stage {
             flag = TransFlag::NO_DEP_VERSION | TransFlag::DB_ONLY;
         }
 
+        if ag.flags().contains(TransactionFlags::NO_HOOKS) {
+            flag |= TransFlag::NO_HOOKS;
+        }
+
         Box::new(Self {
             state: new,
             flags: flag,
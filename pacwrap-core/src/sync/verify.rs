@@ -0,0 +1,201 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+    collections::HashSet,
+    ffi::c_int,
+    fs::File,
+    io::{copy, Read},
+    os::unix::fs::PermissionsExt,
+    path::Path,
+    slice,
+};
+
+use alpm::{Alpm, Package};
+use libarchive::archive::{Entry, FileType};
+use libarchive3_sys::ffi::Struct_archive_entry;
+use sha2::{Digest, Sha256};
+use tar::{Archive, EntryType};
+use zstd::Decoder;
+
+use crate::{
+    constants::{ARROW_RED, BOLD, RESET},
+    utils::print_warning,
+};
+
+const METADATA: [&str; 5] = [".PKGINFO", ".BUILDINFO", ".MTREE", ".INSTALL", ".CHANGELOG"];
+const DIGEST_SHA256: c_int = 4;
+
+extern "C" {
+    fn archive_entry_mode(entry: *mut Struct_archive_entry) -> u32;
+    fn archive_entry_digest(entry: *mut Struct_archive_entry, kind: c_int) -> *const u8;
+}
+
+pub struct Mismatch {
+    package: String,
+    files: Vec<String>,
+}
+
+impl Mismatch {
+    pub fn print(&self) {
+        println!(
+            "{} {}{}{}: {} file{} failed verification.",
+            *ARROW_RED,
+            *BOLD,
+            self.package,
+            *RESET,
+            self.files.len(),
+            if self.files.len() > 1 { "s" } else { "" }
+        );
+
+        for file in &self.files {
+            println!("    {file}");
+        }
+    }
+}
+
+/// Verify the files of the specified packages installed to the container root. Files absent from the
+/// root are reported against the local database's file list, and the size, permissions, and checksum of
+/// regular files are compared against the package's mtree. Absent an mtree, the contents of regular files
+/// are compared against the package archive retained in the package cache, where available.
+pub fn verify_packages(handle: &Alpm, root: &str, packages: &[String]) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+
+    for name in packages {
+        let Ok(pkg) = handle.localdb().pkg(name.as_str()) else {
+            continue;
+        };
+        let backup: HashSet<&str> = pkg.backup().iter().map(|a| a.name()).collect();
+        let mut files: Vec<String> = pkg
+            .files()
+            .files()
+            .iter()
+            .map(|a| a.name())
+            .filter(|a| Path::new(&format!("{root}/{a}")).symlink_metadata().is_err())
+            .map(|a| format!("{a} (missing)"))
+            .collect();
+
+        let archive = pkg.filename().and_then(|file| {
+            handle
+                .cachedirs()
+                .iter()
+                .map(|dir| format!("{dir}/{file}"))
+                .find(|a| Path::new(a).is_file())
+        });
+
+        match (compare_mtree(pkg, root, &backup), archive) {
+            (Some(mismatched), _) => files.extend(mismatched),
+            (None, Some(archive)) => files.extend(compare_archive(&archive, root, &backup)),
+            (None, None) => print_warning(&format!(
+                "{}-{}: Neither an mtree nor a cached package archive is available; file contents are unverified.",
+                pkg.name(),
+                pkg.version()
+            )),
+        }
+
+        if !files.is_empty() {
+            mismatches.push(Mismatch {
+                package: format!("{}-{}", pkg.name(), pkg.version()),
+                files,
+            });
+        }
+    }
+
+    mismatches
+}
+
+fn compare_mtree(pkg: &Package, root: &str, backup: &HashSet<&str>) -> Option<Vec<String>> {
+    let mtree = pkg.mtree().ok()?;
+    let mut mismatched = Vec::new();
+
+    for entry in mtree {
+        let name = entry.pathname().trim_start_matches("./");
+
+        if !matches!(entry.filetype(), FileType::RegularFile) || METADATA.contains(&name) || backup.contains(name) {
+            continue;
+        }
+
+        let path = format!("{root}/{name}");
+        let Ok(meta) = Path::new(&path).symlink_metadata() else {
+            continue;
+        };
+        //The entry remains valid for the duration of the iteration, whereupon libarchive provides either null
+        //or a pointer to the SHA-256 digest, 32 bytes in length, where specified by the mtree.
+        let (mode, digest) = unsafe {
+            let digest = archive_entry_digest(entry.entry(), DIGEST_SHA256);
+
+            (archive_entry_mode(entry.entry()), (!digest.is_null()).then(|| slice::from_raw_parts(digest, 32).to_vec()))
+        };
+
+        if meta.len() != entry.size().max(0) as u64 {
+            mismatched.push(format!("{name} (size mismatch)"));
+        } else if meta.permissions().mode() & 0o7777 != mode & 0o7777 {
+            mismatched.push(format!("{name} (permissions mismatch)"));
+        } else if digest.is_some() && File::open(&path).ok().and_then(|mut file| checksum(&mut file)) != digest {
+            mismatched.push(format!("{name} (checksum mismatch)"));
+        }
+    }
+
+    Some(mismatched)
+}
+
+fn compare_archive(path: &str, root: &str, backup: &HashSet<&str>) -> Vec<String> {
+    if !path.ends_with(".zst") {
+        return Vec::new();
+    }
+
+    let Ok(decoder) = File::open(path).and_then(Decoder::new) else {
+        return Vec::new();
+    };
+    let mut archive = Archive::new(decoder);
+    let Ok(entries) = archive.entries() else {
+        return Vec::new();
+    };
+    let mut mismatched = Vec::new();
+
+    for mut entry in entries.filter_map(|a| a.ok()) {
+        let Ok(name) = entry.path().map(|a| a.to_string_lossy().to_string()) else {
+            continue;
+        };
+
+        if entry.header().entry_type() != EntryType::Regular
+            || METADATA.contains(&name.as_str())
+            || backup.contains(name.as_str())
+        {
+            continue;
+        }
+
+        let Ok(mut file) = File::open(format!("{root}/{name}")) else {
+            continue;
+        };
+
+        if checksum(&mut entry) != checksum(&mut file) {
+            mismatched.push(format!("{name} (checksum mismatch)"));
+        }
+    }
+
+    mismatched
+}
+
+fn checksum(reader: &mut impl Read) -> Option<Vec<u8>> {
+    let mut hasher = Sha256::new();
+
+    copy(reader, &mut hasher).ok()?;
+    Some(hasher.finalize().to_vec())
+}
@@ -19,11 +19,14 @@
 
 use std::{
     env::var,
+    fs::read_to_string,
     os::unix::net::UnixStream,
     path::Path,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+use dialoguer::console::{measure_text_width, truncate_str};
+
 use crate::{
     constants::{BOLD_RED, BOLD_YELLOW, GID, RESET, UID},
     err,
@@ -32,19 +35,24 @@ use crate::{
     Result,
 };
 
-pub use ansi::{is_color_terminal, is_truecolor_terminal};
+pub use ansi::{is_color_terminal, is_truecolor_terminal, strip_ansi};
 pub use arguments::Arguments;
 pub use termcontrol::TermControl;
 
 pub mod ansi;
 pub mod arguments;
 pub mod bytebuffer;
+pub mod porcelain;
 pub mod prompt;
+pub mod status;
 pub mod table;
 pub mod termcontrol;
+pub mod verbosity;
+pub mod warnings;
 
 pub fn print_warning(message: &str) {
     eprintln!("{}warning:{} {}", *BOLD_YELLOW, *RESET, message);
+    warnings::record(message.into());
 }
 
 pub fn print_error(message: &str) {
@@ -55,6 +63,12 @@ pub fn check_socket(socket: &String) -> bool {
     UnixStream::connect(Path::new(socket)).is_ok()
 }
 
+pub fn unprivileged_userns() -> bool {
+    let sysctl = |path: &str| read_to_string(path).map(|value| value.trim() != "0").unwrap_or(true);
+
+    sysctl("/proc/sys/kernel/unprivileged_userns_clone") && sysctl("/proc/sys/user/max_user_namespaces")
+}
+
 pub fn unix_epoch_time() -> Duration {
     SystemTime::now().duration_since(UNIX_EPOCH).expect("SystemTime")
 }
@@ -63,6 +77,16 @@ pub fn whitespace(amt: usize) -> String {
     " ".repeat(amt)
 }
 
+/// Measure the width of the text upon a terminal, in columns, disregarding any ANSI escape sequences.
+pub fn text_width(text: &str) -> usize {
+    measure_text_width(text)
+}
+
+/// Truncate the text to the specified width upon a terminal, in columns, inclusive of an ellipsis.
+pub fn truncate_text(text: &str, width: usize) -> String {
+    truncate_str(text, width, "...").into_owned()
+}
+
 pub fn env_var(env: &'static str) -> Result<String> {
     match var(env) {
         Ok(var) => Ok(var),
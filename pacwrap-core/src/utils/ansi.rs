@@ -123,3 +123,17 @@ pub fn dim() -> &'static str {
         false => "",
     }
 }
+
+pub fn strip_ansi(message: &str) -> String {
+    let mut stripped = String::with_capacity(message.len());
+    let mut chars = message.chars();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\x1b' => while chars.next().is_some_and(|c| !c.is_ascii_alphabetic()) {},
+            _ => stripped.push(char),
+        }
+    }
+
+    stripped
+}
@@ -128,6 +128,18 @@ impl<'a> Arguments<'a> {
         err!(InvalidArgument::TargetUnspecified)
     }
 
+    //Remove a global option from the operand list, returning whether it was specified.
+    pub fn global(&mut self, option: &str) -> bool {
+        let len = self.operands.len();
+
+        self.operands.retain(|a| *a != Operand::Long(option));
+        self.operands.iter_mut().for_each(|a| match a {
+            Operand::LongPos(long, value) if *long == option => *a = Operand::Value(value),
+            _ => (),
+        });
+        len != self.operands.len()
+    }
+
     pub fn set_index(&mut self, index: usize) {
         self.idx = index;
         self.cur = index;
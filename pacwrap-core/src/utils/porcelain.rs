@@ -0,0 +1,70 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{utils::ansi::strip_ansi, Error};
+
+static PORCELAIN: AtomicBool = AtomicBool::new(false);
+
+/// Line-oriented status records emitted upon stdout in porcelain mode.
+///
+/// The format of these records is stable between releases: Fields are delimited by a single space, with the
+/// record keyword always first. Additional record types may be introduced, but existing records will not change.
+pub enum Record<'a> {
+    Install(&'a str, &'a str, &'a str),
+    Upgrade(&'a str, &'a str, &'a str, &'a str),
+    Downgrade(&'a str, &'a str, &'a str, &'a str),
+    Reinstall(&'a str, &'a str, &'a str),
+    Remove(&'a str, &'a str, &'a str),
+    Link(&'a str),
+    Error(i32, &'a Error),
+}
+
+impl Display for Record<'_> {
+    fn fmt(&self, fmter: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Install(ins, pkg, ver) => write!(fmter, "INSTALL {ins} {pkg} {ver}"),
+            Self::Upgrade(ins, pkg, old, new) => write!(fmter, "UPGRADE {ins} {pkg} {old} {new}"),
+            Self::Downgrade(ins, pkg, old, new) => write!(fmter, "DOWNGRADE {ins} {pkg} {old} {new}"),
+            Self::Reinstall(ins, pkg, ver) => write!(fmter, "REINSTALL {ins} {pkg} {ver}"),
+            Self::Remove(ins, pkg, ver) => write!(fmter, "REMOVE {ins} {pkg} {ver}"),
+            Self::Link(ins) => write!(fmter, "LINK {ins}"),
+            Self::Error(code, error) =>
+                write!(fmter, "ERROR {code} {}", strip_ansi(&error.to_string()).lines().collect::<Vec<_>>().join(" ")),
+        }
+    }
+}
+
+pub fn enable() {
+    PORCELAIN.store(true, Ordering::Relaxed);
+}
+
+pub fn is_porcelain() -> bool {
+    PORCELAIN.load(Ordering::Relaxed)
+}
+
+pub fn emit(record: Record) {
+    if is_porcelain() {
+        println!("{record}");
+    }
+}
@@ -31,7 +31,7 @@ use dialoguer::{
 use crate::{
     constants::{BAR_RED, BOLD, RESET},
     err,
-    impl_error,
+    error::{EXIT_GENERIC, EXIT_INTERRUPTED},
     Error,
     ErrorGeneric,
     ErrorTrait,
@@ -53,7 +53,14 @@ impl Display for PromptError {
     }
 }
 
-impl_error!(PromptError);
+impl ErrorTrait for PromptError {
+    fn code(&self) -> i32 {
+        match self {
+            Self::PromptInterrupted => EXIT_INTERRUPTED,
+            Self::PromptNotTerminal => EXIT_GENERIC,
+        }
+    }
+}
 
 pub fn prompt(prefix: &str, prompt: impl Into<String>, yn_prompt: bool) -> Result<bool> {
     let value = create_prompt(prompt.into(), prefix, yn_prompt)?;
@@ -0,0 +1,158 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use std::{
+    env,
+    fs::{remove_file, symlink_metadata, File},
+    io::{BufRead, BufReader, Write},
+    os::{
+        fd::FromRawFd,
+        unix::{
+            fs::FileTypeExt,
+            net::{UnixListener, UnixStream},
+        },
+    },
+    sync::{Mutex, OnceLock},
+    thread,
+};
+
+use os_pipe::PipeWriter;
+use serde::Serialize;
+
+use crate::{err, Error, ErrorGeneric, ErrorKind, Result};
+
+/// Environment variable by which the agent is informed of the status relay's file descriptor.
+pub const STATUS_FD: &str = "PACWRAP_STATUS_FD";
+
+static SOCKET: OnceLock<String> = OnceLock::new();
+static RELAY: OnceLock<Mutex<File>> = OnceLock::new();
+static CLIENTS: Mutex<Vec<UnixStream>> = Mutex::new(Vec::new());
+
+/// Events broadcast as JSON lines to clients connected to the status socket.
+///
+/// Events are serialized with an `event` tag denoting their type. Additional events may be introduced,
+/// hence clients ought to disregard events they do not recognise.
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Status<'a> {
+    ContainerStarted { container: &'a str },
+    ContainerComplete { container: &'a str, updated: bool },
+    ContainerFailed { container: &'a str, error: String },
+    PackageDownloaded { file: &'a str, bytes: u64, result: &'a str },
+    LinkProgress { container: &'a str, linked: usize, total: usize },
+    Complete { failed: usize },
+}
+
+/// Bind the status socket at the specified path, and accept clients upon a background thread.
+///
+/// Clients are written to in a non-blocking fashion: A client which fails to keep up is disconnected,
+/// rather than stall the transaction.
+pub fn bind(path: &str) -> Result<()> {
+    if let Ok(meta) = symlink_metadata(path) {
+        if !meta.file_type().is_socket() {
+            err!(ErrorKind::Message("Status socket path exists and is not a socket."))?
+        }
+
+        remove_file(path).prepend_io(|| path.into())?;
+    }
+
+    let listener = UnixListener::bind(path).prepend_io(|| path.into())?;
+
+    SOCKET.get_or_init(|| path.into());
+    thread::Builder::new()
+        .name("pacwrap-status".into())
+        .spawn(move || {
+            for stream in listener.incoming().map_while(|stream| stream.ok()) {
+                if stream.set_nonblocking(true).is_ok() {
+                    if let Ok(mut clients) = CLIENTS.lock() {
+                        clients.push(stream);
+                    }
+                }
+            }
+        })
+        .prepend_io(|| path.into())?;
+    Ok(())
+}
+
+pub fn is_active() -> bool {
+    SOCKET.get().is_some()
+}
+
+/// Provision a pipe to relay events from the agent to connected clients. Events written to the pipe
+/// are rebroadcast verbatim until the writer, and all copies thereof, are closed.
+pub fn relay() -> Result<Option<PipeWriter>> {
+    if !is_active() {
+        return Ok(None);
+    }
+
+    let (reader, writer) = os_pipe::pipe().prepend_io(|| "Status relay".into())?;
+
+    thread::Builder::new()
+        .name("pacwrap-status-relay".into())
+        .spawn(move || {
+            for line in BufReader::new(reader).lines().map_while(|line| line.ok()) {
+                broadcast(&line);
+            }
+        })
+        .prepend_io(|| "Status relay".into())?;
+    Ok(Some(writer))
+}
+
+/// Adopt the relay file descriptor provisioned by the host, if any, from within the agent.
+pub fn adopt() {
+    if let Some(fd) = env::var(STATUS_FD).ok().and_then(|fd| fd.parse().ok()) {
+        RELAY.get_or_init(|| Mutex::new(unsafe { File::from_raw_fd(fd) }));
+    }
+}
+
+pub fn emit(status: Status) {
+    if SOCKET.get().is_none() && RELAY.get().is_none() {
+        return;
+    }
+
+    if let Ok(line) = serde_json::to_string(&status) {
+        broadcast(&line);
+    }
+}
+
+/// Remove the status socket, if bound, upon completion.
+pub fn release() {
+    if let Some(path) = SOCKET.get() {
+        if let Ok(mut clients) = CLIENTS.lock() {
+            clients.clear();
+        }
+
+        let _ = remove_file(path);
+    }
+}
+
+fn broadcast(line: &str) {
+    if let Some(relay) = RELAY.get() {
+        if let Ok(mut relay) = relay.lock() {
+            let _ = writeln!(relay, "{line}");
+        }
+
+        return;
+    }
+
+    let line = format!("{line}\n");
+
+    if let Ok(mut clients) = CLIENTS.lock() {
+        clients.retain_mut(|client| client.write_all(line.as_bytes()).is_ok());
+    }
+}
@@ -149,6 +149,10 @@ impl<'a> Table<'a> {
         self.rows.len() - 1
     }
 
+    pub fn width(&self) -> usize {
+        self.width_max
+    }
+
     pub fn mark(&mut self, col: usize) {
         self.marker.push(col);
     }
@@ -0,0 +1,50 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Escalating levels of verbosity, specified on the command line with `-v` up to three times, or
+//! otherwise with `PACWRAP_VERBOSE`. The greater of the two is in effect.
+
+use std::{
+    env,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use crate::constants::VERBOSE;
+
+pub const AGENT_VERBOSITY: &str = "PACWRAP_AGENT_VERBOSITY";
+pub const WARNINGS: u8 = 1;
+pub const ALPM: u8 = 2;
+pub const ARGUMENTS: u8 = 3;
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+pub fn set(level: u8) {
+    VERBOSITY.store(level.min(ARGUMENTS), Ordering::Relaxed);
+}
+
+pub fn level() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed).max(*VERBOSE)
+}
+
+/// Adopt the verbosity level conveyed by the host to the transaction agent.
+pub fn adopt() {
+    if let Some(level) = env::var(AGENT_VERBOSITY).ok().and_then(|level| level.parse().ok()) {
+        set(level);
+    }
+}
@@ -0,0 +1,148 @@
+/*
+ * pacwrap-core
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This library is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+    env,
+    fs::File,
+    io::{BufRead, BufReader, Write},
+    os::fd::FromRawFd,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+        OnceLock,
+    },
+    thread,
+};
+
+use os_pipe::PipeWriter;
+
+use crate::{
+    constants::{BOLD, BOLD_YELLOW, RESET},
+    utils::porcelain::is_porcelain,
+    ErrorGeneric,
+    Result,
+};
+
+/// Environment variable by which the agent is informed of the warning relay's file descriptor.
+pub const WARNING_FD: &str = "PACWRAP_WARNING_FD";
+
+static SUMMARY: AtomicBool = AtomicBool::new(true);
+static RELAY: OnceLock<Mutex<File>> = OnceLock::new();
+static CONTEXT: Mutex<Option<String>> = Mutex::new(None);
+static WARNINGS: Mutex<Vec<(Option<String>, String)>> = Mutex::new(Vec::new());
+
+pub fn disable() {
+    SUMMARY.store(false, Ordering::Relaxed);
+}
+
+/// Attribute warnings emitted hereafter to the container specified, until the context is cleared.
+pub fn context(container: Option<&str>) {
+    if let Ok(mut context) = CONTEXT.lock() {
+        *context = container.map(|a| a.into());
+    }
+}
+
+pub fn record(message: String) {
+    if let Some(relay) = RELAY.get() {
+        if let (Ok(mut relay), Ok(message)) = (relay.lock(), serde_json::to_string(&message)) {
+            let _ = writeln!(relay, "{message}");
+        }
+
+        return;
+    }
+
+    insert(CONTEXT.lock().ok().and_then(|a| a.clone()), message);
+}
+
+/// Provision a pipe to relay warnings from the agent, whereupon they are recorded under the context in
+/// effect at the time of provisioning.
+pub fn relay() -> Result<Option<PipeWriter>> {
+    if !SUMMARY.load(Ordering::Relaxed) || is_porcelain() {
+        return Ok(None);
+    }
+
+    let (reader, writer) = os_pipe::pipe().prepend_io(|| "Warning relay".into())?;
+    let context = CONTEXT.lock().ok().and_then(|a| a.clone());
+
+    thread::Builder::new()
+        .name("pacwrap-warning-relay".into())
+        .spawn(move || {
+            for line in BufReader::new(reader).lines().map_while(|line| line.ok()) {
+                if let Ok(message) = serde_json::from_str(&line) {
+                    insert(context.clone(), message);
+                }
+            }
+        })
+        .prepend_io(|| "Warning relay".into())?;
+    Ok(Some(writer))
+}
+
+/// Adopt the relay file descriptor provisioned by the host, if any, from within the agent.
+pub fn adopt() {
+    if let Some(fd) = env::var(WARNING_FD).ok().and_then(|fd| fd.parse().ok()) {
+        RELAY.get_or_init(|| Mutex::new(unsafe { File::from_raw_fd(fd) }));
+    }
+}
+
+fn insert(context: Option<String>, message: String) {
+    if let Ok(mut warnings) = WARNINGS.lock() {
+        if !warnings.iter().any(|(ins, msg)| *ins == context && *msg == message) {
+            warnings.push((context, message));
+        }
+    }
+}
+
+/// Reprint warnings collected over the course of this invocation, grouped by container in order of first emission.
+pub fn summarize() {
+    if !SUMMARY.load(Ordering::Relaxed) || is_porcelain() {
+        return;
+    }
+
+    let warnings = match WARNINGS.lock() {
+        Ok(warnings) if !warnings.is_empty() => warnings,
+        _ => return,
+    };
+    let mut groups: Vec<&Option<String>> = Vec::new();
+
+    for (ins, _) in warnings.iter() {
+        if !groups.contains(&ins) {
+            groups.push(ins);
+        }
+    }
+
+    eprintln!(
+        "\n{}warning:{} {} warning{} emitted:",
+        *BOLD_YELLOW,
+        *RESET,
+        warnings.len(),
+        if warnings.len() > 1 { "s" } else { "" }
+    );
+
+    for group in groups {
+        match group {
+            Some(ins) => eprintln!("  {}{ins}{}:", *BOLD, *RESET),
+            None if warnings.iter().any(|(ins, _)| ins.is_some()) => eprintln!("  {}General{}:", *BOLD, *RESET),
+            None => (),
+        }
+
+        for (_, message) in warnings.iter().filter(|(ins, _)| ins == group) {
+            eprintln!("    - {message}");
+        }
+    }
+}
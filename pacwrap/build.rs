@@ -61,6 +61,18 @@ fn mtime() -> String {
         .expect("'date': executable not found in PATH")
 }
 
+fn epoch() -> String {
+    if let Ok(time) = var("PACWRAP_BUILDTIME") {
+        return time;
+    }
+
+    Command::new("date")
+        .args(["+%s"])
+        .output()
+        .map(|output| String::from_utf8(output.stdout).expect("Invalid UTF-8 value"))
+        .expect("'date': executable not found in PATH")
+}
+
 fn release(debug: bool) -> &'static str {
     match debug {
         true => "DEV",
@@ -88,8 +100,10 @@ fn main() {
     println!("cargo:rerun-if-env-changed=PACWRAP_DIST_META");
     println!("cargo:rerun-if-env-changed=PACWRAP_DIST_FS");
     println!("cargo:rerun-if-env-changed=PACWRAP_DIST_REPO");
+    println!("cargo:rerun-if-env-changed=PACWRAP_BUILDTIME");
     println!("cargo:rustc-env=PACWRAP_BUILD={}", release(debug));
     println!("cargo:rustc-env=PACWRAP_BUILDSTAMP={}", time(debug));
+    println!("cargo:rustc-env=PACWRAP_BUILDTIME={}", epoch());
     println!("cargo:rustc-env=PACWRAP_BUILDHEAD={}", head());
     println!("cargo:rustc-env=PACWRAP_BUILDTAG={}", tag());
 
@@ -17,36 +17,82 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs::{remove_dir_all, remove_file, rename, symlink_metadata},
+    path::Path,
+};
 
 use pacwrap_core::{
-    config::{cache, compose_handle, init::init, ContainerCache, ContainerHandle, ContainerType::*},
+    config::{
+        cache,
+        compose_handle,
+        init::init,
+        manifest_handles,
+        ContainerCache,
+        ContainerHandle,
+        ContainerType::*,
+        ContainerVariables,
+    },
     constants::{ARROW_GREEN, BAR_GREEN, BOLD, RESET},
     err,
+    impl_error,
     lock::Lock,
     log::{Level::Info, Logger},
     sync::{
+        event::summary::override_summary,
+        filesystem::FilesystemSync,
         instantiate_container,
         instantiate_trust,
         transaction::{TransactionAggregator, TransactionFlags, TransactionType},
     },
     utils::{
         arguments::{Arguments, InvalidArgument::*, Operand as Op},
-        check_root,
         print_warning,
         prompt::prompt_targets,
+        status,
+        verbosity,
     },
     Error,
     ErrorGeneric,
     ErrorKind,
+    ErrorTrait,
     ErrorType,
     Result,
 };
 
-use crate::utils::delete::delete_roots;
+use crate::utils::delete::check_processes;
+
+#[derive(Debug)]
+enum ComposeError {
+    DanglingDependency(String, String),
+    DependencyCycle(Vec<String>),
+    DuplicateTarget(String),
+}
+
+impl_error!(ComposeError);
+
+impl Display for ComposeError {
+    fn fmt(&self, fmter: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::DanglingDependency(ins, dep) => write!(
+                fmter,
+                "Container {}{ins}{}: Dependency '{}{dep}{}' is neither present in the manifest nor exists.",
+                *BOLD,
+                *RESET,
+                *BOLD,
+                *RESET
+            ),
+            Self::DependencyCycle(ins) =>
+                write!(fmter, "Dependency cycle detected in manifest between containers: {}{}{}", *BOLD, ins.join(", "), *RESET),
+            Self::DuplicateTarget(ins) =>
+                write!(fmter, "Container {}{ins}{} is specified both in the manifest and as a target.", *BOLD, *RESET),
+        }
+    }
+}
 
 pub fn compose(args: &mut Arguments) -> Result<()> {
-    check_root()?;
     init()?;
 
     let lock = Lock::new().lock()?;
@@ -59,59 +105,148 @@ pub fn compose(args: &mut Arguments) -> Result<()> {
     result
 }
 
-fn delete_containers<'a>(
-    cache: &'a ContainerCache<'a>,
-    lock: &'a Lock,
-    logger: &mut Logger,
-    delete: &[&str],
-    flags: &TransactionFlags,
-    force: bool,
-) -> Result<()> {
-    let message = format!("Deleting existing container{}?", if delete.len() > 1 { "s" } else { "" });
+fn confirm_reinitialize(cache: &ContainerCache, delete: &[&str], flags: &TransactionFlags, force: bool) -> Result<bool> {
+    let message = format!("Reinitialize existing container{}?", if delete.len() > 1 { "s" } else { "" });
+
+    check_processes(cache, delete, force)?;
 
     if flags.contains(TransactionFlags::NO_CONFIRM) {
         println!("{} {}{}...{}", *BAR_GREEN, *BOLD, &message, *RESET);
-        delete_roots(cache, lock, logger, delete, force)?;
-    } else if prompt_targets(delete, &message, false)? {
-        delete_roots(cache, lock, logger, delete, force)?;
+        Ok(true)
+    } else {
+        prompt_targets(delete, &message, false)
+    }
+}
+
+fn remove_root(root: &str) -> Result<()> {
+    match symlink_metadata(root) {
+        Ok(meta) if meta.is_dir() => remove_dir_all(root).prepend(|| format!("Failed to delete container root '{root}'")),
+        Ok(_) => remove_file(root).prepend_io(|| root.into()),
+        Err(_) => Ok(()),
+    }
+}
+
+fn swap_roots(lock: &Lock, logger: &mut Logger, swap: &[&str]) -> Result<()> {
+    for instance in swap {
+        let vars = ContainerVariables::new(instance);
+        let root = vars.root();
+        let staged = vars.clone().staged();
+        let staged = staged.root();
+        let retired = format!("{}.retired", staged.trim_end_matches(".staged"));
+
+        lock.assert()?;
+        remove_root(&retired)?;
+        rename(root, &retired).prepend_io(|| root.into())?;
+
+        if let Err(error) = rename(staged, root).prepend_io(|| staged.into()) {
+            rename(&retired, root).prepend_io(|| retired.clone())?;
+            Err(error)?
+        }
+
+        remove_root(&retired)?;
+        logger.log(Info, &format!("Reinitialized container {instance}"))?;
     }
 
     Ok(())
 }
 
-fn compose_handles<'a>(
-    cache: &ContainerCache<'a>,
-    compose: HashMap<&'a str, Option<&'a str>>,
-) -> Result<HashMap<&'a str, ContainerHandle<'a>>> {
-    let mut composed = HashMap::new();
+fn synchronize_filesystems(lock: &Lock) -> Result<()> {
+    let cache = cache::populate()?;
+    let registered = cache.registered();
+    let mut linker = FilesystemSync::new(&cache).assert_lock(Some(lock));
+
+    linker.filesystem_state();
+    linker.prepare(registered.len(), None);
+    linker.engage(&registered)?;
+    linker.finish(None);
+    linker.release();
+    Ok(())
+}
+
+fn check_type(handle: &ContainerHandle) -> Result<()> {
+    let container_type = handle.metadata().container_type();
+    let depends = handle.metadata().dependencies();
+
+    if let Symbolic = container_type {
+        if depends.is_empty() {
+            err!(ErrorKind::Message("Symbolic containers require at least one dependency."))?;
+        }
+    } else if let Base = container_type {
+        if !depends.is_empty() {
+            err!(ErrorKind::Message("Dependencies cannot be assigned to base containers."))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn compose_handles<'a>(compose: HashMap<&'a str, Option<&'a str>>, staged: &[&str]) -> Result<Vec<ContainerHandle<'a>>> {
+    let mut composed = Vec::new();
 
     for (instance, config) in compose {
-        let handle = compose_handle(instance, config)?;
-        let container_type = handle.metadata().container_type();
-        let depends = handle.metadata().dependencies();
+        //Stale staged roots, left behind by an interrupted reinitialization, are removed prior to composition.
+        if staged.contains(&instance) {
+            remove_root(ContainerVariables::new(instance).staged().root())?;
+        }
 
-        if let Symbolic = container_type {
-            if depends.is_empty() {
-                err!(ErrorKind::Message("Symbolic containers require at least one dependency."))?;
-            }
-        } else if let Base = container_type {
-            if !depends.is_empty() {
-                err!(ErrorKind::Message("Dependencies cannot be assigned to base containers."))?;
+        composed.push(compose_handle(instance, config, staged.contains(&instance))?);
+    }
+
+    Ok(composed)
+}
+
+fn sort_manifest<'a>(
+    cache: &ContainerCache<'a>,
+    handles: Vec<ContainerHandle<'a>>,
+) -> Result<Vec<(&'a str, ContainerHandle<'a>)>> {
+    let mut depends: HashMap<&'a str, Vec<String>> = HashMap::new();
+    let mut pending: HashMap<&'a str, ContainerHandle<'a>> = HashMap::new();
+    let mut sorted = Vec::new();
+
+    for handle in handles {
+        let instance: &'a str = handle.vars().instance().to_string().leak();
+
+        check_type(&handle)?;
+        depends.insert(instance, handle.metadata().dependencies().iter().map(|a| a.to_string()).collect());
+        pending.insert(instance, handle);
+    }
+
+    for (instance, deps) in depends.iter() {
+        for dep in deps {
+            if !pending.contains_key(dep.as_str()) && cache.get_instance_option(dep).is_none() {
+                err!(ComposeError::DanglingDependency(instance.to_string(), dep.into()))?
             }
         }
+    }
+
+    while !pending.is_empty() {
+        let mut ready: Vec<&'a str> = pending
+            .keys()
+            .filter(|a| depends[*a].iter().all(|dep| !pending.contains_key(dep.as_str())))
+            .copied()
+            .collect();
 
-        for target in depends {
-            cache.get_instance(target)?;
+        if ready.is_empty() {
+            let mut cycle: Vec<String> = pending.keys().map(|a| a.to_string()).collect();
+
+            cycle.sort();
+            err!(ComposeError::DependencyCycle(cycle))?
         }
 
-        composed.insert(instance, handle);
+        ready.sort();
+
+        for instance in ready {
+            if let Some(handle) = pending.remove(instance) {
+                sorted.push((instance, handle));
+            }
+        }
     }
 
-    Ok(composed)
+    Ok(sorted)
 }
 
 fn instantiate<'a>(
-    composed: HashMap<&'a str, ContainerHandle<'a>>,
+    composed: Vec<(&'a str, ContainerHandle<'a>)>,
     mut cache: ContainerCache<'a>,
     lock: &'a Lock,
     logger: &mut Logger,
@@ -160,19 +295,19 @@ fn acquire_targets<'a>(
 }
 
 fn engage_aggregator(args: &mut Arguments, lock: &Lock) -> Result<()> {
-    let mut cache = match args.into_iter().find(|a| *a == Op::Long("from-config")) {
+    let cache = match args.into_iter().find(|a| *a == Op::Long("from-config")) {
         Some(_) => cache::populate_config(),
         None => cache::populate(),
     }?;
     let mut flags = TransactionFlags::CREATE | TransactionFlags::FORCE_DATABASE;
     let mut logger = Logger::new("pacwrap-compose").init()?;
-    let mut targets = Vec::new();
     let mut delete = Vec::new();
     let mut compose = HashMap::new();
-    let mut queue = HashMap::new();
     let mut force = false;
+    let mut verbose = 0;
     let mut reinitialize = false;
     let mut current_target = None;
+    let mut manifest = None;
 
     if args.len() <= 1 {
         err!(OperationUnspecified)?
@@ -184,8 +319,20 @@ fn engage_aggregator(args: &mut Arguments, lock: &Lock) -> Result<()> {
         match arg {
             Op::Long("from-config") => continue,
             Op::Long("debug") => flags |= TransactionFlags::DEBUG,
+            Op::Long("trace") => flags |= TransactionFlags::TRACE,
             Op::Long("noconfirm") => flags |= TransactionFlags::NO_CONFIRM,
             Op::Long("disable-sandbox") => flags |= TransactionFlags::NO_ALPM_SANDBOX,
+            Op::Short('v') | Op::Long("verbose") => verbose += 1,
+            Op::Long("summary-format") => match args.next() {
+                Some(Op::LongPos("summary-format", format)) => override_summary(format)?,
+                Some(_) => args.invalid_operand()?,
+                None => err!(UnsuppliedOperand("--summary-format", "Summary format not specified."))?,
+            },
+            Op::Long("status-socket") => match args.next() {
+                Some(Op::LongPos("status-socket", path)) => status::bind(path)?,
+                Some(_) => args.invalid_operand()?,
+                None => err!(UnsuppliedOperand("--status-socket", "Socket path not specified."))?,
+            },
             Op::Long("reinitialize-all") =>
                 for instance in cache.registered() {
                     if let Some(handle) = cache.get_instance_option(instance) {
@@ -198,6 +345,13 @@ fn engage_aggregator(args: &mut Arguments, lock: &Lock) -> Result<()> {
                 },
             Op::Short('l') | Op::Long("lazy-load") => flags |= TransactionFlags::LAZY_LOAD_DB,
             Op::Short('f') | Op::Long("force") => force = true,
+            Op::Short('m') | Op::Long("manifest") => match args.next() {
+                Some(arg) => match arg {
+                    Op::ShortPos('m', path) | Op::LongPos("manifest", path) => manifest = Some(path),
+                    _ => args.invalid_operand()?,
+                },
+                None => err!(ErrorKind::Message("Manifest file not specified."))?,
+            },
             Op::Short('r') | Op::Long("reinitialize") => reinitialize = true,
             Op::Short('t') | Op::Long("target") => match args.next() {
                 Some(arg) => match arg {
@@ -236,12 +390,23 @@ fn engage_aggregator(args: &mut Arguments, lock: &Lock) -> Result<()> {
         }
     }
 
-    if compose.is_empty() {
+    if compose.is_empty() && manifest.is_none() {
         err!(ErrorKind::Message("Composition targets not specified."))?
     }
 
-    if !delete.is_empty() {
-        delete_containers(&cache, lock, &mut logger, &delete, &flags, force)?;
+    verbosity::set(verbose);
+
+    let manifest = match manifest {
+        Some(path) => manifest_handles(path)?,
+        None => Vec::new(),
+    };
+
+    if let Some(handle) = manifest.iter().find(|a| compose.contains_key(a.vars().instance())) {
+        err!(ComposeError::DuplicateTarget(handle.vars().instance().into()))?
+    }
+
+    if !delete.is_empty() && !confirm_reinitialize(&cache, &delete, &flags, force)? {
+        return Ok(());
     }
 
     if flags.contains(TransactionFlags::LAZY_LOAD_DB) {
@@ -250,10 +415,56 @@ fn engage_aggregator(args: &mut Arguments, lock: &Lock) -> Result<()> {
         print_warning("See `--help compose` or the pacwrap(1) man page for further information.");
     }
 
-    cache = instantiate(compose_handles(&cache, compose)?, cache, lock, &mut logger)?;
+    let mut handles = compose_handles(compose, &delete)?;
+
+    handles.extend(manifest);
+
+    let composed = sort_manifest(&cache, handles)?;
+    let result = compose_containers(composed, cache, lock, &mut logger, flags);
+
+    if delete.is_empty() {
+        return result;
+    }
+
+    match result {
+        Ok(_) => swap_roots(lock, &mut logger, &delete)?,
+        Err(error) => {
+            for instance in &delete {
+                remove_root(ContainerVariables::new(instance).staged().root())?;
+            }
+
+            synchronize_filesystems(lock)?;
+            Err(error)?
+        }
+    }
+
+    synchronize_filesystems(lock)
+}
+
+/// Compose a single container from the specified configuration, as is required to restore a backup.
+pub fn compose_config<'a>(instance: &'a str, config: &'a str, lock: &'a Lock) -> Result<()> {
+    let cache = cache::populate()?;
+    let mut logger = Logger::new("pacwrap-compose").init()?;
+    let flags = TransactionFlags::CREATE | TransactionFlags::FORCE_DATABASE;
+    let composed = sort_manifest(&cache, compose_handles(HashMap::from([(instance, Some(config))]), &[])?)?;
+
+    compose_containers(composed, cache, lock, &mut logger, flags)
+}
+
+fn compose_containers<'a>(
+    composed: Vec<(&'a str, ContainerHandle<'a>)>,
+    cache: ContainerCache<'a>,
+    lock: &'a Lock,
+    logger: &mut Logger,
+    flags: TransactionFlags,
+) -> Result<()> {
+    let mut targets = Vec::new();
+    let mut queue = HashMap::new();
+    let cache = instantiate(composed, cache, lock, logger)?;
+
     acquire_targets(&cache, &mut targets, &mut queue)?;
     instantiate_trust()?;
-    TransactionAggregator::new(&cache, &mut logger, TransactionType::Upgrade(true, true, false))
+    TransactionAggregator::new(&cache, logger, TransactionType::Upgrade(true, true, false))
         .assert_lock(lock)?
         .target(Some(targets))
         .flag(flags)
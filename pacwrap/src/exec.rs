@@ -19,35 +19,40 @@
 
 use std::{
     fmt::{Display, Formatter},
-    fs::{remove_file, File},
-    os::unix::io::AsRawFd,
+    fs::{canonicalize, create_dir_all, remove_file, File},
+    io::Error as IOError,
+    os::unix::{io::AsRawFd, process::CommandExt},
     path::Path,
     process::{Child, Command},
+    str::FromStr,
     thread,
-    time::Duration,
+    time::{Duration, Instant},
     vec::Vec,
 };
 
 use command_fds::{CommandFdExt, FdMapping};
 use nix::{
     sys::signal::{kill, Signal},
-    unistd::Pid,
+    unistd::{write, Pid},
 };
 use signal_hook::iterator::Signals;
+use simplebyteunit::simplebyteunit::*;
 
 use pacwrap_core::{
     config::{
         self,
+        global,
         register::{register_dbus, register_filesystems, register_permissions},
         ContainerHandle,
-        ContainerType::Slice,
+        ContainerType::{Base, Slice},
         Dbus,
+        NetworkMode,
     },
     constants::{
-        BWRAP_EXECUTABLE,
         DBUS_PROXY_EXECUTABLE,
         DBUS_SOCKET,
         DEFAULT_PATH,
+        HOME,
         IS_COLOR_TERMINAL,
         SIGNAL_LIST,
         XDG_RUNTIME_DIR,
@@ -56,19 +61,24 @@ use pacwrap_core::{
     error,
     exec::{
         args::{Argument, ExecutionArgs},
+        bwrap_executable,
+        cgroup,
+        check_userns,
         fakeroot_container,
         path::check_path,
+        print_fakeroot_container,
         seccomp::{configure_bpf_program, provide_bpf_program},
-        utils::{decode_info_json, wait_on_container},
+        utils::{decode_info_json, print_command, wait_on_container, watch_idle},
         ExecutionError,
         ExecutionType::Interactive,
     },
     impl_error,
+    sync::schema,
     utils::{
         self,
         arguments::{Arguments, InvalidArgument, Operand as Op},
-        check_root,
         env_var,
+        print_warning,
         TermControl,
     },
     Error,
@@ -79,12 +89,20 @@ use pacwrap_core::{
 };
 
 static SOCKET_SLEEP_DURATION: Duration = Duration::from_micros(500);
+const BUILD_DIR: &str = "/build";
+const BUILD_ENV: [(&str, &str); 4] = [
+    ("BUILDDIR", "/build/src"),
+    ("PKGDEST", "/build/packages"),
+    ("SRCDEST", "/build/sources"),
+    ("LOGDEST", "/build/logs"),
+];
 
 #[derive(Debug)]
 enum ExecError {
     NestedNamespaceEnablement,
     ConsoleSessionRetention,
     SeccompDisablement,
+    HostNetworkSharing,
 }
 
 impl_error!(ExecError);
@@ -93,6 +111,8 @@ impl Display for ExecError {
     fn fmt(&self, fmter: &mut Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
         match self {
             Self::SeccompDisablement => write!(fmter, "Disabling seccomp filtering can allow for sandbox escape."),
+            Self::HostNetworkSharing =>
+                write!(fmter, "Sharing the host's network namespace permits the container to reach the network directly."),
             Self::NestedNamespaceEnablement =>
                 write!(fmter, "Namespace nesting has been known in the past to enable container escape vulnerabilities."),
             Self::ConsoleSessionRetention => write!(
@@ -103,9 +123,20 @@ impl Display for ExecError {
     }
 }
 
+struct ExecOptions {
+    verbosity: i8,
+    shell: bool,
+    print_args: bool,
+    dbus_proxy: bool,
+    dbus_timeout: Option<u64>,
+    idle_timeout: Option<Duration>,
+    network: Option<NetworkMode>,
+    tmp_size: Option<u64>,
+}
+
 enum ExecParams<'a> {
-    FakeRoot(i8, bool, Vec<&'a str>, ContainerHandle<'a>),
-    Container(i8, bool, Vec<&'a str>, ContainerHandle<'a>),
+    FakeRoot(ExecOptions, Vec<&'a str>, ContainerHandle<'a>),
+    Container(ExecOptions, Vec<&'a str>, ContainerHandle<'a>),
 }
 
 impl<'a> ExecParams<'a> {
@@ -113,6 +144,14 @@ impl<'a> ExecParams<'a> {
         let mut verbosity: i8 = 0;
         let mut shell = matches!(args[0], Op::Value("shell"));
         let mut root = false;
+        let mut keep_mounts = false;
+        let mut print_args = false;
+        let mut dbus_proxy = true;
+        let mut dbus_timeout = None;
+        let mut idle_timeout = None;
+        let mut network = None;
+        let mut tmp_size = None;
+        let mut config = None;
         let mut container = None;
         let mut pos = 1;
 
@@ -130,6 +169,29 @@ impl<'a> ExecParams<'a> {
                 Op::Long("root") | Op::Short('r') => root = true,
                 Op::Long("shell") | Op::Short('s') => shell = true,
                 Op::Long("verbose") | Op::Short('v') => verbosity += 1,
+                Op::Long("keep-mounts") => keep_mounts = true,
+                Op::Long("print-bwrap-args") => print_args = true,
+                Op::Long("no-dbus-proxy") => dbus_proxy = false,
+                Op::Long("dbus-timeout") => continue,
+                Op::LongPos("dbus-timeout", timeout) => match timeout.parse() {
+                    Ok(timeout) => dbus_timeout = Some(timeout),
+                    Err(_) => err!(ErrorKind::Message("Timeout can only be specified in milliseconds with a valid integer."))?,
+                },
+                Op::Long("idle-timeout") => continue,
+                Op::LongPos("idle-timeout", timeout) => match timeout.parse() {
+                    Ok(timeout) => idle_timeout = Some(Duration::from_secs(timeout)),
+                    Err(_) => err!(ErrorKind::Message("Idle timeout can only be specified in seconds with a valid integer."))?,
+                },
+                Op::Long("network") => continue,
+                Op::LongPos("network", mode) => match mode {
+                    "host" => network = Some(NetworkMode::Host),
+                    "none" => network = Some(NetworkMode::None),
+                    _ => err!(ErrorKind::Message("Network mode can only be specified as either 'host' or 'none'."))?,
+                },
+                Op::Long("isolate-tmp") => continue,
+                Op::LongPos("isolate-tmp", size) => tmp_size = Some(tmpfs_size(size)?),
+                Op::Long("container-config") => continue,
+                Op::LongPos("container-config", path) => config = Some(path),
                 Op::LongPos(_, str) | Op::ShortPos(_, str) | Op::Value(str) =>
                     if container.is_none() {
                         container = Some(str);
@@ -139,39 +201,82 @@ impl<'a> ExecParams<'a> {
             }
         }
 
-        let handle = match container {
-            Some(container) => config::provide_handle(container)?,
-            None => err!(InvalidArgument::TargetUnspecified)?,
+        let handle = match (container, config) {
+            (Some(container), Some(path)) => config::override_handle(container, path)?,
+            (Some(container), None) => config::provide_handle(container)?,
+            (None, _) => err!(InvalidArgument::TargetUnspecified)?,
         };
         let runtime = args.into_inner(pos);
+        let shell = shell || keep_mounts;
 
         if let (Slice, false, ..) = (handle.metadata().container_type(), root, shell) {
             err!(ErrorKind::Message("Execution in container filesystem segments is not supported."))?
         }
 
-        check_root()?;
+        let options = ExecOptions {
+            verbosity,
+            shell,
+            print_args,
+            dbus_proxy,
+            dbus_timeout,
+            idle_timeout,
+            network,
+            tmp_size,
+        };
+
+        if keep_mounts {
+            print_warning("Debug shell mode engaged by `--keep-mounts`: the requested command will not be executed.");
+        }
+
+        check_schema(&handle);
+
         Ok(match root {
-            true => Self::FakeRoot(verbosity, shell, runtime, handle),
-            false => Self::Container(verbosity, shell, runtime, handle),
+            true => Self::FakeRoot(options, runtime, handle),
+            false => Self::Container(options, runtime, handle),
         })
     }
 }
 
+fn tmpfs_size(size: &str) -> Result<u64> {
+    match ByteUnit::<i64>::from_str(size) {
+        Ok(size) if size.val() > 0 => Ok(size.val() as u64),
+        _ => err!(ErrorKind::Message("Size of /tmp must be specified with a valid, non-zero byte unit, e.g. 512MiB.")),
+    }
+}
+
+fn check_schema(handle: &ContainerHandle) {
+    if let Base = handle.metadata().container_type() {
+        return schema::warn_outdated(handle);
+    }
+
+    for dep in handle.metadata().dependencies() {
+        if let Ok(dep) = config::provide_handle(dep) {
+            if let Base = dep.metadata().container_type() {
+                return schema::warn_outdated(&dep);
+            }
+        }
+    }
+}
+
 pub fn execute<'a>(args: &'a mut Arguments<'a>) -> Result<()> {
     match ExecParams::parse(args)? {
-        ExecParams::FakeRoot(verbosity, true, _, handle) => execute_fakeroot(&handle, None, verbosity),
-        ExecParams::FakeRoot(verbosity, false, args, handle) => execute_fakeroot(&handle, Some(args), verbosity),
-        ExecParams::Container(verbosity, true, _, handle) => execute_container(&handle, vec!["bash"], true, verbosity),
-        ExecParams::Container(verbosity, false, args, handle) => execute_container(&handle, args, false, verbosity),
+        ExecParams::FakeRoot(opts, args, handle) => match opts.shell {
+            true => execute_fakeroot(&handle, None, &opts),
+            false => execute_fakeroot(&handle, Some(args), &opts),
+        },
+        ExecParams::Container(opts, args, handle) => match opts.shell {
+            true => execute_container(&handle, vec!["bash"], &opts),
+            false => execute_container(&handle, args, &opts),
+        },
     }
 }
 
-fn execute_container(ins: &ContainerHandle, arguments: Vec<&str>, shell: bool, verbosity: i8) -> Result<()> {
+fn execute_container(ins: &ContainerHandle, arguments: Vec<&str>, opts: &ExecOptions) -> Result<()> {
     let mut exec = ExecutionArgs::new();
     let mut jobs: Vec<Child> = Vec::new();
     let cfg = ins.config();
     let vars = ins.vars();
-    let dbus = !cfg.dbus().is_empty();
+    let dbus = opts.dbus_proxy && !cfg.dbus().is_empty();
 
     if !cfg.allow_forking() {
         exec.push_env(Argument::DieWithParent);
@@ -187,19 +292,52 @@ fn execute_container(ins: &ContainerHandle, arguments: Vec<&str>, shell: bool, v
         false => error!(ExecError::ConsoleSessionRetention).warn(),
     }
 
-    match shell && *IS_COLOR_TERMINAL {
+    match (opts.tmp_size, cfg.tmp_size()) {
+        (Some(size), _) => exec.tmp_size(size),
+        (None, Some(size)) => exec.tmp_size(tmpfs_size(size)?),
+        (None, None) => (),
+    }
+
+    if let NetworkMode::Host = opts.network.unwrap_or(*cfg.network()) {
+        exec.push_env(Argument::HostNetworking);
+        error!(ExecError::HostNetworkSharing).warn();
+    }
+
+    match opts.shell && *IS_COLOR_TERMINAL {
         true => exec.env("TERM", "xterm"),
         false => exec.env("TERM", "dumb"),
     }
 
     if dbus {
-        jobs.push(instantiate_dbus_proxy(cfg.dbus(), &mut exec, verbosity)?);
+        match opts.print_args {
+            true => {
+                register_dbus(cfg.dbus(), &mut exec)?;
+                bind_dbus_proxy(&mut exec);
+            }
+            false => jobs.push(instantiate_dbus_proxy(cfg.dbus(), &mut exec, opts)?),
+        }
     }
 
     exec.env("XDG_RUNTIME_DIR", &XDG_RUNTIME_DIR);
     register_filesystems(cfg.filesystem(), vars, &mut exec)?;
     register_permissions(cfg.permissions(), &mut exec)?;
 
+    if let (Some(locale), None) = (cfg.locale(), exec.obtain_env("LANG")) {
+        exec.env("LANG", locale);
+    }
+
+    if *cfg.bind_resolv() && exec.share_net() {
+        bind_resolv(&mut exec);
+    }
+
+    if *cfg.bind_fontconfig() {
+        bind_fontconfig(vars.home_mount(), &mut exec);
+    }
+
+    if let Some(dir) = cfg.build_dir() {
+        bind_build_dir(dir, vars.home(), &mut exec)?;
+    }
+
     let path = match exec.obtain_env("PATH") {
         Some(var) => var,
         None => {
@@ -219,7 +357,7 @@ fn execute_container(ins: &ContainerHandle, arguments: Vec<&str>, shell: bool, v
         }
     };
     let term_control = TermControl::new(0);
-    let mut proc = Command::new(BWRAP_EXECUTABLE);
+    let mut proc = Command::new(bwrap_executable()?);
     let proc = if sec_fd == 0 {
         proc.env_clear()
             .args(exec.arguments())
@@ -250,7 +388,7 @@ fn execute_container(ins: &ContainerHandle, arguments: Vec<&str>, shell: bool, v
             .unwrap()
     };
 
-    match verbosity {
+    match opts.verbosity {
         0 => (),
         1 => eprintln!("Arguments:\t     {arguments:?}\n{ins:?}"),
         _ => eprintln!("Arguments:\t     {arguments:?}\n{ins:?}\n{exec:?}"),
@@ -258,35 +396,126 @@ fn execute_container(ins: &ContainerHandle, arguments: Vec<&str>, shell: bool, v
 
     check_path(ins, &arguments, path_vec)?;
 
+    if opts.print_args {
+        print_command(proc.args(arguments));
+        return Ok(());
+    }
+
+    if exec.unshare_user() {
+        check_userns()?;
+    }
+
+    if !cfg.limits().is_empty() {
+        if let Err(error) = cgroup::instantiate(vars.instance(), cfg.limits()) {
+            error.warn();
+        }
+    }
+
+    let procs = cgroup::procs().unwrap_or_else(|error| {
+        error.warn();
+        None
+    });
+
+    if let Some(procs) = &procs {
+        let fd = procs.as_raw_fd();
+
+        //bwrap is moved into the scoped cgroup prior to exec, lest the sandbox fork before it is attached.
+        unsafe {
+            proc.pre_exec(move || match write(fd, b"0") {
+                Ok(_) => Ok(()),
+                Err(errno) => Err(IOError::from_raw_os_error(errno as i32)),
+            });
+        }
+    }
+
     match proc.args(arguments).spawn() {
         Ok(child) => wait_on_container(
             child,
             term_control,
-            decode_info_json(info_pipe)?,
+            watch_session(decode_info_json(info_pipe)?, opts.idle_timeout),
             *cfg.allow_forking(),
             match !jobs.is_empty() {
                 true => Some(jobs),
                 false => None,
             },
             signal_trap,
-            cleanup,
+            match dbus {
+                true => cleanup,
+                false => || {
+                    cgroup::release();
+                    Ok(())
+                },
+            },
         ),
-        Err(err) => err!(ErrorKind::ProcessInitFailure(BWRAP_EXECUTABLE, err.kind())),
+        Err(err) => err!(ErrorKind::ProcessInitFailure(bwrap_executable()?, err.kind())),
+    }
+}
+
+fn watch_session(bwrap_pid: i32, timeout: Option<Duration>) -> i32 {
+    if let Some(timeout) = timeout {
+        thread::spawn(move || watch_idle(bwrap_pid, timeout));
     }
+
+    bwrap_pid
 }
 
-fn execute_fakeroot(ins: &ContainerHandle, arguments: Option<Vec<&str>>, verbosity: i8) -> Result<()> {
+fn execute_fakeroot(ins: &ContainerHandle, arguments: Option<Vec<&str>>, opts: &ExecOptions) -> Result<()> {
     let arguments = match arguments {
         None => vec!["bash"],
         Some(args) => args,
     };
 
-    if verbosity > 0 {
+    if opts.verbosity > 0 {
         eprintln!("Arguments:\t     {arguments:?}\n{ins:?}");
     }
 
     check_path(ins, &arguments, vec!["/usr/bin", "/bin"])?;
-    fakeroot_container(Interactive, Some(signal_trap), ins, arguments)
+
+    match opts.print_args {
+        true => print_fakeroot_container(ins, arguments),
+        false => fakeroot_container(Interactive, Some(signal_trap), ins, arguments),
+    }
+}
+
+fn bind_resolv(exec: &mut ExecutionArgs) {
+    if let Ok(path) = canonicalize("/etc/resolv.conf") {
+        exec.robind(&path.to_string_lossy(), "/etc/resolv.conf");
+    }
+}
+
+/// Bind the host's fontconfig cache read-only over the container user's own, skipped should it not exist.
+fn bind_fontconfig(home: &str, exec: &mut ExecutionArgs) {
+    let cache = match env_var("XDG_CACHE_HOME") {
+        Ok(dir) if dir.starts_with('/') => format!("{dir}/fontconfig"),
+        _ => format!("{}/.cache/fontconfig", *HOME),
+    };
+
+    if Path::new(&cache).is_dir() {
+        exec.robind(&cache, &format!("{home}/.cache/fontconfig"));
+    }
+}
+
+/// Bind the container's build directory to a fixed location, and direct makepkg's build, package, source,
+/// and log destinations therein, unless otherwise set by an environment permission.
+fn bind_build_dir(dir: &str, home: &str, exec: &mut ExecutionArgs) -> Result<()> {
+    let dir = match dir.starts_with('/') {
+        true => dir.to_string(),
+        false => format!("{home}/{dir}"),
+    };
+
+    for (var, path) in BUILD_ENV {
+        let host = format!("{dir}{}", &path[BUILD_DIR.len() ..]);
+
+        create_dir_all(&host).prepend_io(|| host.clone())?;
+
+        if exec.obtain_env(var).is_none() {
+            exec.env(var, path);
+        }
+    }
+
+    exec.bind(&dir, BUILD_DIR);
+
+    Ok(())
 }
 
 fn signal_trap(bwrap_pid: i32) {
@@ -307,39 +536,43 @@ fn signal_trap(bwrap_pid: i32) {
         .unwrap();
 }
 
-fn instantiate_dbus_proxy(per: &[Box<dyn Dbus>], args: &mut ExecutionArgs, verbosity: i8) -> Result<Child> {
-    let dbus_socket_path = format!("/run/user/{}/bus", nix::unistd::geteuid());
+fn instantiate_dbus_proxy(per: &[Box<dyn Dbus>], args: &mut ExecutionArgs, opts: &ExecOptions) -> Result<Child> {
     let dbus_session = env_var("DBUS_SESSION_BUS_ADDRESS")?;
+    let timeout = match opts.dbus_timeout {
+        Some(timeout) => timeout,
+        None => global()?.config().dbus_timeout(),
+    };
     let mut dbus = Command::new(DBUS_PROXY_EXECUTABLE);
 
     register_dbus(per, args)?;
     create_placeholder(&DBUS_SOCKET)?;
     dbus.arg(dbus_session).arg(&*DBUS_SOCKET);
 
-    if verbosity > 1 {
+    if opts.verbosity > 1 {
         dbus.arg("--log");
     }
 
     match dbus.arg("--filter").args(args.get_dbus()).spawn() {
         Ok(mut child) => {
-            let mut increment: u8 = 0;
+            let max = timeout * 1000 / SOCKET_SLEEP_DURATION.as_micros() as u64;
+            let instant = Instant::now();
+            let mut increment = 0;
 
-            args.robind(&DBUS_SOCKET, &dbus_socket_path);
-            args.symlink(&dbus_socket_path, "/run/dbus/system_bus_socket");
-            args.env("DBUS_SESSION_BUS_ADDRESS", &format!("unix:path={dbus_socket_path}"));
+            bind_dbus_proxy(args);
 
             /*
              * This blocking code is required to prevent a downstream race condition with
              * bubblewrap. Unless xdg-dbus-proxy is passed improper parameters, this while loop
              * shouldn't almost ever increment more than once or twice.
              *
-             * With a sleep duration of 500 microseconds, we check the socket 200 times before failure.
+             * With a sleep duration of 500 microseconds, we check the socket twice per millisecond of the
+             * configured timeout, or 200 times by default, before failure.
              *
              * ADDENDUM: Upon further examination of bubblewrap's code, it is not possible to ask bubblewrap
              * to wait on a FD prior to instantiating the filesystem bindings.
              */
 
-            while !check_socket(&DBUS_SOCKET, &increment, &mut child)? {
+            while !check_socket(&DBUS_SOCKET, (increment, max), &instant, &mut child)? {
                 increment += 1;
             }
 
@@ -349,11 +582,19 @@ fn instantiate_dbus_proxy(per: &[Box<dyn Dbus>], args: &mut ExecutionArgs, verbo
     }
 }
 
-fn check_socket(socket: &String, increment: &u8, process_child: &mut Child) -> Result<bool> {
-    if increment == &200 {
+fn bind_dbus_proxy(args: &mut ExecutionArgs) {
+    let dbus_socket_path = format!("/run/user/{}/bus", nix::unistd::geteuid());
+
+    args.robind(&DBUS_SOCKET, &dbus_socket_path);
+    args.symlink(&dbus_socket_path, "/run/dbus/system_bus_socket");
+    args.env("DBUS_SESSION_BUS_ADDRESS", &format!("unix:path={dbus_socket_path}"));
+}
+
+fn check_socket(socket: &String, (increment, max): (u64, u64), instant: &Instant, process_child: &mut Child) -> Result<bool> {
+    if increment >= max {
         process_child.kill().ok();
         remove_file(&*DBUS_SOCKET).prepend_io(|| DBUS_SOCKET.to_string())?;
-        err!(ExecutionError::SocketTimeout(socket.into()))?
+        err!(ExecutionError::SocketTimeout(socket.into(), instant.elapsed()))?
     }
 
     thread::sleep(SOCKET_SLEEP_DURATION);
@@ -371,6 +612,8 @@ fn create_placeholder(path: &str) -> Result<()> {
 }
 
 fn cleanup() -> Result<()> {
+    cgroup::release();
+
     if Path::new(&*DBUS_SOCKET).exists() {
         remove_file(&*DBUS_SOCKET).prepend_io(|| DBUS_SOCKET.to_string())?;
     }
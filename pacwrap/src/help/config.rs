@@ -93,12 +93,17 @@ This document was generated by the {name} binary with version {} of the program.
 {sub_bold}alpm:{reset_bold}
 {sub_para}{tab}Configuration pertaining to {bold}libalpm{reset_bold}(3) are to be declared within this section.
 
+{sub_bold}profile:{reset_bold}
+{sub_para}{tab}Default modules inherited by newly-created containers are to be declared within this section.
+
 {head}CONFIG{reset}
 {sub_bold}logging{reset_bold}: Basic
 {tab}{tab}Logging verbosity specified here. Available options are {bold}Basic{reset_bold}, {bold}Verbose{bold}, and {bold}None{reset_bold}.
 
 {sub_bold}summary{reset_bold}: Basic
-{tab}{tab}Transaction summary type. Available options are {bold}Basic{reset_bold}, {bold}BasicForeign{reset_bold}, {bold}Table{reset_bold}, and {bold}TableForeign{reset_bold}. 
+{tab}{tab}Transaction summary type. Available options are {bold}Sum{reset_bold}, {bold}Basic{reset_bold}, {bold}Table{reset_bold}, and {bold}Json{reset_bold},
+{tab}{tab}alongside their {bold}SumForeign{reset_bold}, {bold}BasicForeign{reset_bold}, {bold}TableForeign{reset_bold}, and {bold}JsonForeign{reset_bold} counterparts.
+{tab}{tab}This may be overridden per invocation with {bold}--summary-format{reset_bold}.
 
 {tab}{tab}Each option suffixed with the {bold}Foreign{reset_bold} juxtaposition, will take effect only during the
 {tab}{tab}juxtaposed transaction type with otherwise the opposite effect.
@@ -106,6 +111,24 @@ This document was generated by the {name} binary with version {} of the program.
 {sub_bold}progress:{reset_bold}
 {tab}{tab}Progress types are declared within this subsection.
 
+{sub_bold}dbus_timeout{reset_bold}: 100
+{tab}{tab}Maximum duration, in milliseconds, to wait upon the socket provided by {bold}xdg-dbus-proxy{reset_bold}(1) prior to 
+{tab}{tab}failure. Specify an {bold}integer{reset_bold} to declare a maximum value.
+
+{sub_bold}bwrap_executable{reset_bold}: /usr/bin/bwrap
+{tab}{tab}Absolute path to the {bold}bwrap{reset_bold}(1) executable used to construct containers. When unspecified, {bold}bwrap{reset_bold}
+{tab}{tab}is located in {bold}$PATH{reset_bold}. The {bold}$PACWRAP_BWRAP{reset_bold} environment variable takes precedence over this option.
+
+{sub_bold}editor{reset_bold}: vim
+{tab}{tab}Editor invoked by the {bold}--edit{reset_bold} and {bold}--view{reset_bold} utilities when neither {bold}$VISUAL{reset_bold} nor
+{tab}{tab}{bold}$EDITOR{reset_bold} is specified. When unspecified, the first of {bold}nano{reset_bold}, {bold}vim{reset_bold}, or {bold}vi{reset_bold} located
+{tab}{tab}in {bold}$PATH{reset_bold} is invoked.
+
+{sub_bold}home_skeleton{reset_bold}: skel
+{tab}{tab}Directory of files, akin to {bold}/etc/skel{reset_bold}, copied recursively into the home directory of base and
+{tab}{tab}aggregate containers upon creation. Relative paths are resolved against {bold}~/.config/pacwrap{reset_bold}. Files
+{tab}{tab}already present in the container's home, such as when reinitialized with {bold}--from-config{reset_bold}, are not overwritten.
+
 {head}PROGRESS{reset}
 {sub_bold}transact{reset_bold}: CondensedForeign
 {tab}{tab}Progress type for transaction progress is specified with this option. Available values are 
@@ -148,10 +171,34 @@ This document was generated by the {name} binary with version {} of the program.
 {tab}{tab}Instructs {bold}libalpm{reset_bold}(3) to parallelise the download queue with a maximum queue amount. Specify an 
 {tab}{tab}{bold}integer{reset_bold} to declare a maximum value.
 
+{sub_bold}max_download_rate{reset_bold}: 0
+{tab}{tab}Throttle package and database downloads to the specified rate in KiB/s, shared amongst parallel downloads.
+{tab}{tab}Specify an {bold}integer{reset_bold}, or 0 to leave downloads unthrottled.
+
 {sub_bold}disable_sandbox{reset_bold}: false
 {tab}{tab}Instructs {bold}libalpm{reset_bold}(3) to disable the landlock and seccomp sandbox for downloads. Specify a
 {tab}{tab}{bold}bool{reset_bold} to declare a maximum value.
 
+{sub_bold}repo_cache:{reset_bold}
+{tab}{tab}Additional package cache directories are declared herein with a mapping of repository names to paths.
+{tab}{tab}These directories are registered in order of repository declaration following the default package
+{tab}{tab}cache, such that the default cache is consulted first; where absent, the default cache alone applies.
+
+{head}PROFILE{reset}
+{sub_bold}filesystems:{reset_bold}
+{tab}{tab}Filesystem modules inherited by containers upon creation, declared as within a container configuration.
+
+{sub_bold}permissions:{reset_bold}
+{tab}{tab}Permission modules inherited by containers upon creation, declared as within a container configuration.
+
+{sub_bold}dbus:{reset_bold}
+{tab}{tab}Dbus modules inherited by containers upon creation, declared as within a container configuration.
+
+{tab}{tab}Entries of each list are appended to the container's default entries, whereupon an entry of a module
+{tab}{tab}the container already engages, such as {bold}root{reset_bold} or {bold}home{reset_bold}, takes precedence.
+{tab}{tab}Only containers created by {bold}pacwrap -Sc{reset_bold} inherit the profile, unless
+{tab}{tab}{bold}--no-profile{reset_bold} or {bold}--copy-config-from{reset_bold} is specified; existing containers are unaffected.
+
 {head}SEE ALSO{reset}
 {tab}{tab}{bold}pacman.conf{reset_bold}(5), {bold}libalpm{reset_bold}(3)
 
@@ -97,7 +97,63 @@ pub fn default(buf: &mut String, layout: &HelpLayout) -> Result {
 {tab}{tab}Display version banner or information.
 
 {sub_bold}-h, --help{reset_bold} <{bold}OPERATION{reset_bold} | {bold}VERB{reset_bold} | {bold}TOPIC{reset_bold}>
-{tab}{tab}Print the help manual to {bold}STDOUT{reset_bold}.\n"
+{tab}{tab}Print the help manual to {bold}STDOUT{reset_bold}.
+
+{head}GLOBAL OPTIONS{reset}
+{sub_bold}--porcelain{reset_bold}
+{tab}{tab}Emit line-oriented status records upon {bold}STDOUT{reset_bold} in lieu of formatted output, designed to be parsed
+{tab}{tab}by scripts. Use in combination with {bold}--noconfirm{reset_bold} for fully unattended operation. Not applicable
+{tab}{tab}to the execute operation, where it is instead passed to the command sequence.
+
+{sub_bold}--no-warning-summary{reset_bold}
+{tab}{tab}Refrain from reprinting the warnings emitted over the course of an operation upon its completion. Warnings
+{tab}{tab}are otherwise summarised at the end of each operation, grouped by the container to which they pertain.
+{tab}{tab}Not applicable to the execute operation.
+
+{head}PORCELAIN FORMAT{reset}
+{sub_para}The following records are stable between releases. Fields are delimited by a single space, and the record
+{tab}keyword is always first. Additional records may be introduced in future releases: Lines not beginning with
+{tab}a known record keyword, such as output from the transaction agent or package hooks, are to be disregarded.
+
+{sub_bold}INSTALL{reset_bold} <{bold}CONTAINER{reset_bold}> <{bold}PACKAGE{reset_bold}> <{bold}VERSION{reset_bold}>
+{sub_bold}REINSTALL{reset_bold} <{bold}CONTAINER{reset_bold}> <{bold}PACKAGE{reset_bold}> <{bold}VERSION{reset_bold}>
+{sub_bold}REMOVE{reset_bold} <{bold}CONTAINER{reset_bold}> <{bold}PACKAGE{reset_bold}> <{bold}VERSION{reset_bold}>
+{sub_bold}UPGRADE{reset_bold} <{bold}CONTAINER{reset_bold}> <{bold}PACKAGE{reset_bold}> <{bold}OLD{reset_bold}> <{bold}NEW{reset_bold}>
+{sub_bold}DOWNGRADE{reset_bold} <{bold}CONTAINER{reset_bold}> <{bold}PACKAGE{reset_bold}> <{bold}OLD{reset_bold}> <{bold}NEW{reset_bold}>
+{tab}{tab}Package transaction committed to, or previewed upon, the specified container.
+
+{sub_bold}LINK{reset_bold} <{bold}CONTAINER{reset_bold}>
+{tab}{tab}Filesystem of the specified aggregate container synchronized.
+
+{sub_bold}ERROR{reset_bold} <{bold}CODE{reset_bold}> <{bold}MESSAGE{reset_bold}>
+{tab}{tab}Operation failed with the specified exit status, and terminating error message upon a single line.
+
+{head}EXIT STATUS{reset}
+{sub_para}The following exit statuses are stable between releases. Otherwise, when executing a container, the exit
+{tab}status of the command sequence is returned.
+
+{sub_bold}1{reset_bold}
+{tab}{tab}General error, or an error which is otherwise unclassified.
+
+{sub_bold}2{reset_bold}
+{tab}{tab}Input or output error.
+
+{sub_bold}3{reset_bold}
+{tab}{tab}Lock file is held by another instance of pacwrap.
+
+{sub_bold}4{reset_bold}
+{tab}{tab}Container or dependency not found.
+
+{sub_bold}5{reset_bold}
+{tab}{tab}Transaction failure.
+
+{sub_bold}6{reset_bold}
+{tab}{tab}Invoked with elevated privileges. Every operation, save for {bold}--help{reset_bold} and {bold}--version{reset_bold}, refuses to execute
+{tab}{tab}with an effective user or group ID of 0, as the sandbox depends upon unprivileged user namespaces. No
+{tab}{tab}override is provided.
+
+{sub_bold}130{reset_bold}
+{tab}{tab}Operation interrupted by signal or by the user.\n"
     )
 }
 
@@ -129,6 +185,39 @@ pub fn execute(buf: &mut String, layout: &HelpLayout) -> Result {
 
 {sub_bold}-r, --root{reset_bold}
 {tab}{tab}Execute the provided command sequence with fakeroot and fakechroot.
+
+{sub_bold}--keep-mounts{reset_bold}
+{tab}{tab}Debug shell mode: launch a shell inside the fully-assembled sandbox in lieu of the requested command,
+{tab}{tab}in order to inspect the mount namespace produced by the configured filesystem and permission modules.
+
+{sub_bold}--print-bwrap-args{reset_bold}
+{tab}{tab}Assemble and print the complete, shell-quoted {bold}bwrap{reset_bold}(1) command line to {bold}STDOUT{reset_bold} in lieu of
+{tab}{tab}executing it. When specified alongside {bold}--root{reset_bold}, the fakeroot invocation is printed.
+
+{sub_bold}--no-dbus-proxy{reset_bold}
+{tab}{tab}Skip instantiation of the dbus proxy for this invocation, irrespective of the dbus modules specified
+{tab}{tab}in the container's configuration.
+
+{sub_bold}--dbus-timeout{reset_bold}=<{bold}MILLISECONDS{reset_bold}>
+{tab}{tab}Override the maximum duration to wait upon the dbus proxy's socket prior to failure, otherwise
+{tab}{tab}specified by the {bold}dbus_timeout{reset_bold} option in {bold}pacwrap.yml{reset_bold}(2).
+
+{sub_bold}--idle-timeout{reset_bold}=<{bold}SECONDS{reset_bold}>
+{tab}{tab}Tear down the container once its session has remained idle for the duration specified. A session is
+{tab}{tab}deemed idle whilst the process executed persists without any other process running beneath it within
+{tab}{tab}the container, such that background processes working silently keep the session alive.
+
+{sub_bold}--network{reset_bold}=<{bold}host{reset_bold}|{bold}none{reset_bold}>
+{tab}{tab}Override the container's {bold}network{reset_bold} option for this invocation. Specifying {bold}host{reset_bold} shares
+{tab}{tab}the host's network namespace with the container, whereas {bold}none{reset_bold} retains an isolated namespace.
+
+{sub_bold}--isolate-tmp{reset_bold}=<{bold}SIZE{reset_bold}>
+{tab}{tab}Back the container's {bold}/tmp{reset_bold} with a tmpfs limited to the size specified, e.g. {bold}512MiB{reset_bold}, in lieu
+{tab}{tab}of an unbounded tmpfs. Overrides the container's {bold}tmp_size{reset_bold} option for this invocation.
+
+{sub_bold}--container-config{reset_bold}=<{bold}FILE_PATH{reset_bold}>
+{tab}{tab}Launch the container with the configuration specified in lieu of the container's own configuration.
+{tab}{tab}Permissions, filesystems, and dbus modules are derived from this file; the container root is unchanged.
 	
 {sub_sect}EXAMPLES{reset_bold}
 {sub}`$ pacwrap run firefox firefox`
@@ -154,6 +243,8 @@ pub fn sync(buf: &mut String, layout: &HelpLayout) -> Result {
              buf,
              "{head}SYNCHRONIZATION{reset}
 {sub_para}Provides the facilities required to be able to synchronize and create containers in aggregate. 
+{tab}Upon receipt of {bold}SIGUSR1{reset_bold}, the present stage of synchronization, along with the container being 
+{tab}processed and the remaining queue depth, are printed to {bold}STDERR{reset_bold} without interrupting the operation.
 
 {sub_bold}-y, --refresh{reset_bold}
 {tab}{tab}Synchronize remote package databases. Specify up to 2 times to force a refresh.
@@ -174,6 +265,17 @@ pub fn sync(buf: &mut String, layout: &HelpLayout) -> Result {
 {tab}{tab}dependency per slice or aggregate is supported. Filesystem and package deduplication via slices and 
 {tab}{tab}aggregate containers are recommended, but optional. This container type is not dependant.
 
+{sub_bold}--minimal{reset_bold}
+{tab}{tab}Create a minimal base container, in lieu of the standard base. Specify prior to the target operand alongside
+{tab}{tab}{bold}`-b, --base`{reset_bold}. Only the {bold}filesystem{reset_bold}, {bold}glibc{reset_bold}, {bold}bash{reset_bold}, and {bold}coreutils{reset_bold} packages are installed, and only the
+{tab}{tab}essential portion of the container schema is extracted. A minimal base remains valid as a dependency for
+{tab}{tab}downstream slices and aggregates; subsequent schema updates are applied to the minimal portion only.
+
+{tab}{tab}The minimal schema guarantees {bold}/usr/share/libalpm/hooks{reset_bold}, {bold}/usr/share/libalpm/scripts{reset_bold}, and {bold}/usr/bin/pacwrap-key{reset_bold},
+{tab}{tab}along with the container's schema metadata. The {bold}/usr/local/bin{reset_bold} xdg-open and xdg-email shims, the systemd hook
+{tab}{tab}overrides in {bold}/etc/pacman.d/hooks{reset_bold}, and {bold}/etc/bash.bashrc{reset_bold} are omitted. Locale, timezone, and user configuration
+{tab}{tab}is deferred until the {bold}base{reset_bold} package is installed, whereupon the distribution hook is triggered.
+
 {sub_bold}-s, --slice{reset_bold}
 {tab}{tab}Slice container type. Specify alongside {bold}`-c, --create`{reset_bold} to assign this container type during creation.
 
@@ -183,6 +285,10 @@ pub fn sync(buf: &mut String, layout: &HelpLayout) -> Result {
 {tab}{tab}of common downstream package and filesystem dependencies.
 
 {tab}{tab}Useful for graphics drivers, graphical toolkits, fonts, etc.; these are not meant for applications.
+{tab}{tab}This guidance can be enforced per slice with the {bold}template_lock{reset_bold} option, which restricts the packages
+{tab}{tab}installed, inclusive of the dependencies of each target, to an allowlist and denylist of package names.
+{tab}{tab}A lock in {bold}refuse{reset_bold} mode is overridden by {bold}--force{reset_bold}, whereupon the transaction
+{tab}{tab}proceeds with a warning.
 
 {sub_bold}-a, --aggegrate{reset_bold}
 {tab}{tab}Aggregate container type. Specify alongside {bold}`-c, --create`{reset_bold} to this assign container type during creation.
@@ -196,6 +302,18 @@ pub fn sync(buf: &mut String, layout: &HelpLayout) -> Result {
 
 {sub_bold}-t, --target{reset_bold} <{bold}CONTAINER{reset_bold}> <..{bold}PACKAGE{reset_bold}>
 {tab}{tab}Declare a target container for the specified operation, followed by a list of package target(s).
+{tab}{tab}Package targets already installed and up-to-date are skipped, unless {bold}--reinstall{reset_bold} is specified.
+
+{sub_bold}--tag{reset_bold}=<{bold}TAG{reset_bold}> <..{bold}PACKAGE{reset_bold}>
+{tab}{tab}Target all containers bearing the specified tag in their {bold}tags{reset_bold} field, followed by a list of package
+{tab}{tab}target(s) applicable to each. In conjunction with {bold}-o/--target-only{reset_bold}, the operation is restricted to the
+{tab}{tab}tagged containers, such as to upgrade them alone. This option can be specified multiple times, whereupon the
+{tab}{tab}union of tagged containers is targeted. A tag matching no containers is an error.
+
+{sub_bold}--target-file{reset_bold}=<{bold}FILE_PATH{reset_bold}>
+{tab}{tab}Read target containers and their package targets from the specified file, or from stdin if {bold}-{reset_bold}
+{tab}{tab}is specified. Each line declares a container followed by its packages: {bold}container: package ...{reset_bold}
+{tab}{tab}Blank lines and comments following {bold}#{reset_bold} are ignored.
 
 {sub_bold}-f, --filesystem{reset_bold}
 {tab}{tab}Force execution of filesystem synchronization target on all or specified containers. In combination 
@@ -203,15 +321,60 @@ pub fn sync(buf: &mut String, layout: &HelpLayout) -> Result {
 {tab}{tab}without package synhcronization on on all applicable containers. This operation is useful for propagation 
 {tab}{tab}of manual filesystem changes to all aggregate containers.
 
+{sub_bold}--force-relink{reset_bold}
+{tab}{tab}In combination with {bold}-f/--filesystem{reset_bold}, clear all files linked from upstream containers into the 
+{tab}{tab}specified aggregate container(s) prior to the filesystem synchronization, whereupon the linker rebuilds them
+{tab}{tab}from empty. Files are cleared according to the recorded state of each upstream container; files local to
+{tab}{tab}the aggregate container, not provided by an upstream container, are preserved.
+
 {sub_bold}-o, --target-only{reset_bold}
 {tab}{tab}Apply specified operation on the specified target(s) only.
 
+{sub_bold}--only-outdated{reset_bold}
+{tab}{tab}Determine which containers are out-of-date prior to an upgrade, skipping up-to-date containers entirely.
+{tab}{tab}Containers dependent upon an out-of-date container are upgraded, and filesystems of skipped containers
+{tab}{tab}are synchronized as per usual.
+
+{sub_bold}--sysupgrade-only{reset_bold}
+{tab}{tab}In combination with {bold}-u/--upgrade{reset_bold}, upgrade installed packages whilst refusing to install any new
+{tab}{tab}explicit package targets. Queued package targets are ignored with a warning; dependencies newly required
+{tab}{tab}by upgraded packages are nonetheless installed.
+
+{sub_bold}--after{reset_bold}=<{bold}CONTAINER{reset_bold}>
+{tab}{tab}Transact upon the preceding target only after the specified container. This option can be specified
+{tab}{tab}multiple times. Constraints solely order otherwise independent containers, dependencies always being
+{tab}{tab}transacted prior to their dependents. As aggregate containers are transacted after base and slice
+{tab}{tab}containers, base and slice containers cannot be ordered after an aggregate. Cyclic constraints are refused
+{tab}{tab}prior to any transaction.
+
+{sub_bold}--ignore-container{reset_bold}=<{bold}CONTAINER{reset_bold}>
+{tab}{tab}Exclude the specified container from package transactions. This option can be specified multiple times.
+{tab}{tab}Filesystem synchronization of excluded containers is retained in order to keep dependents current.
+{tab}{tab}Where no package transaction is to occur, such as with {bold}-Sof{reset_bold}, excluded aggregate containers are
+{tab}{tab}not relinked. The state of excluded base and slice containers is nonetheless obtained, as it is required
+{tab}{tab}to link their dependents.
+
 {sub_bold}-d, --dep{reset_bold} <{bold}CONTAINER{reset_bold}>
 {tab}{tab}Specify dependencies for a container create operation.
 
+{sub_bold}--no-profile{reset_bold}
+{tab}{tab}Create containers with the default configuration alone, disregarding the default module profile declared
+{tab}{tab}within the {bold}profile:{reset_bold} section of {bold}pacwrap.yml{reset_bold}(2).
+
+{sub_bold}--copy-config-from{reset_bold}=<{bold}CONTAINER{reset_bold}>
+{tab}{tab}Copy the runtime configuration, such as permissions, filesystems, dbus modules, and hooks, from an existing
+{tab}{tab}container to the container being created. Specify following the target operand. The new container retains
+{tab}{tab}its own type, dependencies, and packages, as specified with {bold}`-d, --dep`{reset_bold}, and is built upon a fresh root.
+{tab}{tab}The default module profile is not applied to the copied configuration.
+
 {sub_bold}-p, --preview{reset_bold}
 {tab}{tab}Perform a dryrun operation on existing containers to preview changes applicable or otherwise specified.
-{tab}{tab}Only applicable to pre-existing targets and not create operations.
+{tab}{tab}Only applicable to pre-existing targets and not create operations. Dependency conflicts, unsatisfied
+{tab}{tab}dependencies, and invalid architectures are reported for each container without aborting the preview;
+{tab}{tab}conflicts with files present upon the container filesystem are only detectable upon commit, as packages
+{tab}{tab}must first be retrieved. In combination with {bold}-f/--filesystem{reset_bold},
+{tab}{tab}the number of files to be created, relinked, and deleted in each aggregate container is reported, whilst
+{tab}{tab}no container filesystem or state data is modified.
 
 {sub_bold}-l, --lazy-load{reset_bold}
 {tab}{tab}Enable lazy-database initialization for this transaction. {bold}NOTE{reset_bold}: This feature is experimental.
@@ -222,19 +385,101 @@ pub fn sync(buf: &mut String, layout: &HelpLayout) -> Result {
 {tab}{tab}a new package in an aggregate container without all the prerequisite foreign dependencies
 {tab}{tab}synchronized to the resident container's package database.
 
+{sub_bold}--max-download-rate{reset_bold}=<{bold}KiB/s{reset_bold}>
+{tab}{tab}Throttle package and database downloads to the specified rate in KiB/s, shared amongst parallel
+{tab}{tab}downloads. Overrides the {bold}max_download_rate{reset_bold} configuration key, wherein 0 leaves downloads unthrottled.
+
+{sub_bold}--treat-resident{reset_bold}=<{bold}PACKAGE{reset_bold}>
+{tab}{tab}Treat the specified upstream package as resident for the duration of this transaction, permitting it to
+{tab}{tab}be transacted upon resident containers. This option can be specified multiple times. {bold}NOTE{reset_bold}: The
+{tab}{tab}resident copy of the package will diverge from the upstream slice, and will shadow the upstream copy
+{tab}{tab}until it is removed from the resident container.
+
 {sub_bold}--dbonly{reset_bold}
 {tab}{tab}Transact on resident containers with a database-only transaction.
 
+{sub_bold}--no-hooks{reset_bold}
+{tab}{tab}Skip execution of alpm hooks for this transaction. Post-transaction steps performed by hooks, such as
+{tab}{tab}the rebuilding of caches, are left unperformed until a subsequent transaction executes them.
+
+{sub_bold}--continue-on-error{reset_bold}
+{tab}{tab}Continue transacting on the remaining containers when a container's transaction fails. Containers
+{tab}{tab}dependent upon a failed container are skipped. A summary of failed and skipped containers is reported
+{tab}{tab}upon completion, with a non-zero exit status.
+
+{sub_bold}--verify-packages{reset_bold}
+{tab}{tab}Verify the integrity of packages installed by this transaction upon its completion. Files absent from
+{tab}{tab}the container root are reported, and the size, permissions, and checksum of regular files, excluding
+{tab}{tab}configuration files marked for backup, are compared against the package's mtree; or, absent an mtree, against
+{tab}{tab}the package archive in the package cache. Mismatches are reported per package, with a non-zero exit status.
+
+{sub_bold}--no-filesystem-sync{reset_bold}
+{tab}{tab}Skip filesystem synchronization upon the completion of package transactions in upstream containers.
+{tab}{tab}Aggregate containers may be out of sync until the next filesystem synchronization is performed.
+
+{sub_bold}--offline{reset_bold}
+{tab}{tab}Perform the transaction solely with cached packages and the existing sync databases. Mirrors are
+{tab}{tab}never contacted; the transaction fails if any package required is absent from the package cache.
+
+{sub_bold}--reinstall, --no-needed{reset_bold}
+{tab}{tab}Reinstall the specified package targets irrespective of whether the installed package is up-to-date.
+{tab}{tab}Package targets not presently installed are installed as per usual. By default, package targets which
+{tab}{tab}are installed and up-to-date are skipped with a warning, as with pacman's {bold}--needed{reset_bold}; the
+{tab}{tab}{bold}--needed{reset_bold} option is accepted for familiarity, but has no further effect.
+
+{sub_bold}--refresh-keys{reset_bold}
+{tab}{tab}Initialize the package trust database where absent, then repopulate and refresh the package keyrings
+{tab}{tab}without performing a package transaction. In combination with {bold}`-y, --refresh`{reset_bold} and {bold}`-u, --upgrade`{reset_bold},
+{tab}{tab}keys are refreshed prior to the upgrade operation.
+
 {sub_bold}--noconfirm{reset_bold}
 {tab}{tab}Override confirmation prompts and confirm all operations.
 
+{sub_bold}--summary-format=FORMAT{reset_bold}
+{tab}{tab}Override the configured transaction summary for this invocation. Available formats are {bold}sum{reset_bold},
+{tab}{tab}{bold}basic{reset_bold}, {bold}table{reset_bold}, and {bold}json{reset_bold}, each of which may be suffixed with {bold}-foreign{reset_bold}.
+
+{sub_bold}--status-socket=PATH{reset_bold}
+{tab}{tab}Bind a unix socket at the specified path, upon which status events are broadcast to connected clients as
+{tab}{tab}lines of JSON, each tagged with an {bold}event{reset_bold} field: {bold}container_started{reset_bold}, {bold}container_complete{reset_bold},
+{tab}{tab}{bold}container_failed{reset_bold}, {bold}package_downloaded{reset_bold}, {bold}link_progress{reset_bold}, and {bold}complete{reset_bold}. Clients which
+{tab}{tab}fail to keep up are disconnected. The socket is removed upon completion.
+
 {sub_bold}--disable-sandbox{reset_bold}
 {tab}{tab}Instruct libalpm to disable its own sandbox, utilizing landlock and seccomp, in order to mitigate potential
 {tab}{tab}issues with kernel compatibillity.
 
+{sub_bold}--unfreeze{reset_bold}
+{tab}{tab}Override the {bold}frozen{reset_bold} flag of the target containers for this invocation, permitting package
+{tab}{tab}transactions to proceed.
+{tab}{tab}Frozen containers are otherwise skipped with a warning upon a system upgrade.
+
+{sub_bold}--force{reset_bold}
+{tab}{tab}Override the {bold}quota{reset_bold} of the target containers for this invocation, permitting transactions projected
+{tab}{tab}to exceed the quota to proceed with a warning. Likewise, a {bold}template_lock{reset_bold} in
+{tab}{tab}{bold}refuse{reset_bold} mode is overridden, permitting packages denied by the lock to be installed with a warning.
+
+{sub_bold}--cachedir=PATH{reset_bold}
+{tab}{tab}Override the package cache directory used for this transaction. The directory is created where absent.
+{tab}{tab}{bold}NOTE{reset_bold}: This option is intended for advanced users.
+
+{sub_bold}--dbpath=PATH{reset_bold}
+{tab}{tab}Override the package database path of the target containers for this transaction, with the database
+{tab}{tab}of each container residing in a subdirectory thereof named after the container. Directories are
+{tab}{tab}created where absent. {bold}NOTE{reset_bold}: This option is intended for advanced users.
+
 {sub_bold}--debug{reset_bold}
 {tab}{tab}Use this option when reporting bugs.
 
+{sub_bold}--trace{reset_bold}
+{tab}{tab}Record every libalpm progress, question, and download event with a timestamp to {bold}trace.log{reset_bold} within
+{tab}{tab}the data directory, which is overwritten upon each traced invocation. Attach this file when reporting bugs.
+
+{sub_bold}-v, --verbose{reset_bold}
+{tab}{tab}Escalate verbosity; specify up to 3 times. At level 1, libalpm warnings are printed and the progress
+{tab}{tab}indicator is disabled; at level 2, all libalpm log messages are printed; at level 3, the arguments
+{tab}{tab}supplied to bubblewrap for the transaction agent are also printed. See {bold}PACWRAP_VERBOSE{reset_bold}.
+
 {sub_sect}EXAMPLES{reset_bold}
 {sub}`$ pacwrap init --base --target base`
 {tab}{tab}Synchronize remotes and create a base-type container named `base` with no additional packages.
@@ -284,6 +529,14 @@ pub fn remove(buf: &mut String, layout: &HelpLayout) -> Result {
 {tab}{tab}Specify a target container for the specified operation. At least one container target is 
 {tab}{tab}is required for package removal operations.
 
+{sub_bold}--tag{reset_bold}=<{bold}TAG{reset_bold}> <..{bold}PACKAGE{reset_bold}>
+{tab}{tab}Target all containers bearing the specified tag, followed by a list of package target(s) to remove from
+{tab}{tab}each. This option can be specified multiple times, whereupon the union of tagged containers is targeted.
+
+{sub_bold}--target-file{reset_bold}=<{bold}FILE_PATH{reset_bold}>
+{tab}{tab}Read target containers and their package targets from the specified file, or from stdin if {bold}-{reset_bold}
+{tab}{tab}is specified, in the same format as described for the sync operation.
+
 {sub_bold}--force-foreign{reset_bold}
 {tab}{tab}Force the removal of foreign packages on target container. Useful for cleaning up
 {tab}{tab}the package database of foreign, upstream dependencies synchronized to the target
@@ -293,21 +546,57 @@ pub fn remove(buf: &mut String, layout: &HelpLayout) -> Result {
 {tab}{tab}Delete root filesystem(s) of specified targets. Shortcout to {bold}-Ur{reset_bold}.
 
 {sub_bold}-p, --preview{reset_bold}
-{tab}{tab}Preview operation and perform no transaction.
+{tab}{tab}Preview operation and perform no transaction. Unsatisfied dependencies are reported for each container
+{tab}{tab}without aborting the preview.
+
+{sub_bold}-o, --target-only{reset_bold}
+{tab}{tab}Restrict removal strictly to the specified target containers. Filesystem synchronization is not engaged,
+{tab}{tab}either prior to the transaction upstream or afterwards downstream. {bold}NOTE{reset_bold}: Downstream aggregates will
+{tab}{tab}continue to reference any removed packages until their next filesystem synchronization, which may be
+{tab}{tab}invoked with {bold}`pacwrap -Sf`{reset_bold}. Mutually exclusive with {bold}`-f, --filesystem`{reset_bold}.
 
 {sub_bold}--dbonly{reset_bold}
 {tab}{tab}Transact on resident containers with a database-only transaction.
 
+{sub_bold}--no-hooks{reset_bold}
+{tab}{tab}Skip execution of alpm hooks for this transaction.
+
+{sub_bold}--continue-on-error{reset_bold}
+{tab}{tab}Continue transacting on the remaining containers when a container's transaction fails.
+
 {sub_bold}--noconfirm{reset_bold}
 {tab}{tab}Override confirmation prompts and confirm all operations.
 
+{sub_bold}--summary-format=FORMAT{reset_bold}
+{tab}{tab}Override the configured transaction summary for this invocation. Available formats are {bold}sum{reset_bold},
+{tab}{tab}{bold}basic{reset_bold}, {bold}table{reset_bold}, and {bold}json{reset_bold}, each of which may be suffixed with {bold}-foreign{reset_bold}.
+
+{sub_bold}--status-socket=PATH{reset_bold}
+{tab}{tab}Bind a unix socket at the specified path, upon which status events are broadcast to connected clients as
+{tab}{tab}lines of JSON, each tagged with an {bold}event{reset_bold} field: {bold}container_started{reset_bold}, {bold}container_complete{reset_bold},
+{tab}{tab}{bold}container_failed{reset_bold}, {bold}package_downloaded{reset_bold}, {bold}link_progress{reset_bold}, and {bold}complete{reset_bold}. Clients which
+{tab}{tab}fail to keep up are disconnected. The socket is removed upon completion.
+
 {sub_bold}--disable-sandbox{reset_bold}
 {tab}{tab}Instruct libalpm to disable its own sandbox, utilizing landlock and seccomp, in order to mitigate potential
 {tab}{tab}issues with kernel compatibillity.
 
+{sub_bold}--unfreeze{reset_bold}
+{tab}{tab}Override the {bold}frozen{reset_bold} flag of the target containers for this invocation, permitting package
+{tab}{tab}transactions to proceed.
+
 {sub_bold}--debug{reset_bold}
 {tab}{tab}Use this option when reporting bugs.
 
+{sub_bold}--trace{reset_bold}
+{tab}{tab}Record every libalpm progress, question, and download event with a timestamp to {bold}trace.log{reset_bold} within
+{tab}{tab}the data directory, which is overwritten upon each traced invocation. Attach this file when reporting bugs.
+
+{sub_bold}-v, --verbose{reset_bold}
+{tab}{tab}Escalate verbosity; specify up to 3 times. At level 1, libalpm warnings are printed and the progress
+{tab}{tab}indicator is disabled; at level 2, all libalpm log messages are printed; at level 3, the arguments
+{tab}{tab}supplied to bubblewrap for the transaction agent are also printed. See {bold}PACWRAP_VERBOSE{reset_bold}.
+
 {sub_sect}EXAMPLES{reset_bold}
 {sub}`$ pacwrap -Rt firefox firefox`
 {tab}{tab}Remove the target package firefox from target container firefox.
@@ -339,8 +628,9 @@ pub fn compose(buf: &mut String, layout: &HelpLayout) -> Result {
 {tab}{tab}filename provided.
 
 {sub_bold}-r, --reinitialize{reset_bold}
-{tab}{tab}Compose an available, existing container for composition. The pre-existing container root
-{tab}{tab}will be deleted and the container will be composited from the configuration data enumerated.
+{tab}{tab}Compose an available, existing container for composition. The replacement root is composed
+{tab}{tab}alongside the pre-existing container root, and is swapped into place only upon success; the
+{tab}{tab}pre-existing root is deleted thereafter. Should composition fail, the original root remains intact.
 
 {sub_bold}-t, --target{reset_bold} <{bold}CONTAINER{reset_bold}>
 {tab}{tab}Specify a target container for the specified operation.
@@ -348,9 +638,14 @@ pub fn compose(buf: &mut String, layout: &HelpLayout) -> Result {
 {sub_bold}-f, --force{reset_bold}
 {tab}{tab}Disable sanity checks and force removal of container filesystem(s).
 
+{sub_bold}-m, --manifest{reset_bold} <{bold}FILE_PATH{reset_bold}>
+{tab}{tab}Compose multiple containers from a manifest file, comprised of a mapping of container names to their
+{tab}{tab}respective configurations. Containers are composed in order of their dependencies; no container will
+{tab}{tab}be composed if the manifest contains a dependency cycle or an unavailable dependency.
+
 {sub_bold}--reinitialize-all{reset_bold}
 {tab}{tab}Queues all available, existing containers for composition. All pre-existing container roots
-{tab}{tab}will be deleted and composited from the available configuration data enumerated.
+{tab}{tab}are replaced by roots composited from the available configuration data enumerated.
 
 {sub_bold}-l, --lazy-load{reset_bold}
 {tab}{tab}Enable lazy-database initialization for this transaction. {bold}NOTE{reset_bold}: This feature is experimental.
@@ -365,6 +660,16 @@ pub fn compose(buf: &mut String, layout: &HelpLayout) -> Result {
 {sub_bold}--noconfirm{reset_bold}
 {tab}{tab}Override confirmation prompts and confirm all operations.
 
+{sub_bold}--summary-format=FORMAT{reset_bold}
+{tab}{tab}Override the configured transaction summary for this invocation. Available formats are {bold}sum{reset_bold},
+{tab}{tab}{bold}basic{reset_bold}, {bold}table{reset_bold}, and {bold}json{reset_bold}, each of which may be suffixed with {bold}-foreign{reset_bold}.
+
+{sub_bold}--status-socket=PATH{reset_bold}
+{tab}{tab}Bind a unix socket at the specified path, upon which status events are broadcast to connected clients as
+{tab}{tab}lines of JSON, each tagged with an {bold}event{reset_bold} field: {bold}container_started{reset_bold}, {bold}container_complete{reset_bold},
+{tab}{tab}{bold}container_failed{reset_bold}, {bold}package_downloaded{reset_bold}, {bold}link_progress{reset_bold}, and {bold}complete{reset_bold}. Clients which
+{tab}{tab}fail to keep up are disconnected. The socket is removed upon completion.
+
 {sub_bold}--disable-sandbox{reset_bold}
 {tab}{tab}Instruct libalpm to disable its own sandbox, utilizing landlock and seccomp, in order to mitigate potential
 {tab}{tab}issues with kernel compatibillity.
@@ -372,11 +677,23 @@ pub fn compose(buf: &mut String, layout: &HelpLayout) -> Result {
 {sub_bold}--debug{reset_bold}
 {tab}{tab}Use this option when reporting bugs.
 
+{sub_bold}--trace{reset_bold}
+{tab}{tab}Record every libalpm progress, question, and download event with a timestamp to {bold}trace.log{reset_bold} within
+{tab}{tab}the data directory, which is overwritten upon each traced invocation. Attach this file when reporting bugs.
+
+{sub_bold}-v, --verbose{reset_bold}
+{tab}{tab}Escalate verbosity; specify up to 3 times. At level 1, libalpm warnings are printed and the progress
+{tab}{tab}indicator is disabled; at level 2, all libalpm log messages are printed; at level 3, the arguments
+{tab}{tab}supplied to bubblewrap for the transaction agent are also printed. See {bold}PACWRAP_VERBOSE{reset_bold}.
+
 {sub_sect}EXAMPLES{reset_bold}
 {sub}`$ pacwrap compose -rt element element.yml`
 {tab}{tab}Reinitialize an existing container named element with its configuration derived 
 {tab}{tab}from the file 'element.yml'.
 
+{sub}`$ pacwrap compose --manifest workstation.yml`
+{tab}{tab}Compose all containers enumerated in the manifest file 'workstation.yml'.
+
 {sub}`$ pacwrap compose --reinitialize-all --from-config`
 {tab}{tab}Reinitialize all container configurations available in '{bold}$PACWRAP_CONFIG_DIR{reset_bold}/container/'.\n"
     )
@@ -396,7 +713,9 @@ pub fn query(buf: &mut String, layout: &HelpLayout) -> Result {
     writeln!(
         buf,
         "{head}QUERY{reset}
-{sub_para}Query package list on target container.
+{sub_para}Query package list on target container. Absent {bold}--target{reset_bold}, operands which do not name a container
+{tab}{tab}are instead queried as package names amongst the local databases of every container, reporting each
+{tab}{tab}package named, or providing, the operands specified.
 
 {sub_bold}-q, --quiet{reset_bold}
 {tab}{tab}Quiet the output by truncating the package string.
@@ -407,9 +726,29 @@ pub fn query(buf: &mut String, layout: &HelpLayout) -> Result {
 {sub_bold}-e, --explicit{reset_bold}
 {tab}{tab}Filter output to explicitly-marked packages.
 
+{sub_bold}-d, --deps{reset_bold}
+{tab}{tab}Filter output to packages installed as dependencies. Mutually exclusive with {bold}`-e, --explicit`{reset_bold}.
+
+{sub_bold}-l, --files{reset_bold} <{bold}PACKAGE{reset_bold}>
+{tab}{tab}List the files owned by the specified packages relative to the container root. Output is truncated to
+{tab}{tab}the file path alone when specified alongside {bold}--quiet{reset_bold}.
+
+{sub_bold}-o, --owns{reset_bold} <{bold}PATH{reset_bold}>
+{tab}{tab}Search for the package owning the specified path, relative to the container root, amongst the target
+{tab}{tab}container and its upstream dependencies. The owning container and package are reported. Host paths
+{tab}{tab}residing upon the root of the container, or of its dependencies, are resolved relative thereto. Where
+{tab}{tab}the path is unowned, pacwrap exits with a status of 1 as per {bold}pacman -Qo{reset_bold}. Unless the target container
+{tab}{tab}is specified beforehand, the value immediately following this option is taken to be the target container.
+
 {sub_sect}EXAMPLE{reset_bold}
 {sub}`$ pacwrap -Qqe base`
-{tab}{tab}Print a list of explicit packages from the {bold}base{reset_bold} container to {bold}STDOUT{reset_bold}.\n"
+{tab}{tab}Print a list of explicit packages from the {bold}base{reset_bold} container to {bold}STDOUT{reset_bold}.
+
+{sub}`$ pacwrap -Ql -t base pacman`
+{tab}{tab}Print a list of files owned by the {bold}pacman{reset_bold} package in the {bold}base{reset_bold} container.
+
+{sub}`$ pacwrap -Q firefox`
+{tab}{tab}Print each container in which {bold}firefox{reset_bold} is installed, alongside the version installed.\n"
     )
 }
 
@@ -480,15 +819,34 @@ pub fn list(buf: &mut String, layout: &HelpLayout) -> Result {
 {sub_para}This command module is a shortcut to {bold}-Ul{reset_bold}. Command verb {bold}`ls`{reset_bold} also is a
 {tab}{tab}shortcut to this command module.
 
+{sub_para}On interactive terminals too narrow to fit the table, columns are elided in order of ascending priority:
+{tab}{tab}description, last updated, explicit packages, total, size on disk, and then type. Descriptions are
+{tab}{tab}truncated to fit the remaining width prior to being elided.
+
 {sub_bold}-t, --total{reset_bold}
 {tab}{tab}Display a total column.
 
 {sub_bold}-o, --on-disk{reset_bold}
 {tab}{tab}Display a size on disk column.
 
+{sub_bold}-p, --packages{reset_bold}
+{tab}{tab}Display a column counting explicitly-installed packages.
+
+{sub_bold}-a, --age{reset_bold}
+{tab}{tab}Display a column detailing when the container was last updated; relative on interactive terminals.
+{tab}{tab}Containers absent of any recorded update are displayed as {bold}never{reset_bold}.
+
+{sub_bold}-d, --description{reset_bold}
+{tab}{tab}Display a column with the description specified by the {bold}description{reset_bold} field of each container.
+
 {sub_bold}-b, --bytes{reset_bold}
 {tab}{tab}Toggle byte unit display.
 
+{sub_bold}--json{reset_bold}
+{tab}{tab}Print a single JSON array to {bold}STDOUT{reset_bold} in lieu of the table, with an object for each container detailing
+{tab}{tab}its name, type, dependencies, explicit package count, size on disk, total size in bytes, description, and
+{tab}{tab}the UNIX timestamp of its last update. Column options are disregarded.
+
 {sub_sect}EXAMPLES{reset_bold}
 {sub}`$ pacwrap -Ld`
 {tab}{tab}Print container tabulation out to {bold}STDOUT{reset_bold} with two total columns, one listing the
@@ -523,10 +881,13 @@ pub fn utils(buf: &mut String, layout: &HelpLayout) -> Result {
 {tab}{tab}Create desktop file to launch application inside of a pacwrap container.
 
 {sub_bold}-v, --view{reset_bold}
-{tab}{tab}Invoke {bold}$EDITOR{reset_bold} to view file associated with pacwrap.
+{tab}{tab}Invoke {bold}$VISUAL{reset_bold} or {bold}$EDITOR{reset_bold} to view file associated with pacwrap.
 
 {sub_bold}-e, --edit{reset_bold}
-{tab}{tab}Invoke {bold}$EDITOR{reset_bold} to edit file associated with pacwrap.
+{tab}{tab}Invoke {bold}$VISUAL{reset_bold} or {bold}$EDITOR{reset_bold} to edit file associated with pacwrap.
+
+{sub_bold}--cat{reset_bold}
+{tab}{tab}Print file associated with pacwrap to {bold}STDOUT{reset_bold} verbatim, in lieu of invoking an editor.
 
 {sub_bold}-o, --open{reset_bold}
 {tab}{tab}Invoke default file viewer on specified target's home or root directory.
@@ -537,9 +898,80 @@ pub fn utils(buf: &mut String, layout: &HelpLayout) -> Result {
 {sub_bold}-s, --symlink{reset_bold}
 {tab}{tab}Create a symbolic container.
 
+{sub_bold}-g, --graph{reset_bold}
+{tab}{tab}Print the container dependency graph to {bold}STDOUT{reset_bold} in Graphviz DOT format. Nodes are colored by
+{tab}{tab}container type, with edges directed from dependent to dependency. Symbolic containers are drawn as
+{tab}{tab}ellipses with a dashed edge to their target.
+
 {sub_bold}-r, --remove{reset_bold}
 {tab}{tab}Delete a container(s) root filesystem.
 
+{sub_bold}--doctor{reset_bold}
+{tab}{tab}Diagnose the host environment for common issues, reporting the result of each diagnostic alongside
+{tab}{tab}remediation advice. Returns a non-zero exit status if any hard requirement is unsatisfied.
+
+{sub_bold}--gc{reset_bold}
+{tab}{tab}Collect garbage: Delete filesystem state files and container roots without a corresponding container
+{tab}{tab}configuration, then report the reclaimed size. State of registered containers is never affected.
+
+{sub_bold}--verify-dedup{reset_bold}
+{tab}{tab}Verify files deduplicated from upstream containers into aggregate containers remain hardlinked, hashing
+{tab}{tab}any which are not in order to report those which have diverged from their upstream counterparts.
+
+{sub_bold}--compact-state{reset_bold} <{bold}CONTAINER{reset_bold}>
+{tab}{tab}Rewrite filesystem state, discarding entries which refer to files neither present upstream nor linked
+{tab}{tab}upon any downstream container, and report the bytes saved per container. All containers are compacted
+{tab}{tab}where no target is specified.
+
+{sub_bold}--check-config{reset_bold}
+{tab}{tab}Validate the global configuration and all container configurations, reporting every problem found,
+{tab}{tab}such as unknown keys, out-of-range values, or invalid signature levels, with file and field context.
+{tab}{tab}Returns a non-zero exit status upon any problem, suitable for use in CI or pre-commit checks.
+
+{sub_bold}--find{reset_bold} <{bold}PATTERN{reset_bold}>
+{tab}{tab}Search the local database of every container for packages matching the patterns specified, as with
+{tab}{tab}{bold}pacman -Qs{reset_bold}, reporting each container in which a match is installed alongside its version. Matches are
+{tab}{tab}grouped by package, and are followed by the versions available from the sync databases, unless
+{tab}{tab}{bold}--installed-only{reset_bold} is specified.
+{tab}{tab}Specify {bold}-q, --quiet{reset_bold} to print only the package and container names of installed matches.
+
+{sub_bold}--print-config{reset_bold} <{bold}-t, --target{reset_bold}> <{bold}CONTAINER{reset_bold}>
+{tab}{tab}Print the effective configuration as YAML, after all defaults, included fragments, and environment overrides
+{tab}{tab}are applied, with each value annotated by the file, fragment, or variable from which it originates. Values
+{tab}{tab}not otherwise specified are annotated as {bold}default{reset_bold}. Prints the global configuration unless a target is specified.
+
+{sub_bold}--dump-config-schema{reset_bold} <{bold}global{reset_bold}|{bold}container{reset_bold}>
+{tab}{tab}Emit a JSON Schema describing the valid structure of {bold}pacwrap.yml{reset_bold}, or of a container configuration
+{tab}{tab}when {bold}container{reset_bold} is specified, for use with editor tooling. The global schema covers the {bold}config:{reset_bold},
+{tab}{tab}{bold}alpm:{reset_bold}, and {bold}progress:{reset_bold} sections. The schema is derived from the configuration types, inclusive of
+{tab}{tab}the fields of each filesystem, permission, and dbus module; unrecognised keys are refused. Defaults to {bold}global{reset_bold}.
+
+{sub_bold}--apply-schema{reset_bold} <{bold}-t, --target{reset_bold}> <{bold}CONTAINER{reset_bold}>
+{tab}{tab}Re-extract the present container schema over one or more existing base containers. The schema version
+{tab}{tab}is recorded in each base's {bold}.container_schema{reset_bold} file; executing a container whose base was built with a
+{tab}{tab}differing schema version emits a warning. Only files within the schema's managed set are written, and
+{tab}{tab}files belonging to a prior schema are removed only when absent from the present schema. Re-application
+{tab}{tab}is idempotent.
+
+{sub_bold}--shell-completion-cache{reset_bold} [{bold}--clear{reset_bold}]
+{tab}{tab}Precompute the lists of container names, and of installed package names per container, read directly by
+{tab}{tab}shell completion scripts. Lists are written to {bold}$PACWRAP_CACHE_DIR/completion{reset_bold}: {bold}containers{reset_bold} holds one
+{tab}{tab}container name per line, and {bold}packages/CONTAINER{reset_bold} one package name per line. Once the cache exists, it is
+{tab}{tab}refreshed whenever pacwrap loads its containers: the container list when it is older than the container
+{tab}{tab}configuration directory, and a package list when older than its container's package database. Specify
+{tab}{tab}{bold}--clear{reset_bold} to remove the cache and cease its maintenance.
+
+{sub_bold}--backup{reset_bold} <{bold}CONTAINER{reset_bold}> <{bold}FILE{reset_bold}>
+{tab}{tab}Write a zstd-compressed tar archive of the container's configuration, its home directory, and the files
+{tab}{tab}local to its root: those not provided by upstream containers, as determined by their filesystem state,
+{tab}{tab}or which have since diverged from them. Only this delta is backed up, not the shared upstream filesystem;
+{tab}{tab}upstream containers must therefore be present upon restoration.
+
+{sub_bold}--restore{reset_bold} <{bold}FILE{reset_bold}>
+{tab}{tab}Compose the container recorded in a backup archive from its configuration, thereby installing its explicit
+{tab}{tab}packages and linking its upstream filesystem, then overlay the local files and home directory thereupon.
+{tab}{tab}The container must not already exist.
+
 {sub_sect}DESKTOP OPTIONS{reset_bold}
 {sub_para}Create and manage desktop files to launch applications in pacwrap from your favourite applications menu.
 
@@ -554,7 +986,7 @@ pub fn utils(buf: &mut String, layout: &HelpLayout) -> Result {
 {tab}{tab}Remove desktop file associated with application from `$HOME/.local/share/applications/`. 
 
 {sub_sect}EDITOR OPTIONS{reset_bold}
-{sub_para}These options are associated with the {bold}--edit{reset_bold} and {bold}--view{reset_bold} utility command modules.
+{sub_para}These options are associated with the {bold}--edit{reset_bold}, {bold}--view{reset_bold}, and {bold}--cat{reset_bold} utility command modules.
 
 {sub_bold}-c, --config{reset_bold} <{bold}CONTAINER{reset_bold}>
 {tab}{tab}Edit specified container configuration located in the pacwrap data directory. Defaults to
@@ -588,15 +1020,30 @@ pub fn utils(buf: &mut String, layout: &HelpLayout) -> Result {
 {sub_bold}-t, --total{reset_bold}
 {tab}{tab}Display a total column.
 
-{sub_bold}-d, --on-disk{reset_bold}
+{sub_bold}-o, --on-disk{reset_bold}
 {tab}{tab}Display a size on disk column.
 
+{sub_bold}-p, --packages{reset_bold}
+{tab}{tab}Display a column counting explicitly-installed packages.
+
+{sub_bold}-a, --age{reset_bold}
+{tab}{tab}Display a column detailing when the container was last updated; relative on interactive terminals.
+{tab}{tab}Containers absent of any recorded update are displayed as {bold}never{reset_bold}.
+
+{sub_bold}-d, --description{reset_bold}
+{tab}{tab}Display a column with the description specified by the {bold}description{reset_bold} field of each container.
+
 {sub_bold}-s, --summary{reset_bold}
 {tab}{tab}Print out a summary table to {bold}STDOUT{reset_bold}.
 
 {sub_bold}-b, --bytes{reset_bold}
 {tab}{tab}Toggle byte unit display for the proceeding item.
 
+{sub_bold}--json{reset_bold}
+{tab}{tab}Print a single JSON array to {bold}STDOUT{reset_bold} in lieu of the table, with an object for each container detailing
+{tab}{tab}its name, type, dependencies, explicit package count, size on disk, total size in bytes, description, and
+{tab}{tab}the UNIX timestamp of its last update. Column options are disregarded.
+
 {sub_sect}REMOVE OPTIONS{reset_bold}
 {sub_para}These options are associated with the {bold}--remove{reset_bold} utility command module.
 
@@ -609,6 +1056,24 @@ pub fn utils(buf: &mut String, layout: &HelpLayout) -> Result {
 {sub_bold}--force{reset_bold}
 {tab}{tab}Disable sanity checks and force removal of conatiner filesystem.
 
+{sub_sect}GARBAGE COLLECTION{reset_bold}
+{sub_para}These options are associated with the {bold}--gc{reset_bold} utility command module.
+
+{sub_bold}--dry-run{reset_bold}
+{tab}{tab}List orphaned data alongside the reclaimable size without deleting anything.
+
+{sub_bold}--noconfirm{reset_bold}
+{tab}{tab}Perform the operation without confirmation.
+
+{sub_sect}DEDUPLICATION{reset_bold}
+{sub_para}These options are associated with the {bold}--verify-dedup{reset_bold} utility command module.
+
+{sub_bold}-t, --target{reset_bold} <{bold}CONTAINER{reset_bold}>
+{tab}{tab}Aggregate container to verify. All aggregate containers are verified where no target is specified.
+
+{sub_bold}--repair{reset_bold}
+{tab}{tab}Re-link unlinked and divergent files to their upstream counterparts.
+
 {sub_sect}SYMBOLIC{reset_bold}
 {sub_para}These options are associated with the {bold}--symlink{reset_bold} utility command module.
 
@@ -639,6 +1104,9 @@ pub fn utils(buf: &mut String, layout: &HelpLayout) -> Result {
 {tab}{tab}Print container tabulation out to {bold}STDOUT{reset_bold} with two total columns, one listing the
 {tab}{tab}container name, and the other detailing the total size-on-disk consumption displayed with byteunits.
 
+{sub}`$ pacwrap -Ug | dot -Tsvg -o graph.svg`
+{tab}{tab}Render the container dependency graph to `graph.svg` with Graphviz.
+
 {sub}`$ pacwrap utils -lbtbts`
 {tab}{tab}Print container tabulation to {bold}STDOUT{reset_bold} with three total columns, first listing the
 {tab}{tab}container name, second the total amount of bytes, and the last showing the total with byteunits. 
@@ -700,15 +1168,20 @@ pub fn environment(buf: &mut String, layout: &HelpLayout) -> Result {
 {sub_bold}PACWRAP_CACHE_DIR{reset_bold} <{bold}DIR{reset_bold}> 
 {tab}{tab}Set path of the cache directory, overriding the default location.
 
+{sub_bold}PACWRAP_BWRAP{reset_bold} <{bold}PATH{reset_bold}>
+{tab}{tab}Set absolute path of the {bold}bwrap{reset_bold}(1) executable, overriding both $PATH resolution and the
+{tab}{tab}{bold}bwrap_executable{reset_bold} configuration option.
+
 {sub_bold}PACWRAP_HOME{reset_bold} <{bold}DIR{reset_bold}>
 {tab}{tab}Upon container invocation, mount the set path provided when engaging the {bold}`home`{reset_bold} filesystem module.
 
 {sub_bold}PACWRAP_ROOT{reset_bold} <{bold}DIR{reset_bold}>
 {tab}{tab}Upon container invocation, mount the set path provided when engaging the {bold}`root`{reset_bold} filesystem module.
 
-{sub_bold}PACWRAP_VERBOSE{reset_bold} <{bold}0{reset_bold} | {bold}1{reset_bold}>
-{tab}{tab}Toggle verbose output during a transaction. Valid options are `1` for enablement and `0` for 
-{tab}{tab}disablement of verbosity.
+{sub_bold}PACWRAP_VERBOSE{reset_bold} <{bold}0{reset_bold} - {bold}3{reset_bold}>
+{tab}{tab}Set the verbosity level during a transaction, upon the same scale as {bold}`-v, --verbose`{reset_bold}. Where both are
+{tab}{tab}specified, the greater of the two levels is in effect. `0` disables verbosity, whereas `1` is treated as
+{tab}{tab}level `2` for compatibility with the former toggle.
 
 {sub_sect}DEFAULT{reset_bold}
 {sub_para}For the following environment variables, contained herein are default runtime values. Any variables not
@@ -738,7 +1211,11 @@ pub fn version(buf: &mut String, layout: &HelpLayout) -> Result {
         "{head}VERSION{reset}
 {sub_bold}-V, --version, --version=min{reset_bold}
 {tab}{tab}Sends version information to {bold}STDOUT{reset_bold} with colourful ASCII art. 
-{tab}{tab}The 'min' option provides a minimalistic output as is provided to non-colour terms.\n"
+{tab}{tab}The 'min' option provides a minimalistic output as is provided to non-colour terms.
+
+{sub_bold}--format{reset_bold} <{bold}plain{reset_bold} | {bold}json{reset_bold}>
+{tab}{tab}Print version information in a format suitable for scripting. The 'plain' format prints the bare
+{tab}{tab}version string, whereas the 'json' format prints the version, build type, build stamp, and build time.\n"
     )
 }
 
@@ -18,12 +18,52 @@
  */
 
 use pacwrap_core::{
-    utils::{arguments::Operand, is_truecolor_terminal, Arguments},
+    err,
+    utils::{
+        arguments::{InvalidArgument::UnsuppliedOperand, Operand},
+        is_truecolor_terminal,
+        Arguments,
+    },
+    Error,
     Result,
 };
 
 use crate::help::version_string;
 
+enum VersionFormat {
+    Default,
+    Plain,
+    Json,
+}
+
+fn format(args: &mut Arguments) -> Result<VersionFormat> {
+    let mut format = VersionFormat::Default;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            Operand::Long("format") => match args.next() {
+                Some(Operand::LongPos("format", "plain")) => format = VersionFormat::Plain,
+                Some(Operand::LongPos("format", "json")) => format = VersionFormat::Json,
+                Some(_) => args.invalid_operand()?,
+                None => err!(UnsuppliedOperand("--format", "Version format not specified."))?,
+            },
+            _ => continue,
+        }
+    }
+
+    Ok(format)
+}
+
+fn print_json() {
+    println!(
+        "{{\"version\":\"{}\",\"build\":\"{}\",\"buildstamp\":\"{}\",\"buildtime\":{}}}",
+        env!("CARGO_PKG_VERSION"),
+        env!("PACWRAP_BUILD"),
+        env!("PACWRAP_BUILDSTAMP"),
+        env!("PACWRAP_BUILDTIME")
+    );
+}
+
 fn minimal(args: &mut Arguments) -> bool {
     args.into_iter()
         .filter(|a| a == &Operand::LongPos("version", "min") || a == &Operand::ShortPos('V', "min"))
@@ -32,6 +72,16 @@ fn minimal(args: &mut Arguments) -> bool {
 }
 
 pub fn print_version(args: &mut Arguments) -> Result<()> {
+    match format(args)? {
+        VersionFormat::Plain => println!("{}", env!("CARGO_PKG_VERSION")),
+        VersionFormat::Json => print_json(),
+        VersionFormat::Default => print_banner(args),
+    }
+
+    Ok(())
+}
+
+fn print_banner(args: &mut Arguments) {
     let version = format!("{} v{}", env!("CARGO_PKG_NAME"), version_string());
 
     if !minimal(args) && is_truecolor_terminal() {
@@ -68,6 +118,4 @@ This program may be freely redistributed under the
 terms of the GNU General Public License v3 only.\n"
         );
     }
-
-    Ok(())
 }
@@ -17,7 +17,15 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use pacwrap_core::utils::arguments::{Arguments, Operand as Op};
+use std::process::exit;
+
+use pacwrap_core::utils::{
+    arguments::{Arguments, Operand as Op},
+    check_root,
+    porcelain::{self, Record},
+    status,
+    warnings,
+};
 
 use crate::utils::list;
 
@@ -30,23 +38,56 @@ mod remove;
 mod sync;
 mod utils;
 
+const GLOBALS: [&str; 2] = ["porcelain", "no-warning-summary"];
+
 fn main() {
     let arguments = &mut Arguments::new().populate();
-    let result = match arguments.next().unwrap_or_default() {
-        Op::Short('E') | Op::Long("exec") | Op::Value("shell") | Op::Value("run") => exec::execute(arguments),
-        Op::Short('S') | Op::Long("sync") | Op::Value("sync") | Op::Value("init") => sync::synchronize(arguments),
-        Op::Short('L') | Op::Long("list") | Op::Value("ls") | Op::Value("list") => list::list_containers(arguments),
-        Op::Short('R') | Op::Long("remove") | Op::Value("remove") | Op::Value("rm") => remove::remove(arguments),
-        Op::Short('P') | Op::Long("process") | Op::Value("process") | Op::Value("ps") => proc::process(arguments),
-        Op::Short('Q') | Op::Long("query") | Op::Value("query") => query::query(arguments),
-        Op::Short('C') | Op::Long("compose") | Op::Value("compose") => compose::compose(arguments),
-        Op::Short('U') | Op::Long("utils") | Op::Value("utils") => utils::engage_utility(arguments),
-        Op::Short('V') | Op::Long("version") | Op::Value("version") => help::print_version(arguments),
-        Op::Short('h') | Op::Long("help") | Op::Value("help") => help::help(arguments),
+    //Global options may precede the operation, hence are passed over in order to ascertain it.
+    let operation = arguments.find(|a| !matches!(a, Op::Long(long) if GLOBALS.contains(long))).unwrap_or_default();
+
+    if matches!(operation, Op::Short('E') | Op::Long("exec") | Op::Value("shell") | Op::Value("run")) {
+        //Warnings are not summarised upon execution, where global options are instead passed to the command sequence.
+        warnings::disable();
+    } else {
+        if arguments.global("porcelain") {
+            porcelain::enable();
+        }
+
+        if arguments.global("no-warning-summary") {
+            warnings::disable();
+        }
+
+        //Upon removal of any preceding global options, the operation is foremost.
+        arguments.set_index(0);
+        arguments.next();
+    }
+
+    let result = match (operation, check_root()) {
+        (Op::Short('V') | Op::Long("version") | Op::Value("version"), _) => help::print_version(arguments),
+        (Op::Short('h') | Op::Long("help") | Op::Value("help"), _) => help::help(arguments),
+        (_, Err(error)) => Err(error),
+        (Op::Short('E') | Op::Long("exec") | Op::Value("shell") | Op::Value("run"), _) => exec::execute(arguments),
+        (Op::Short('S') | Op::Long("sync") | Op::Value("sync") | Op::Value("init"), _) => sync::synchronize(arguments),
+        (Op::Short('L') | Op::Long("list") | Op::Value("ls") | Op::Value("list"), _) => list::list_containers(arguments),
+        (Op::Short('R') | Op::Long("remove") | Op::Value("remove") | Op::Value("rm"), _) => remove::remove(arguments),
+        (Op::Short('P') | Op::Long("process") | Op::Value("process") | Op::Value("ps"), _) => proc::process(arguments),
+        (Op::Short('Q') | Op::Long("query") | Op::Value("query"), _) => query::query(arguments),
+        (Op::Short('C') | Op::Long("compose") | Op::Value("compose"), _) => compose::compose(arguments),
+        (Op::Short('U') | Op::Long("utils") | Op::Value("utils"), _) => utils::engage_utility(arguments),
         _ => arguments.invalid_operand(),
     };
 
+    status::release();
+    warnings::summarize();
+
     if let Err(error) = result {
+        if porcelain::is_porcelain() {
+            let code = error.kind().code();
+
+            porcelain::emit(Record::Error(code, &error));
+            exit(code);
+        }
+
         error.error();
     }
 }
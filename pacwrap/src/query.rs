@@ -17,48 +17,272 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use alpm::PackageReason;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Formatter, Result as FmtResult},
+    path::{Component, Path},
+};
+
+use alpm::{Alpm, PackageReason};
 
 use pacwrap_core::{
-    config,
-    constants::{BOLD_GREEN, RESET},
+    config::{self, cache::populate, ContainerHandle, ContainerType},
+    constants::{ARROW_CYAN, ARROW_GREEN, BOLD, BOLD_GREEN, RESET},
     err,
     error::*,
+    impl_error,
     sync::{instantiate_alpm, transaction::TransactionFlags},
-    utils::{
-        arguments::{Arguments, InvalidArgument, Operand},
-        check_root,
-    },
+    utils::arguments::{Arguments, InvalidArgument, Operand},
+    ErrorKind,
 };
 
+#[derive(Debug)]
+enum QueryError {
+    PackageNotInstalled(String, String),
+    PackageNotFound(String),
+    PackageUnspecified,
+    PathUnowned(String, String),
+    PathUnspecified,
+    PatternUnspecified,
+    NoMatches(String),
+}
+
+impl_error!(QueryError);
+
+impl Display for QueryError {
+    fn fmt(&self, fmter: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::PackageNotInstalled(ins, pkg) =>
+                write!(fmter, "Package '{}{pkg}{}' is not installed in container {}{ins}{}.", *BOLD, *RESET, *BOLD, *RESET),
+            Self::PackageNotFound(pkg) => write!(fmter, "Package '{}{pkg}{}' was not found in any container.", *BOLD, *RESET),
+            Self::PackageUnspecified => write!(fmter, "Package unspecified."),
+            Self::PathUnowned(ins, path) => write!(
+                fmter,
+                "'{}{path}{}' is not owned by any package in container {}{ins}{} or its dependencies.",
+                *BOLD,
+                *RESET,
+                *BOLD,
+                *RESET
+            ),
+            Self::PathUnspecified => write!(fmter, "Path unspecified."),
+            Self::PatternUnspecified => write!(fmter, "Search pattern unspecified."),
+            Self::NoMatches(pattern) => write!(fmter, "No packages matching '{}{pattern}{}' were found.", *BOLD, *RESET),
+        }
+    }
+}
+
 pub fn query(arguments: &mut Arguments) -> Result<()> {
     let mut flags: TransactionFlags = TransactionFlags::NONE;
     let mut target = "";
+    let mut targeted = false;
+    let mut packages = Vec::new();
     let mut explicit = false;
+    let mut deps = false;
     let mut quiet = false;
-
-    check_root()?;
+    let mut files = false;
+    let mut owns = false;
 
     while let Some(arg) = arguments.next() {
         match arg {
             Operand::Long("debug") => flags |= TransactionFlags::DEBUG,
             Operand::Long("target") | Operand::Short('t') => continue,
             Operand::Short('e') | Operand::Long("explicit") => explicit = true,
+            Operand::Short('d') | Operand::Long("deps") => deps = true,
             Operand::Short('q') | Operand::Long("quiet") => quiet = true,
+            Operand::Short('l') | Operand::Long("files") => files = true,
+            Operand::Short('o') | Operand::Long("owns") => owns = true,
+            //Absent a target, the value is instead the target container, as per 'pacwrap -Qo <CONTAINER> <PATH>'.
+            Operand::ShortPos('o', path) | Operand::LongPos("owns", path) if !target.is_empty() => {
+                owns = true;
+                packages.push(path);
+            }
+            Operand::ShortPos('t', t) | Operand::LongPos("target", t) => {
+                target = t;
+                targeted = true;
+            }
+            Operand::Value(pkg) if !target.is_empty() => packages.push(pkg),
             Operand::LongPos(_, t) | Operand::ShortPos(_, t) | Operand::Value(t) => target = t,
             _ => arguments.invalid_operand()?,
         }
     }
 
+    if explicit && deps {
+        err!(ErrorKind::Message("Options '--explicit' and '--deps' are mutually exclusive."))?
+    }
+
     if target.is_empty() {
         err!(InvalidArgument::TargetUnspecified)?
     }
 
-    let handle = config::provide_handle(target)?;
-    let handle = instantiate_alpm(&handle, &flags)?;
+    let reason = match (explicit, deps) {
+        (true, _) => Some(PackageReason::Explicit),
+        (_, true) => Some(PackageReason::Depend),
+        _ => None,
+    };
+
+    if !targeted && !owns && !files {
+        let cache = populate()?;
+
+        //Absent an explicit target, operands not naming a container are queried across all containers.
+        if cache.get_instance_option(target).is_none() {
+            let handles = cache.filter_handle(vec![ContainerType::Base, ContainerType::Slice, ContainerType::Aggregate]);
+
+            packages.insert(0, target);
+            return query_all(&handles, &packages, reason, quiet);
+        }
+    }
+
+    let inshandle = config::provide_handle(target)?;
+
+    if owns {
+        return query_owner(&inshandle, &packages, &flags, quiet);
+    }
+
+    let handle = instantiate_alpm(&inshandle, &flags)?;
+
+    match files {
+        true => list_files(&handle, target, &packages, quiet),
+        false => list_packages(&handle, reason, quiet),
+    }
+}
+
+/// Search the local database of every container, and optionally their sync databases, for packages
+/// matching the patterns specified, reporting matches grouped by package and then by container.
+pub fn find(arguments: &mut Arguments) -> Result<()> {
+    let mut patterns = Vec::new();
+    let mut installed_only = false;
+    let mut quiet = false;
+
+    while let Some(arg) = arguments.next() {
+        match arg {
+            Operand::Long("find") | Operand::Value("find") => continue,
+            Operand::Long("installed-only") => installed_only = true,
+            Operand::Short('q') | Operand::Long("quiet") => quiet = true,
+            Operand::LongPos("find", pattern) | Operand::Value(pattern) => patterns.push(pattern),
+            _ => arguments.invalid_operand()?,
+        }
+    }
+
+    if patterns.is_empty() {
+        err!(QueryError::PatternUnspecified)?
+    }
+
+    let cache = populate()?;
+    let mut installed: BTreeMap<String, Vec<(&str, String)>> = BTreeMap::new();
+    let mut available: BTreeMap<String, BTreeSet<(String, String)>> = BTreeMap::new();
+
+    for inshandle in cache.filter_handle(vec![ContainerType::Base, ContainerType::Slice, ContainerType::Aggregate]) {
+        if !Path::new(inshandle.vars().root()).join("var/lib/pacman").exists() {
+            continue;
+        }
+
+        let instance = inshandle.vars().instance();
+        let handle = instantiate_alpm(inshandle, &TransactionFlags::NONE)?;
+
+        if let Ok(pkgs) = handle.localdb().search(patterns.iter().copied()) {
+            for pkg in pkgs.iter() {
+                installed.entry(pkg.name().into()).or_default().push((instance, pkg.version().to_string()));
+            }
+        }
+
+        if installed_only {
+            continue;
+        }
 
+        for db in handle.syncdbs() {
+            if let Ok(pkgs) = db.search(patterns.iter().copied()) {
+                for pkg in pkgs.iter() {
+                    available.entry(pkg.name().into()).or_default().insert((db.name().into(), pkg.version().to_string()));
+                }
+            }
+        }
+    }
+
+    let packages: BTreeSet<&String> = installed.keys().chain(available.keys()).collect();
+
+    if packages.is_empty() {
+        err!(QueryError::NoMatches(patterns.join(" ")))?
+    }
+
+    for name in packages {
+        if quiet {
+            for (ins, _) in installed.get(name).into_iter().flatten() {
+                println!("{name} {ins}");
+            }
+
+            continue;
+        }
+
+        println!("{}{name}{}", *BOLD, *RESET);
+
+        for (ins, version) in installed.get(name).into_iter().flatten() {
+            println!("{} {ins} {}{version}{}", *ARROW_GREEN, *BOLD_GREEN, *RESET);
+        }
+
+        for (repo, version) in available.get(name).into_iter().flatten() {
+            println!("{} {repo}/{name} {}{version}{} (available)", *ARROW_CYAN, *BOLD_GREEN, *RESET);
+        }
+    }
+
+    Ok(())
+}
+
+/// Search the local database of every container for packages named, or providing, those specified.
+fn query_all(handles: &[&ContainerHandle], packages: &[&str], reason: Option<PackageReason>, quiet: bool) -> Result<()> {
+    let mut found = BTreeSet::new();
+
+    for inshandle in handles {
+        if !Path::new(inshandle.vars().root()).join("var/lib/pacman").exists() {
+            continue;
+        }
+
+        let instance = inshandle.vars().instance();
+        let handle = instantiate_alpm(inshandle, &TransactionFlags::NONE)?;
+
+        for pkg in handle.localdb().pkgs() {
+            if reason.is_some_and(|reason| pkg.reason() != reason) {
+                continue;
+            }
+
+            let name = match packages
+                .iter()
+                .find(|name| pkg.name() == **name || pkg.provides().iter().any(|dep| dep.name() == **name))
+            {
+                Some(name) => name,
+                None => continue,
+            };
+
+            match quiet {
+                true => println!("{instance} {}", pkg.name()),
+                false => println!("{}{instance}{}: {} {}{}{}", *BOLD, *RESET, pkg.name(), *BOLD_GREEN, pkg.version(), *RESET),
+            }
+
+            found.insert(*name);
+        }
+    }
+
+    if found.is_empty() {
+        err!(QueryError::PackageNotFound(packages.join(" ")))?
+    }
+
+    Ok(())
+}
+
+/// Count explicitly-installed packages in the container, or `None` where its root is uninitialized.
+pub fn explicit_packages(inshandle: &ContainerHandle) -> Result<Option<usize>> {
+    if !Path::new(inshandle.vars().root()).join("var/lib/pacman").exists() {
+        return Ok(None);
+    }
+
+    let handle = instantiate_alpm(inshandle, &TransactionFlags::NONE)?;
+    let count = handle.localdb().pkgs().iter().filter(|pkg| pkg.reason() == PackageReason::Explicit).count();
+
+    Ok(Some(count))
+}
+
+fn list_packages(handle: &Alpm, reason: Option<PackageReason>, quiet: bool) -> Result<()> {
     for pkg in handle.localdb().pkgs() {
-        if explicit && pkg.reason() != PackageReason::Explicit {
+        if reason.is_some_and(|reason| pkg.reason() != reason) {
             continue;
         }
 
@@ -70,3 +294,124 @@ pub fn query(arguments: &mut Arguments) -> Result<()> {
 
     Ok(())
 }
+
+fn query_owner(inshandle: &ContainerHandle, paths: &[&str], flags: &TransactionFlags, quiet: bool) -> Result<()> {
+    let instance = inshandle.vars().instance();
+    let mut handles = Vec::new();
+    let mut roots = vec![inshandle.vars().root().to_string()];
+
+    if paths.is_empty() {
+        err!(QueryError::PathUnspecified)?
+    }
+
+    //Upstream containers are searched first, given that packages foreign to a container
+    //persist within its local database despite their files residing upstream.
+    for dep in inshandle.metadata().dependencies() {
+        let dephandle = config::provide_handle(dep)?;
+
+        roots.push(dephandle.vars().root().into());
+        handles.push((dep.to_string(), instantiate_alpm(&dephandle, flags)?));
+    }
+
+    handles.push((instance.to_string(), instantiate_alpm(inshandle, flags)?));
+
+    for path in paths {
+        let file = normalize(path, &roots);
+        let file = file.as_str();
+        let owner = handles.iter().find_map(|(ins, handle)| {
+            handle
+                .localdb()
+                .pkgs()
+                .iter()
+                .find(|pkg| pkg.files().contains(file).or(pkg.files().contains(format!("{file}/"))).is_some())
+                .map(|pkg| (ins, pkg))
+        });
+
+        match owner {
+            Some((ins, pkg)) => match quiet {
+                true => println!("{}", pkg.name()),
+                false => println!(
+                    "/{file} is owned by {} {}{}{} in container {}{ins}{}",
+                    pkg.name(),
+                    *BOLD_GREEN,
+                    pkg.version(),
+                    *RESET,
+                    *BOLD,
+                    *RESET
+                ),
+            },
+            None => err!(QueryError::PathUnowned(instance.into(), format!("/{file}")))?,
+        }
+    }
+
+    Ok(())
+}
+
+//Paths are resolved relative to the container root, whereby host paths residing upon the root of the
+//container, or of its dependencies, are stripped of their prefix.
+fn normalize(path: &str, roots: &[String]) -> String {
+    let path = Path::new(path);
+    let path = roots.iter().find_map(|root| path.strip_prefix(root).ok()).unwrap_or(path);
+    let mut components = Vec::new();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => components.push(name.to_string_lossy()),
+            Component::ParentDir => {
+                components.pop();
+            }
+            _ => continue,
+        }
+    }
+
+    components.join("/")
+}
+
+fn list_files(handle: &Alpm, instance: &str, packages: &[&str], quiet: bool) -> Result<()> {
+    if packages.is_empty() {
+        err!(QueryError::PackageUnspecified)?
+    }
+
+    for name in packages {
+        let pkg = match handle.localdb().pkg(*name) {
+            Ok(pkg) => pkg,
+            Err(_) => err!(QueryError::PackageNotInstalled(instance.into(), name.to_string()))?,
+        };
+
+        for file in pkg.files().files() {
+            match quiet {
+                true => println!("/{}", file.name()),
+                false => println!("{} /{}", pkg.name(), file.name()),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalize_absolute() {
+        assert_eq!(normalize("/usr/bin/bash", &[]), "usr/bin/bash");
+        assert_eq!(normalize("usr/bin/bash", &[]), "usr/bin/bash");
+    }
+
+    #[test]
+    fn normalize_components() {
+        assert_eq!(normalize("/usr/lib/../bin/./bash", &[]), "usr/bin/bash");
+        assert_eq!(normalize("/usr//bin/bash/", &[]), "usr/bin/bash");
+        assert_eq!(normalize("/../usr/bin/bash", &[]), "usr/bin/bash");
+    }
+
+    #[test]
+    fn normalize_roots() {
+        let roots = vec!["/pacwrap/root/base".to_string(), "/pacwrap/root/slice".to_string()];
+
+        assert_eq!(normalize("/pacwrap/root/base/usr/bin/bash", &roots), "usr/bin/bash");
+        assert_eq!(normalize("/pacwrap/root/slice/usr/lib/libfoo.so", &roots), "usr/lib/libfoo.so");
+        assert_eq!(normalize("/pacwrap/root/other/usr/bin/bash", &roots), "pacwrap/root/other/usr/bin/bash");
+    }
+}
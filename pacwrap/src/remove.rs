@@ -25,18 +25,21 @@ use pacwrap_core::{
     error::*,
     lock::Lock,
     log::Logger,
-    sync::transaction::{TransactionAggregator, TransactionFlags, TransactionType},
+    sync::{
+        event::summary::override_summary,
+        transaction::{TransactionAggregator, TransactionFlags, TransactionType},
+    },
     utils::{
         arguments::{Arguments, InvalidArgument::*, Operand as Op},
-        check_root,
+        status,
+        verbosity,
     },
     ErrorKind,
 };
 
-use crate::utils::delete::remove_containers;
+use crate::utils::{delete::remove_containers, targets::read_targets};
 
 pub fn remove(args: &mut Arguments) -> Result<()> {
-    check_root()?;
     init()?;
 
     if args.len() > 1 && (args[0] == Op::Value("rm") || args[1] == Op::Short('m') || args[1] == Op::Long("delete")) {
@@ -79,8 +82,10 @@ fn engage_aggregator<'a>(
     let cache = cache::populate()?;
     let mut flags = TransactionFlags::NONE;
     let mut targets = Vec::new();
+    let mut verbose = 0;
     let mut queue: HashMap<&'a str, Vec<&'a str>> = HashMap::new();
     let mut current_target = None;
+    let mut tagged = Vec::new();
 
     if let Op::Nothing = args.next().unwrap_or_default() {
         err!(OperationUnspecified)?
@@ -95,12 +100,65 @@ fn engage_aggregator<'a>(
             | Op::Short('c')
             | Op::Short('s') => continue,
             Op::Long("debug") => flags |= TransactionFlags::DEBUG,
+            Op::Long("trace") => flags |= TransactionFlags::TRACE,
             Op::Long("dbonly") => flags |= TransactionFlags::DATABASE_ONLY,
             Op::Long("noconfirm") => flags |= TransactionFlags::NO_CONFIRM,
             Op::Long("force-foreign") => flags |= TransactionFlags::FORCE_DATABASE,
             Op::Long("disable-sandbox") => flags |= TransactionFlags::NO_ALPM_SANDBOX,
+            Op::Long("unfreeze") => flags |= TransactionFlags::UNFREEZE,
+            Op::Long("no-hooks") => flags |= TransactionFlags::NO_HOOKS,
+            Op::Long("continue-on-error") => flags |= TransactionFlags::CONTINUE_ON_ERROR,
             Op::Short('p') | Op::Long("preview") => flags |= TransactionFlags::PREVIEW,
             Op::Short('f') | Op::Long("filesystem") => flags |= TransactionFlags::FILESYSTEM_SYNC,
+            Op::Short('o') | Op::Long("target-only") => flags |= TransactionFlags::TARGET_ONLY,
+            Op::Short('v') | Op::Long("verbose") => verbose += 1,
+            Op::Long("summary-format") => match args.next() {
+                Some(Op::LongPos("summary-format", format)) => override_summary(format)?,
+                Some(_) => args.invalid_operand()?,
+                None => err!(UnsuppliedOperand("--summary-format", "Summary format not specified."))?,
+            },
+            Op::Long("status-socket") => match args.next() {
+                Some(Op::LongPos("status-socket", path)) => status::bind(path)?,
+                Some(_) => args.invalid_operand()?,
+                None => err!(UnsuppliedOperand("--status-socket", "Socket path not specified."))?,
+            },
+            Op::Long("target-file") => match args.next() {
+                Some(Op::LongPos("target-file", path)) =>
+                    for (target, packages) in read_targets(path)? {
+                        if let ContainerType::Symbolic = cache.get_instance(target)?.metadata().container_type() {
+                            err!(ErrorKind::Message("Symbolic containers cannot be transacted."))?;
+                        }
+
+                        current_target = Some(target);
+                        targets.push(target);
+                        queue.entry(target).or_default().extend(packages);
+                    },
+                Some(_) => args.invalid_operand()?,
+                None => err!(ErrorKind::Message("Target file not specified."))?,
+            },
+            Op::Long("tag") => match args.next() {
+                Some(Op::LongPos("tag", tag)) => {
+                    let containers = cache.tagged(tag);
+
+                    if containers.is_empty() {
+                        err!(ErrorKind::TagNotFound(tag.into()))?;
+                    }
+
+                    for target in containers {
+                        if !tagged.contains(&target) {
+                            tagged.push(target);
+                        }
+
+                        if !targets.contains(&target) {
+                            targets.push(target);
+                        }
+                    }
+
+                    current_target = None;
+                }
+                Some(_) => args.invalid_operand()?,
+                None => err!(ErrorKind::Message("Tag not specified."))?,
+            },
             Op::Short('t') | Op::Long("target") => match args.next() {
                 Some(arg) => match arg {
                     Op::ShortPos('t', target) | Op::LongPos("target", target) => {
@@ -123,15 +181,22 @@ fn engage_aggregator<'a>(
                             queue.insert(target, vec![package]);
                         }
                     }
+                } else {
+                    for target in &tagged {
+                        queue.entry(target).or_default().push(package);
+                    }
                 },
             _ => args.invalid_operand()?,
         }
     }
 
-    if current_target.is_none() {
+    if current_target.is_none() && tagged.is_empty() {
         err!(TargetUnspecified)?
+    } else if flags.contains(TransactionFlags::TARGET_ONLY | TransactionFlags::FILESYSTEM_SYNC) {
+        err!(ErrorKind::Message("Options '--target-only' and '--filesystem' are mutually exclusive."))?
     }
 
+    verbosity::set(verbose);
     TransactionAggregator::new(&cache, log, action_type)
         .assert_lock(lock)?
         .target(Some(targets))
@@ -21,27 +21,34 @@ use std::collections::{HashMap, HashSet};
 
 use indexmap::IndexMap;
 use pacwrap_core::{
-    config::{cache, init::init, ConfigError::AlreadyExists, ContainerCache, ContainerType},
+    config::{cache, init::init, provide_handle, ConfigError::AlreadyExists, ContainerCache, ContainerType},
     constants::{ARROW_GREEN, BAR_GREEN, BOLD, RESET},
     err,
     error::*,
     lock::Lock,
     log::{Level::Info, Logger},
     sync::{
+        event::summary::override_summary,
         instantiate_container,
         instantiate_trust,
+        override_download_rate,
+        override_paths,
+        refresh_trust,
+        schema::MINIMAL_PACKAGES,
         transaction::{TransactionAggregator, TransactionFlags, TransactionType},
     },
     utils::{
         arguments::{Arguments, InvalidArgument::*, Operand as Op},
-        check_root,
         print_warning,
+        status,
+        verbosity,
     },
     ErrorKind,
 };
 
+use crate::utils::targets::read_targets;
+
 pub fn synchronize(args: &mut Arguments) -> Result<()> {
-    check_root()?;
     init()?;
 
     let mut logger = Logger::new("pacwrap-sync").init()?;
@@ -77,10 +84,12 @@ fn action(args: &mut Arguments) -> (TransactionType, bool) {
 
 fn instantiate<'a>(
     cache: &mut ContainerCache<'a>,
-    lock: &'a Lock,
     logger: &mut Logger,
     action_type: &TransactionType,
     targets: IndexMap<&'a str, (ContainerType, Vec<&'a str>)>,
+    minimal: HashSet<&'a str>,
+    copied: HashMap<&'a str, &'a str>,
+    profile: bool,
 ) -> Result<()> {
     if targets.is_empty() {
         err!(OperationUnspecified)?;
@@ -104,11 +113,21 @@ fn instantiate<'a>(
         }
     }
 
-    lock.assert()?;
     println!("{} {}Instantiating container{}...{}", *BAR_GREEN, *BOLD, if targets.len() > 1 { "s" } else { "" }, *RESET);
 
     for (container, (container_type, deps)) in targets {
-        cache.add(container, container_type, deps)?;
+        cache.add(container, container_type, deps, profile)?;
+
+        if minimal.contains(container) {
+            cache.get_instance_mut(container)?.metadata_mut().set_minimal(true);
+        }
+
+        if let Some(source) = copied.get(container) {
+            let source = provide_handle(source)?;
+
+            cache.get_instance_mut(container)?.copy_config(&source);
+        }
+
         instantiate_container(cache.get_instance(container)?)?;
         logger.log(Info, &format!("Instantiation of {container} complete."))?;
         println!("{} Instantiation of {container} complete.", *ARROW_GREEN);
@@ -155,10 +174,23 @@ fn engage_aggregator<'a>(
     let mut flags = TransactionFlags::NONE;
     let mut create_targets: IndexMap<&'a str, (ContainerType, Vec<&'a str>)> = IndexMap::new();
     let mut targets = HashSet::new();
-    let mut queue = HashMap::new();
+    let mut queue: HashMap<&str, Vec<&str>> = HashMap::new();
     let mut current_target = None;
+    let mut tagged = Vec::new();
     let mut container_type = None;
     let mut create = init;
+    let mut minimal = false;
+    let mut force_relink = false;
+    let mut minimal_targets = HashSet::new();
+    let mut copied = HashMap::new();
+    let mut profile = true;
+    let mut verbose = 0;
+    let mut refresh_keys = false;
+    let mut ignored = Vec::new();
+    let mut treated = Vec::new();
+    let mut after: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut cachedir = None;
+    let mut dbpath = None;
 
     if let Op::Nothing = args.next().unwrap_or_default() {
         err!(OperationUnspecified)?
@@ -168,14 +200,119 @@ fn engage_aggregator<'a>(
         match arg {
             Op::Short('y') | Op::Short('u') | Op::Long("refresh") | Op::Long("upgrade") => continue,
             Op::Long("debug") => flags |= TransactionFlags::DEBUG,
+            Op::Long("trace") => flags |= TransactionFlags::TRACE,
             Op::Long("dbonly") => flags |= TransactionFlags::DATABASE_ONLY,
             Op::Long("noconfirm") => flags |= TransactionFlags::NO_CONFIRM,
             Op::Long("force-foreign") => flags |= TransactionFlags::FORCE_DATABASE,
             Op::Long("disable-sandbox") => flags |= TransactionFlags::NO_ALPM_SANDBOX,
+            Op::Long("unfreeze") => flags |= TransactionFlags::UNFREEZE,
+            Op::Long("reinstall") | Op::Long("no-needed") => flags |= TransactionFlags::REINSTALL,
+            Op::Long("needed") => continue,
+            Op::Long("force") => flags |= TransactionFlags::FORCE,
+            Op::Long("no-filesystem-sync") => flags |= TransactionFlags::NO_FILESYSTEM_SYNC,
+            Op::Long("offline") => flags |= TransactionFlags::OFFLINE,
+            Op::Long("no-hooks") => flags |= TransactionFlags::NO_HOOKS,
+            Op::Long("continue-on-error") => flags |= TransactionFlags::CONTINUE_ON_ERROR,
+            Op::Long("verify-packages") => flags |= TransactionFlags::VERIFY_PACKAGES,
+            Op::Long("only-outdated") => flags |= TransactionFlags::ONLY_OUTDATED,
+            Op::Long("sysupgrade-only") => flags |= TransactionFlags::SYSUPGRADE_ONLY,
+            Op::Long("refresh-keys") => refresh_keys = true,
+            Op::Long("minimal") => minimal = true,
+            Op::Long("force-relink") => force_relink = true,
+            Op::Long("no-profile") => profile = false,
+            Op::Long("ignore-container") => match args.next() {
+                Some(arg) => match arg {
+                    Op::LongPos("ignore-container", container) => {
+                        cache.get_instance(container)?;
+                        ignored.push(container);
+                    }
+                    _ => args.invalid_operand()?,
+                },
+                None => err!(TargetUnspecified)?,
+            },
+            Op::Long("after") => match args.next() {
+                Some(Op::LongPos("after", container)) => match current_target {
+                    Some(target) => {
+                        cache.get_instance(container)?;
+                        after.entry(target).or_default().push(container);
+                    }
+                    None => err!(ErrorKind::Message("Option '--after' must follow a target."))?,
+                },
+                Some(_) => args.invalid_operand()?,
+                None => err!(TargetUnspecified)?,
+            },
+            Op::Long("treat-resident") => match args.next() {
+                Some(Op::LongPos("treat-resident", package)) => treated.push(package),
+                Some(_) => args.invalid_operand()?,
+                None => err!(ErrorKind::Message("Package not specified."))?,
+            },
+            Op::Long("max-download-rate") => match args.next() {
+                Some(Op::LongPos("max-download-rate", rate)) => match rate.parse() {
+                    Ok(rate) => override_download_rate(rate),
+                    Err(_) => err!(ErrorKind::Message("Download rate must be an integer value in KiB/s."))?,
+                },
+                Some(_) => args.invalid_operand()?,
+                None => err!(ErrorKind::Message("Download rate not specified."))?,
+            },
+            Op::Long("summary-format") => match args.next() {
+                Some(Op::LongPos("summary-format", format)) => override_summary(format)?,
+                Some(_) => args.invalid_operand()?,
+                None => err!(UnsuppliedOperand("--summary-format", "Summary format not specified."))?,
+            },
+            Op::Long("status-socket") => match args.next() {
+                Some(Op::LongPos("status-socket", path)) => status::bind(path)?,
+                Some(_) => args.invalid_operand()?,
+                None => err!(UnsuppliedOperand("--status-socket", "Socket path not specified."))?,
+            },
+            Op::Long("cachedir") => match args.next() {
+                Some(Op::LongPos("cachedir", path)) => cachedir = Some(path),
+                Some(_) => args.invalid_operand()?,
+                None => err!(ErrorKind::Message("Cache directory not specified."))?,
+            },
+            Op::Long("target-file") => match args.next() {
+                Some(Op::LongPos("target-file", path)) =>
+                    for (target, packages) in read_targets(path)? {
+                        if let ContainerType::Symbolic = cache.get_instance(target)?.metadata().container_type() {
+                            err!(ErrorKind::Message("Symbolic containers cannot be transacted."))?;
+                        }
+
+                        targets.insert(target);
+                        queue.entry(target).or_default().extend(packages);
+                    },
+                Some(_) => args.invalid_operand()?,
+                None => err!(ErrorKind::Message("Target file not specified."))?,
+            },
+            Op::Long("tag") => match args.next() {
+                Some(Op::LongPos("tag", tag)) => {
+                    let containers = cache.tagged(tag);
+
+                    if containers.is_empty() {
+                        err!(ErrorKind::TagNotFound(tag.into()))?;
+                    }
+
+                    for target in containers {
+                        if !tagged.contains(&target) {
+                            tagged.push(target);
+                        }
+
+                        targets.insert(target);
+                    }
+
+                    current_target = None;
+                }
+                Some(_) => args.invalid_operand()?,
+                None => err!(ErrorKind::Message("Tag not specified."))?,
+            },
+            Op::Long("dbpath") => match args.next() {
+                Some(Op::LongPos("dbpath", path)) => dbpath = Some(path),
+                Some(_) => args.invalid_operand()?,
+                None => err!(ErrorKind::Message("Database path not specified."))?,
+            },
             Op::Short('l') | Op::Long("lazy-load") => flags |= TransactionFlags::LAZY_LOAD_DB,
             Op::Short('o') | Op::Long("target-only") => flags |= TransactionFlags::TARGET_ONLY,
             Op::Short('f') | Op::Long("filesystem") => flags |= TransactionFlags::FILESYSTEM_SYNC,
             Op::Short('p') | Op::Long("preview") => flags |= TransactionFlags::PREVIEW,
+            Op::Short('v') | Op::Long("verbose") => verbose += 1,
             Op::Short('b') | Op::Long("base") => container_type = Some(ContainerType::Base),
             Op::Short('s') | Op::Long("slice") => container_type = Some(ContainerType::Slice),
             Op::Short('a') | Op::Long("aggregate") => container_type = Some(ContainerType::Aggregate),
@@ -183,6 +320,17 @@ fn engage_aggregator<'a>(
                 container_type = None;
                 create = true;
             }
+            Op::Long("copy-config-from") => match args.next() {
+                Some(Op::LongPos("copy-config-from", source)) => match current_target {
+                    Some(target) if create_targets.contains_key(target) => {
+                        cache.get_instance(source)?;
+                        copied.insert(target, source);
+                    }
+                    _ => err!(ErrorKind::Message("Option '--copy-config-from' is only applicable to container creation."))?,
+                },
+                Some(_) => args.invalid_operand()?,
+                None => err!(ErrorKind::Message("Source container not specified."))?,
+            },
             Op::Short('d') | Op::Long("dep") => match args.next() {
                 Some(arg) => match arg {
                     Op::ShortPos('d', dep) | Op::LongPos("dep", dep) => match container_type {
@@ -214,7 +362,10 @@ fn engage_aggregator<'a>(
                         targets.insert(target);
 
                         if let (true, Some(container_type)) = (create, container_type) {
-                            if let ContainerType::Base = container_type {
+                            if let (ContainerType::Base, true) = (container_type, minimal) {
+                                queue.insert(target, MINIMAL_PACKAGES.to_vec());
+                                minimal_targets.insert(target);
+                            } else if let ContainerType::Base = container_type {
                                 queue.insert(target, vec!["base"]);
                             }
 
@@ -237,30 +388,98 @@ fn engage_aggregator<'a>(
                     } else {
                         queue.insert(current_target, vec![package]);
                     }
+                } else {
+                    for target in &tagged {
+                        queue.entry(target).or_default().push(package);
+                    }
                 },
             _ => args.invalid_operand()?,
         }
     }
 
+    verbosity::set(verbose);
+
+    if minimal && minimal_targets.is_empty() {
+        err!(ErrorKind::Message("Option '--minimal' is only applicable to the creation of base containers."))?;
+    }
+
+    if flags.contains(TransactionFlags::SYSUPGRADE_ONLY) {
+        if !matches!(action_type, TransactionType::Upgrade(true, ..)) {
+            err!(ErrorKind::Message("Option '--sysupgrade-only' requires '-u/--upgrade'."))?;
+        } else if !create_targets.is_empty() || init {
+            err!(ErrorKind::Message("Option '--sysupgrade-only' is not applicable to container creation."))?;
+        }
+    }
+
+    if force_relink {
+        if !flags.contains(TransactionFlags::FILESYSTEM_SYNC) {
+            err!(ErrorKind::Message("Option '--force-relink' requires '-f/--filesystem'."))?;
+        } else if targets.is_empty() {
+            err!(TargetUnspecified)?;
+        }
+
+        for target in &targets {
+            if !matches!(cache.get_instance(target)?.metadata().container_type(), ContainerType::Aggregate) {
+                err!(ErrorKind::Message("Only aggregate containers can be relinked."))?;
+            }
+        }
+    }
+
     if flags.contains(TransactionFlags::LAZY_LOAD_DB) {
         print_warning("Database lazy-loading triggered by `-l/--lazy-load`; this feature is experimental.");
         print_warning("In future, manual intervention may be required for missing dependencies.");
         print_warning("See `--help sync` or the pacwrap(1) man page for further information.");
     }
 
+    for package in &treated {
+        print_warning(&format!("Upstream package '{package}' treated as resident by `--treat-resident`."));
+    }
+
+    if cachedir.is_some() || dbpath.is_some() {
+        override_paths(cachedir, dbpath)?;
+
+        if let Some(path) = cachedir {
+            print_warning(&format!("Package cache directory overridden with '{path}' by `--cachedir`."));
+        }
+
+        if let Some(path) = dbpath {
+            print_warning(&format!("Package database paths overridden beneath '{path}' by `--dbpath`."));
+            print_warning("The database at this path will not reflect the contents of the container's filesystem.");
+        }
+    }
+
+    if refresh_keys {
+        lock.assert()?;
+        refresh_trust()?;
+
+        if let (TransactionType::Upgrade(false, false, _), true) = (action_type, targets.is_empty() && !init) {
+            return Ok(());
+        }
+    }
+
     if !create_targets.is_empty() || init {
         if flags.intersects(TransactionFlags::PREVIEW) {
             err!(ErrorKind::Message("Container creation cannot be previewed."))?;
         }
 
         flags = flags | TransactionFlags::CREATE | TransactionFlags::FORCE_DATABASE;
+        lock.assert()?;
         instantiate_trust()?;
-        instantiate(cache, lock, log, &action_type, create_targets)?;
+        instantiate(cache, log, &action_type, create_targets, minimal_targets, copied, profile)?;
     }
 
+    let relink = match force_relink {
+        true => targets.iter().copied().collect(),
+        false => Vec::new(),
+    };
+
     TransactionAggregator::new(cache, log, action_type)
         .assert_lock(lock)?
+        .relink(relink)
         .target(acquire_targets(cache, &flags, targets)?)
+        .ignore(ignored)
+        .after(after)
+        .treat_resident(treated)
         .queue(queue)
         .flag(flags)
         .progress()
@@ -31,11 +31,23 @@ use pacwrap_core::{
     Result,
 };
 
+use crate::query;
+
 pub mod delete;
 pub mod desktop;
 pub mod list;
+pub mod targets;
 
+mod backup;
+mod check;
+mod compact;
+mod completion;
+mod dedup;
+mod doctor;
 mod edit;
+mod gc;
+mod graph;
+mod schema;
 mod symlink;
 
 const GIO: &str = "gio";
@@ -44,11 +56,25 @@ pub fn engage_utility(args: &mut Arguments) -> Result<()> {
     match args.next().unwrap_or_default() {
         Operand::Short('v') | Operand::Long("view") | Operand::Value("view") => edit::edit(args, false),
         Operand::Short('e') | Operand::Long("edit") | Operand::Value("edit") => edit::edit(args, true),
+        Operand::Long("cat") | Operand::Value("cat") => edit::cat(args),
         Operand::Short('r') | Operand::Long("remove") | Operand::Value("remove") => delete::remove_containers(args),
         Operand::Short('l') | Operand::Long("list") | Operand::Value("list") => list::list_containers(args),
         Operand::Short('d') | Operand::Long("desktop") | Operand::Value("desktop") => desktop::file(args),
         Operand::Short('s') | Operand::Long("symlink") | Operand::Value("symlink") => symlink::link(args),
         Operand::Short('o') | Operand::Long("open") | Operand::Value("open") => open(args),
+        Operand::Short('g') | Operand::Long("graph") | Operand::Value("graph") => graph::graph(args),
+        Operand::Long("doctor") | Operand::Value("doctor") => doctor::diagnose(args),
+        Operand::Long("gc") | Operand::Value("gc") => gc::collect(args),
+        Operand::Long("verify-dedup") | Operand::Value("verify-dedup") => dedup::verify(args),
+        Operand::Long("compact-state") | Operand::Value("compact-state") => compact::compact(args),
+        Operand::Long("check-config") | Operand::Value("check-config") => check::check_config(args),
+        Operand::Long("find") | Operand::Value("find") => query::find(args),
+        Operand::Long("print-config") | Operand::Value("print-config") => check::print_config(args),
+        Operand::Long("dump-config-schema") | Operand::Value("dump-config-schema") => check::dump_schema(args),
+        Operand::Long("apply-schema") | Operand::Value("apply-schema") => schema::apply_schema(args),
+        Operand::Long("backup") | Operand::Value("backup") => backup::backup(args),
+        Operand::Long("restore") | Operand::Value("restore") => backup::restore(args),
+        Operand::Long("shell-completion-cache") | Operand::Value("shell-completion-cache") => completion::completion_cache(args),
         _ => args.invalid_operand(),
     }
 }
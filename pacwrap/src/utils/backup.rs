@@ -0,0 +1,228 @@
+/*
+ * pacwrap
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs::{canonicalize, remove_file, symlink_metadata, write, File},
+    io::Read,
+    path::{Component, Path},
+};
+
+use tar::{Archive, Builder, Entries, Header};
+use zstd::{Decoder, Encoder};
+
+use pacwrap_core::{
+    config::{cache::populate, ContainerType::Symbolic, ContainerVariables},
+    constants::{ARROW_GREEN, BAR_GREEN, BOLD, CACHE_DIR, RESET},
+    err,
+    impl_error,
+    lock::Lock,
+    log::{Level::Info, Logger},
+    sync::filesystem::FilesystemSync,
+    utils::{arguments::Operand, Arguments},
+    Error,
+    ErrorGeneric,
+    ErrorKind,
+    ErrorTrait,
+    Result,
+};
+
+use crate::compose::compose_config;
+
+const INSTANCE_ENTRY: &str = "instance";
+const CONFIG_ENTRY: &str = "container.yml";
+
+#[derive(Debug)]
+enum BackupError {
+    MalformedArchive(String, &'static str),
+    UnconfinedEntry(String, String),
+}
+
+impl_error!(BackupError);
+
+impl Display for BackupError {
+    fn fmt(&self, fmter: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MalformedArchive(path, entry) =>
+                write!(fmter, "'{path}': Malformed backup archive: Entry '{}{entry}{}' is absent.", *BOLD, *RESET),
+            Self::UnconfinedEntry(path, entry) =>
+                write!(fmter, "'{path}': Entry '{}{entry}{}' resolves outside of the container.", *BOLD, *RESET),
+        }
+    }
+}
+
+pub fn backup(args: &mut Arguments) -> Result<()> {
+    let mut values = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg {
+            Operand::Long("backup") | Operand::Value("backup") => continue,
+            Operand::ShortPos('t', val) | Operand::LongPos("target", val) | Operand::Value(val) => values.push(val),
+            Operand::Short('t') | Operand::Long("target") => continue,
+            _ => args.invalid_operand()?,
+        }
+    }
+
+    let (instance, path) = match values[..] {
+        [instance, path] => (instance, path),
+        [_] => err!(ErrorKind::Message("Backup file not specified."))?,
+        _ => err!(ErrorKind::Message("Backup requires a container and a file."))?,
+    };
+    let cache = populate()?;
+    let handle = cache.get_instance(instance)?;
+
+    if let Symbolic = handle.metadata().container_type() {
+        err!(ErrorKind::Message("Symbolic containers cannot be backed up."))?;
+    }
+
+    let lock = Lock::new().lock()?;
+    let mut logger = Logger::new("pacwrap-utils").init()?;
+    let mut linker = FilesystemSync::new(&cache);
+    let (root, home) = (handle.vars().root(), handle.vars().home());
+
+    println!("{} {}Backing up container {instance}...{}", *BAR_GREEN, *BOLD, *RESET);
+
+    let files = linker.local_files(handle)?;
+    let output = File::create(path).prepend_io(|| path.into())?;
+    let mut builder = Builder::new(Encoder::new(output, 3).prepend_io(|| path.into())?.auto_finish());
+    let mut header = Header::new_gnu();
+
+    header.set_size(instance.len() as u64);
+    header.set_mode(0o644);
+    builder.follow_symlinks(false);
+    builder.append_data(&mut header, INSTANCE_ENTRY, instance.as_bytes()).prepend_io(|| path.into())?;
+    builder.append_path_with_name(handle.vars().config_path(), CONFIG_ENTRY).prepend_io(|| path.into())?;
+
+    for file in &files {
+        lock.assert()?;
+        builder.append_path_with_name(format!("{root}{file}"), format!("root{file}")).prepend_io(|| format!("{root}{file}"))?;
+    }
+
+    if Path::new(home).exists() {
+        builder.append_dir_all("home", home).prepend_io(|| home.into())?;
+    }
+
+    builder.finish().prepend_io(|| path.into())?;
+    linker.release();
+    logger.log(Info, &format!("Backed up container {instance} to '{path}': {} local files", files.len()))?;
+    println!("{} Backed up {}{}{} local files of {instance} to '{path}'.", *ARROW_GREEN, *BOLD, files.len(), *RESET);
+    lock.unlock()
+}
+
+pub fn restore(args: &mut Arguments) -> Result<()> {
+    let mut path = None;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            Operand::Long("restore") | Operand::Value("restore") => continue,
+            Operand::Value(val) => path = Some(val),
+            _ => args.invalid_operand()?,
+        }
+    }
+
+    let path = match path {
+        Some(path) => path,
+        None => err!(ErrorKind::Message("Backup file not specified."))?,
+    };
+    let file = File::open(path).prepend_io(|| path.into())?;
+    let mut archive = Archive::new(Decoder::new(file).prepend_io(|| path.into())?);
+    let mut entries = archive.entries().prepend_io(|| path.into())?;
+    let instance = read_entry(&mut entries, path, INSTANCE_ENTRY)?;
+    let config = read_entry(&mut entries, path, CONFIG_ENTRY)?;
+    let config_path = &format!("{}/{instance}.restore.yml", *CACHE_DIR);
+    let vars = ContainerVariables::new(&instance);
+    let (root, home) = (vars.root(), vars.home());
+    let lock = Lock::new().lock()?;
+
+    write(config_path, config).prepend_io(|| config_path.into())?;
+
+    let result = compose_config(&instance, config_path, &lock);
+
+    remove_file(config_path).prepend_io(|| config_path.into())?;
+    result?;
+    println!("{} {}Restoring local files of {instance}...{}", *BAR_GREEN, *BOLD, *RESET);
+
+    let mut restored = 0;
+
+    for entry in entries {
+        let mut entry = entry.prepend_io(|| path.into())?;
+        let entry_path = entry.path().prepend_io(|| path.into())?.to_path_buf();
+        let (base, relative) = match (entry_path.strip_prefix("root"), entry_path.strip_prefix("home")) {
+            (Ok(relative), _) => (root, relative),
+            (_, Ok(relative)) => (home, relative),
+            _ => continue,
+        };
+
+        if relative.as_os_str().is_empty()
+            || relative.components().any(|a| !matches!(a, Component::Normal(_)))
+            || entry.header().entry_type().is_hard_link()
+        {
+            continue;
+        }
+
+        let dest = Path::new(base).join(relative);
+
+        if !confined(Path::new(base), &dest) {
+            err!(BackupError::UnconfinedEntry(path.into(), entry_path.to_string_lossy().into()))?
+        }
+
+        //Replace, rather than write through, files linked from upstream containers.
+        if symlink_metadata(&dest).is_ok_and(|a| !a.is_dir()) {
+            remove_file(&dest).prepend_io(|| dest.to_string_lossy().into())?;
+        }
+
+        lock.assert()?;
+        entry.set_preserve_permissions(true);
+        entry.unpack(&dest).prepend_io(|| dest.to_string_lossy().into())?;
+        restored += 1;
+    }
+
+    Logger::new("pacwrap-utils").init()?.log(Info, &format!("Restored container {instance} from '{path}'"))?;
+    println!("{} Restored {}{restored}{} local files of {instance}.", *ARROW_GREEN, *BOLD, *RESET);
+    lock.unlock()
+}
+
+//Entries are confined to their destination, lest symlinks, whether planted by a crafted archive or resident
+//within the container, be written through to the host.
+fn confined(base: &Path, dest: &Path) -> bool {
+    let base = match canonicalize(base) {
+        Ok(base) => base,
+        Err(_) => return false,
+    };
+
+    dest.ancestors()
+        .skip(1)
+        .find_map(|path| canonicalize(path).ok())
+        .is_some_and(|path| path.starts_with(base))
+}
+
+fn read_entry<R: Read>(entries: &mut Entries<R>, path: &str, name: &'static str) -> Result<String> {
+    let mut entry = match entries.next() {
+        Some(entry) => entry.prepend_io(|| path.into())?,
+        None => err!(BackupError::MalformedArchive(path.into(), name))?,
+    };
+    let mut contents = String::new();
+
+    if entry.path().prepend_io(|| path.into())?.as_os_str() != name {
+        err!(BackupError::MalformedArchive(path.into(), name))?
+    }
+
+    entry.read_to_string(&mut contents).prepend_io(|| path.into())?;
+    Ok(contents)
+}
@@ -0,0 +1,117 @@
+/*
+ * pacwrap
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use pacwrap_core::{
+    config::{
+        self,
+        effective::{container_config, global_config},
+        schema::{container_schema, global_schema},
+        validate::validate,
+    },
+    constants::{ARROW_GREEN, ARROW_RED, BAR_GREEN, BOLD, RESET},
+    err,
+    impl_error,
+    utils::{arguments::Operand, Arguments},
+    Error,
+    ErrorTrait,
+    Result,
+};
+
+#[derive(Debug)]
+enum CheckError {
+    ConfigurationInvalid(usize),
+}
+
+impl_error!(CheckError);
+
+impl Display for CheckError {
+    fn fmt(&self, fmter: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::ConfigurationInvalid(count) => write!(
+                fmter,
+                "{}{count}{} configuration problem{} found.",
+                *BOLD,
+                *RESET,
+                if *count > 1 { "s" } else { "" }
+            ),
+        }
+    }
+}
+
+pub fn check_config(args: &mut Arguments) -> Result<()> {
+    while let Some(arg) = args.next() {
+        match arg {
+            Operand::Long("check-config") | Operand::Value("check-config") => continue,
+            _ => args.invalid_operand()?,
+        }
+    }
+
+    println!("{} {}Validating configuration...{}", *BAR_GREEN, *BOLD, *RESET);
+
+    let problems = validate();
+
+    for problem in problems.iter() {
+        println!("{} {problem}", *ARROW_RED);
+    }
+
+    if !problems.is_empty() {
+        err!(CheckError::ConfigurationInvalid(problems.len()))?
+    }
+
+    println!("{} Configuration is valid.", *ARROW_GREEN);
+    Ok(())
+}
+
+pub fn dump_schema(args: &mut Arguments) -> Result<()> {
+    let mut container = false;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            Operand::Long("dump-config-schema") | Operand::Value("dump-config-schema") => continue,
+            Operand::LongPos("dump-config-schema", "global") => container = false,
+            Operand::LongPos("dump-config-schema", "container") => container = true,
+            _ => args.invalid_operand()?,
+        }
+    }
+
+    println!("{:#}", if container { container_schema() } else { global_schema() });
+    Ok(())
+}
+
+pub fn print_config(args: &mut Arguments) -> Result<()> {
+    let mut target = None;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            Operand::Long("print-config") | Operand::Value("print-config") | Operand::Short('t') | Operand::Long("target") =>
+                continue,
+            Operand::ShortPos('t', ins) | Operand::LongPos("target", ins) | Operand::Value(ins) => target = Some(ins),
+            _ => args.invalid_operand()?,
+        }
+    }
+
+    match target {
+        Some(ins) => print!("{}", container_config(&config::provide_handle(ins)?)?),
+        None => print!("{}", global_config()?),
+    }
+
+    Ok(())
+}
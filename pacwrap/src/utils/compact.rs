@@ -0,0 +1,91 @@
+/*
+ * pacwrap
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use simplebyteunit::simplebyteunit::*;
+
+use pacwrap_core::{
+    config::cache::populate,
+    constants::{ARROW_GREEN, BAR_GREEN, BOLD, RESET},
+    lock::Lock,
+    log::{Level::Info, Logger},
+    sync::filesystem::compact_state,
+    utils::{arguments::Operand, Arguments},
+    Result,
+};
+
+pub fn compact(args: &mut Arguments) -> Result<()> {
+    let mut targets = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg {
+            Operand::Long("compact-state") | Operand::Value("compact-state") => continue,
+            Operand::ShortPos('t', val) | Operand::LongPos("target", val) | Operand::Value(val) => targets.push(val),
+            Operand::Short('t') | Operand::Long("target") => continue,
+            _ => args.invalid_operand()?,
+        }
+    }
+
+    let cache = populate()?;
+    let containers = match targets.is_empty() {
+        true => cache.registered(),
+        false => {
+            for target in &targets {
+                cache.get_instance(target)?;
+            }
+
+            targets
+        }
+    };
+    let mut logger = Logger::new("pacwrap-utils").init()?;
+    let lock = Lock::new().lock()?;
+    let mut saved = 0;
+
+    println!("{} {}Compacting filesystem states...{}", *BAR_GREEN, *BOLD, *RESET);
+
+    for container in containers {
+        lock.assert()?;
+
+        let downstream: Vec<&str> = cache
+            .downstream(container)
+            .iter()
+            .filter_map(|ins| cache.get_instance_option(ins))
+            .map(|ins| ins.vars().root())
+            .collect();
+
+        if let Some((size, compacted)) = compact_state(container, &downstream)? {
+            let (size, compacted) = (size as i64, compacted as i64);
+            let difference = size - compacted;
+
+            saved += difference;
+            println!(
+                "{} {}{container}{}: {} -> {} ({} saved)",
+                *ARROW_GREEN,
+                *BOLD,
+                *RESET,
+                size.to_byteunit(IEC),
+                compacted.to_byteunit(IEC),
+                difference.to_byteunit(IEC)
+            );
+            logger.log(Info, &format!("Compacted filesystem state of {container}: {difference} bytes saved"))?;
+        }
+    }
+
+    println!("{} Saved {} in total.", *ARROW_GREEN, saved.to_byteunit(IEC));
+    lock.unlock()
+}
@@ -0,0 +1,56 @@
+/*
+ * pacwrap
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fs::remove_dir_all;
+
+use pacwrap_core::{
+    config::{cache::populate, completion},
+    constants::{ARROW_GREEN, BAR_GREEN, BOLD, RESET},
+    utils::{arguments::Operand, Arguments},
+    ErrorGeneric,
+    Result,
+};
+
+pub fn completion_cache(args: &mut Arguments) -> Result<()> {
+    let mut clear = false;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            Operand::Long("shell-completion-cache") | Operand::Value("shell-completion-cache") => continue,
+            Operand::Long("clear") => clear = true,
+            _ => args.invalid_operand()?,
+        }
+    }
+
+    let directory = completion::directory();
+
+    if clear {
+        if completion::enabled() {
+            remove_dir_all(&directory).prepend_io(|| directory.clone())?;
+        }
+
+        println!("{} Shell completion cache at '{directory}' cleared.", *ARROW_GREEN);
+        return Ok(());
+    }
+
+    println!("{} {}Precomputing shell completion cache...{}", *BAR_GREEN, *BOLD, *RESET);
+    completion::refresh(&populate()?, true)?;
+    println!("{} Shell completion cache written to '{directory}'.", *ARROW_GREEN);
+    Ok(())
+}
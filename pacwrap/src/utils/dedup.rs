@@ -0,0 +1,100 @@
+/*
+ * pacwrap
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use pacwrap_core::{
+    config::{cache::populate, ContainerType::Aggregate},
+    constants::{ARROW_CYAN, ARROW_GREEN, ARROW_RED, BAR_GREEN, BOLD, RESET},
+    err,
+    lock::Lock,
+    sync::filesystem::FilesystemSync,
+    utils::{arguments::Operand, Arguments},
+    Error,
+    ErrorKind,
+    Result,
+};
+
+pub fn verify(args: &mut Arguments) -> Result<()> {
+    let mut targets = Vec::new();
+    let mut repair = false;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            Operand::Long("verify-dedup") | Operand::Value("verify-dedup") => continue,
+            Operand::Long("repair") => repair = true,
+            Operand::ShortPos('t', val) | Operand::LongPos("target", val) | Operand::Value(val) => targets.push(val),
+            Operand::Short('t') | Operand::Long("target") => continue,
+            _ => args.invalid_operand()?,
+        }
+    }
+
+    let cache = populate()?;
+    let containers = match targets.is_empty() {
+        true => cache.filter_handle(vec![Aggregate]),
+        false => cache.filter_target_handle(&targets, vec![Aggregate]),
+    };
+
+    if containers.len() != targets.len() && !targets.is_empty() {
+        err!(ErrorKind::Message("Only aggregate containers can be verified."))?
+    }
+
+    let lock = match repair {
+        true => Some(Lock::new().lock()?),
+        false => None,
+    };
+    let mut linker = FilesystemSync::new(&cache).assert_lock(lock.as_ref());
+    let (mut divergent, mut unlinked, mut relinked) = (0, 0, 0);
+
+    println!("{} {}Verifying deduplicated files...{}", *BAR_GREEN, *BOLD, *RESET);
+
+    for container in &containers {
+        for file in linker.verify(container, repair)? {
+            let status = match file.identical() {
+                true => "Unlinked from upstream",
+                false => "Diverged from upstream",
+            };
+
+            match file.relinked() {
+                true => println!("{} '{}': {status}; re-linked.", *ARROW_CYAN, file.path()),
+                false => println!("{} '{}': {status}.", *ARROW_RED, file.path()),
+            }
+
+            match file.identical() {
+                true => unlinked += 1,
+                false => divergent += 1,
+            }
+
+            if file.relinked() {
+                relinked += 1;
+            }
+        }
+    }
+
+    linker.release();
+    println!(
+        "{} Verified {} container{}: {divergent} divergent, {unlinked} unlinked, {relinked} re-linked.",
+        *ARROW_GREEN,
+        containers.len(),
+        if containers.len() == 1 { "" } else { "s" }
+    );
+
+    match lock {
+        Some(lock) => lock.unlock(),
+        None => Ok(()),
+    }
+}
@@ -101,10 +101,9 @@ pub fn remove_containers(args: &mut Arguments) -> Result<()> {
     lock.unlock()
 }
 
-pub fn delete_roots(cache: &ContainerCache<'_>, lock: &Lock, logger: &mut Logger, targets: &[&str], force: bool) -> Result<()> {
+pub fn check_processes(cache: &ContainerCache<'_>, targets: &[&str], force: bool) -> Result<()> {
     let process = process::list(cache)?;
     let processes = process.filter_by_target(targets);
-    let containers = cache.filter_target_handle(targets, vec![]);
 
     if !processes.is_empty() && !force {
         for process in processes {
@@ -112,6 +111,14 @@ pub fn delete_roots(cache: &ContainerCache<'_>, lock: &Lock, logger: &mut Logger
         }
     }
 
+    Ok(())
+}
+
+pub fn delete_roots(cache: &ContainerCache<'_>, lock: &Lock, logger: &mut Logger, targets: &[&str], force: bool) -> Result<()> {
+    let containers = cache.filter_target_handle(targets, vec![]);
+
+    check_processes(cache, targets, force)?;
+
     for container in containers {
         let root = container.vars().root();
         let instance = container.vars().instance();
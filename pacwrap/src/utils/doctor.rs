@@ -0,0 +1,208 @@
+/*
+ * pacwrap
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+    env::var,
+    fmt::{Display, Formatter, Result as FmtResult},
+    path::Path,
+};
+
+use nix::unistd::{access, AccessFlags};
+use pacwrap_core::{
+    constants::{
+        ARROW_CYAN,
+        ARROW_GREEN,
+        ARROW_RED,
+        BAR_GREEN,
+        BOLD,
+        BOLD_GREEN,
+        BOLD_RED,
+        BOLD_YELLOW,
+        CACHE_DIR,
+        CONFIG_DIR,
+        DATA_DIR,
+        DBUS_PROXY_EXECUTABLE,
+        DEFAULT_PATH,
+        RESET,
+    },
+    err,
+    exec::{bwrap_executable, DIST_IMG},
+    impl_error,
+    utils::{arguments::Operand, unprivileged_userns, Arguments},
+    Error,
+    ErrorTrait,
+    Result,
+};
+
+#[derive(Debug)]
+enum DoctorError {
+    RequirementsUnsatisfied(usize),
+}
+
+impl_error!(DoctorError);
+
+impl Display for DoctorError {
+    fn fmt(&self, fmter: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::RequirementsUnsatisfied(count) => write!(
+                fmter,
+                "{}{count}{} hard requirement{} unsatisfied.",
+                *BOLD,
+                *RESET,
+                if *count > 1 { "s are" } else { " is" }
+            ),
+        }
+    }
+}
+
+enum Status {
+    Pass,
+    Warn(&'static str),
+    Fail(&'static str),
+}
+
+struct Diagnostic {
+    description: String,
+    status: Status,
+}
+
+impl Diagnostic {
+    fn new(description: impl Into<String>, status: Status) -> Self {
+        Self {
+            description: description.into(),
+            status,
+        }
+    }
+
+    fn failed(&self) -> bool {
+        matches!(self.status, Status::Fail(_))
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, fmter: &mut Formatter<'_>) -> FmtResult {
+        match self.status {
+            Status::Pass => write!(fmter, "{} {}pass{} {}", *ARROW_GREEN, *BOLD_GREEN, *RESET, self.description),
+            Status::Warn(hint) =>
+                write!(fmter, "{} {}warn{} {}\n       {hint}", *ARROW_CYAN, *BOLD_YELLOW, *RESET, self.description),
+            Status::Fail(hint) => write!(fmter, "{} {}fail{} {}\n       {hint}", *ARROW_RED, *BOLD_RED, *RESET, self.description),
+        }
+    }
+}
+
+pub fn diagnose(args: &mut Arguments) -> Result<()> {
+    while let Some(arg) = args.next() {
+        match arg {
+            Operand::Long("doctor") | Operand::Value("doctor") => continue,
+            _ => args.invalid_operand()?,
+        }
+    }
+
+    println!("{} {}Diagnosing environment...{}", *BAR_GREEN, *BOLD, *RESET);
+
+    let diagnostics = vec![
+        bwrap(),
+        executable(DBUS_PROXY_EXECUTABLE, Status::Warn("Install xdg-dbus-proxy to enable dbus modules in containers.")),
+        fakechroot(),
+        userns(),
+        keyrings(),
+        directory("Cache", *CACHE_DIR),
+        directory("Configuration", *CONFIG_DIR),
+        directory("Data", *DATA_DIR),
+    ];
+    let failures = diagnostics.iter().filter(|a| a.failed()).count();
+
+    for diagnostic in diagnostics {
+        println!("{}", diagnostic);
+    }
+
+    if failures > 0 {
+        err!(DoctorError::RequirementsUnsatisfied(failures))?
+    }
+
+    Ok(())
+}
+
+fn executable(exec: &'static str, status: Status) -> Diagnostic {
+    let path = var("PATH").unwrap_or(DEFAULT_PATH.into());
+
+    match path.split(':').any(|dir| Path::new(&format!("{dir}/{exec}")).is_file()) {
+        true => Diagnostic::new(format!("Executable '{exec}' located in $PATH."), Status::Pass),
+        false => Diagnostic::new(format!("Executable '{exec}' not found in $PATH."), status),
+    }
+}
+
+fn bwrap() -> Diagnostic {
+    match bwrap_executable() {
+        Ok(exec) if exec.starts_with('/') => Diagnostic::new(format!("Executable '{exec}' located."), Status::Pass),
+        Ok(exec) => executable(exec, Status::Fail("Install the bubblewrap package with your package manager.")),
+        Err(err) => Diagnostic::new(
+            format!("{err}"),
+            Status::Fail("Ensure $PACWRAP_BWRAP or 'bwrap_executable' specifies an absolute path to an executable."),
+        ),
+    }
+}
+
+fn fakechroot() -> Diagnostic {
+    let path = format!("{}/lib/libfakechroot.so", *DIST_IMG);
+
+    match Path::new(&path).exists() {
+        true => Diagnostic::new(format!("Library '{path}' is present."), Status::Pass),
+        false => Diagnostic::new(
+            format!("Library '{path}' is absent."),
+            Status::Fail("Reinstall pacwrap, or ensure the runtime is installed to the runtime directory."),
+        ),
+    }
+}
+
+fn userns() -> Diagnostic {
+    match unprivileged_userns() {
+        true => Diagnostic::new("Unprivileged user namespaces are enabled.", Status::Pass),
+        false => Diagnostic::new(
+            "Unprivileged user namespaces are disabled.",
+            Status::Fail("Enable with `sysctl kernel.unprivileged_userns_clone=1` or `sysctl user.max_user_namespaces=28633`."),
+        ),
+    }
+}
+
+fn keyrings() -> Diagnostic {
+    match Path::new("/usr/share/pacman/keyrings").exists() {
+        true => Diagnostic::new("Package keyrings located in '/usr/share/pacman/keyrings'.", Status::Pass),
+        false => Diagnostic::new(
+            "Package keyrings not found in '/usr/share/pacman/keyrings'.",
+            Status::Fail("Install the archlinux-keyring package with your package manager."),
+        ),
+    }
+}
+
+fn directory(name: &str, dir: &str) -> Diagnostic {
+    if !Path::new(dir).exists() {
+        Diagnostic::new(
+            format!("{name} directory '{dir}' is absent."),
+            Status::Warn("This directory is created upon the first invocation of pacwrap."),
+        )
+    } else if access(dir, AccessFlags::W_OK).is_err() {
+        Diagnostic::new(
+            format!("{name} directory '{dir}' is not writable."),
+            Status::Fail("Ensure the directory is owned by and writable to the current user."),
+        )
+    } else {
+        Diagnostic::new(format!("{name} directory '{dir}' is writable."), Status::Pass)
+    }
+}
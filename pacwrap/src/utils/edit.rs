@@ -18,23 +18,51 @@
  */
 
 use std::{
+    env::var,
     fmt::{Display, Formatter, Result as FmtResult},
     fs::{copy, remove_file, File},
-    io::copy as copy_io,
+    io::{copy as copy_io, stdout},
+    path::Path,
     process::Command,
 };
 
 use pacwrap_core::{
-    constants::{ARROW_CYAN, ARROW_GREEN, CONFIG_DIR, DATA_DIR, EDITOR, HOME},
+    config::global,
+    constants::{ARROW_CYAN, ARROW_GREEN, CONFIG_DIR, DATA_DIR, DEFAULT_PATH, HOME},
+    err,
     exec::utils::handle_process,
+    impl_error,
     lock::Lock,
     utils::{arguments::Operand, Arguments},
+    Error,
     ErrorGeneric,
+    ErrorTrait,
     Result,
 };
 use rand::distributions::{Alphanumeric, DistString};
 use sha2::{Digest, Sha256};
 
+const EDITORS: [&str; 3] = ["nano", "vim", "vi"];
+
+#[derive(Debug)]
+enum EditError {
+    EditorNotFound,
+}
+
+impl_error!(EditError);
+
+impl Display for EditError {
+    fn fmt(&self, fmter: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::EditorNotFound => write!(
+                fmter,
+                "No editor available: $VISUAL, $EDITOR, and the 'editor' option are unspecified, and none of {} are in $PATH.",
+                EDITORS.join(", ")
+            ),
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 enum FileType<'a> {
     ContainerConfig(&'a str),
@@ -81,28 +109,7 @@ impl Display for FileType<'_> {
 }
 
 pub fn edit(args: &mut Arguments, edit: bool) -> Result<()> {
-    let mut file = None;
-
-    while let Some(arg) = args.next() {
-        file = Some(match arg {
-            Operand::Short('d') | Operand::Long("desktop") => continue,
-            Operand::Short('l') | Operand::Long("log") | Operand::Value("log") => FileType::LogFile,
-            Operand::Short('r') | Operand::Long("repo") | Operand::Value("repo") => FileType::Repo,
-            Operand::Short('c') | Operand::Long("config") | Operand::Value("config") => FileType::Config,
-            Operand::ShortPos('d', val) | Operand::LongPos("desktop", val) => FileType::DesktopFile(val),
-            Operand::ShortPos('c', val) | Operand::LongPos("config", val) => FileType::ContainerConfig(val),
-            Operand::LongPos("view", arg)
-            | Operand::LongPos("edit", arg)
-            | Operand::ShortPos('e', arg)
-            | Operand::ShortPos('v', arg) => match FileType::from(arg) {
-                Some(f) => f,
-                None => return args.invalid_operand(),
-            },
-            _ => return args.invalid_operand(),
-        });
-    }
-
-    let (file, temp, lock, edit) = &match file {
+    let (file, temp, lock, edit) = &match file_type(args)? {
         Some(file) => {
             let (edit, ext) = (file.can_edit(edit), file.ext());
             let prs = Alphanumeric.sample_string(&mut rand::thread_rng(), 10);
@@ -127,9 +134,50 @@ pub fn edit(args: &mut Arguments, edit: bool) -> Result<()> {
     result
 }
 
+pub fn cat(args: &mut Arguments) -> Result<()> {
+    let file = match file_type(args)? {
+        Some(file) => file.to_string(),
+        None => return args.invalid_operand(),
+    };
+    let mut handle = File::open(&file).prepend_io(|| file.clone())?;
+
+    copy_io(&mut handle, &mut stdout()).prepend_io(|| file.clone())?;
+    Ok(())
+}
+
+fn file_type<'a>(args: &mut Arguments<'a>) -> Result<Option<FileType<'a>>> {
+    let mut file = None;
+
+    while let Some(arg) = args.next() {
+        file = Some(match arg {
+            Operand::Short('d') | Operand::Long("desktop") => continue,
+            Operand::Short('l') | Operand::Long("log") | Operand::Value("log") => FileType::LogFile,
+            Operand::Short('r') | Operand::Long("repo") | Operand::Value("repo") => FileType::Repo,
+            Operand::Short('c') | Operand::Long("config") | Operand::Value("config") => FileType::Config,
+            Operand::ShortPos('d', val) | Operand::LongPos("desktop", val) => FileType::DesktopFile(val),
+            Operand::ShortPos('c', val) | Operand::LongPos("config", val) => FileType::ContainerConfig(val),
+            Operand::LongPos("view", arg)
+            | Operand::LongPos("edit", arg)
+            | Operand::LongPos("cat", arg)
+            | Operand::ShortPos('e', arg)
+            | Operand::ShortPos('v', arg) => match FileType::from(arg) {
+                Some(f) => f,
+                None => return args.invalid_operand().map(|_| None),
+            },
+            _ => return args.invalid_operand().map(|_| None),
+        });
+    }
+
+    Ok(file)
+}
+
 fn edit_file(file: &str, temporary_file: &str, lock: Option<&Lock>, edit: bool) -> Result<()> {
+    let editor = editor()?;
+    let mut editor = editor.split_whitespace();
+    let exec = editor.next().unwrap_or_default();
+
     copy(file, temporary_file).prepend_io(|| file.into())?;
-    handle_process(*EDITOR, Command::new(*EDITOR).arg(temporary_file).spawn())?;
+    handle_process(exec, Command::new(exec).args(editor).arg(temporary_file).spawn())?;
 
     if edit && hash_file(file)? != hash_file(temporary_file)? {
         if let Some(lock) = lock {
@@ -145,6 +193,27 @@ fn edit_file(file: &str, temporary_file: &str, lock: Option<&Lock>, edit: bool)
     remove_file(temporary_file).prepend_io(|| temporary_file.into())
 }
 
+fn editor() -> Result<&'static str> {
+    for env in ["VISUAL", "EDITOR"] {
+        if let Ok(editor) = var(env) {
+            if !editor.trim().is_empty() {
+                return Ok(editor.leak());
+            }
+        }
+    }
+
+    if let Some(editor) = global()?.config().editor() {
+        return Ok(editor);
+    }
+
+    let path = var("PATH").unwrap_or(DEFAULT_PATH.into());
+
+    match EDITORS.iter().find(|exec| path.split(':').any(|dir| Path::new(&format!("{dir}/{exec}")).is_file())) {
+        Some(editor) => Ok(editor),
+        None => err!(EditError::EditorNotFound),
+    }
+}
+
 fn hash_file(file_path: &str) -> Result<Vec<u8>> {
     let mut file = File::open(file_path).prepend_io(|| file_path.into())?;
     let mut hasher = Sha256::new();
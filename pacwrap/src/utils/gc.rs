@@ -0,0 +1,149 @@
+/*
+ * pacwrap
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+    fs::{read_dir, remove_dir_all, remove_file, symlink_metadata},
+    path::Path,
+    result::Result as StdResult,
+};
+
+use simplebyteunit::simplebyteunit::*;
+
+use pacwrap_core::{
+    config::{cache::populate_config, ContainerVariables},
+    constants::{ARROW_GREEN, BAR_GREEN, BAR_RED, BOLD, CONTAINER_DIR, DATA_DIR, RESET},
+    lock::Lock,
+    log::{Level::Info, Logger},
+    sync::filesystem::disk_usage,
+    utils::{arguments::Operand, prompt::prompt, Arguments},
+    ErrorGeneric,
+    Result,
+};
+
+struct Orphan {
+    path: String,
+    size: i64,
+    directory: bool,
+}
+
+pub fn collect(args: &mut Arguments) -> Result<()> {
+    let mut dry_run = false;
+    let mut no_confirm = false;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            Operand::Long("gc") | Operand::Value("gc") => continue,
+            Operand::Long("dry-run") => dry_run = true,
+            Operand::Long("noconfirm") => no_confirm = true,
+            _ => args.invalid_operand()?,
+        }
+    }
+
+    let cache = populate_config()?;
+    let registered = cache.registered();
+    let state_dir = format!("{}/state", *DATA_DIR);
+    let mut orphans = Vec::new();
+
+    for name in read_entries(&state_dir)? {
+        let instance = name.strip_suffix(".dat.new").or(name.strip_suffix(".dat"));
+
+        if let Some(false) = instance.map(|a| is_registered(&registered, a)) {
+            let path = format!("{state_dir}/{name}");
+            let size = symlink_metadata(&path).prepend_io(|| path.clone())?.len() as i64;
+
+            orphans.push(Orphan {
+                path,
+                size,
+                directory: false,
+            });
+        }
+    }
+
+    for name in read_entries(&CONTAINER_DIR)? {
+        if is_registered(&registered, &name) {
+            continue;
+        }
+
+        let path = format!("{}{name}", *CONTAINER_DIR);
+        let meta = symlink_metadata(&path).prepend_io(|| path.clone())?;
+        let size = match meta.is_dir() {
+            true => disk_usage(&path).map(|(on_disk, _)| on_disk as i64)?,
+            false => 0,
+        };
+
+        orphans.push(Orphan {
+            path,
+            size,
+            directory: meta.is_dir(),
+        });
+    }
+
+    if orphans.is_empty() {
+        eprintln!("{} Nothing to collect.", *ARROW_GREEN);
+        return Ok(());
+    }
+
+    let reclaimable: i64 = orphans.iter().map(|a| a.size).sum();
+
+    eprintln!("{} {}Orphaned data ({}){}\n", *BAR_RED, *BOLD, orphans.len(), *RESET);
+
+    for orphan in &orphans {
+        eprintln!("{} ({})", orphan.path, orphan.size.to_byteunit(IEC));
+    }
+
+    eprintln!("\n{}Total Reclaimable Size{}: {}", *BOLD, *RESET, reclaimable.to_byteunit(IEC));
+
+    if dry_run || !(no_confirm || prompt("::", format!("{}Delete orphaned data?", *BOLD), false)?) {
+        return Ok(());
+    }
+
+    let mut logger = Logger::new("pacwrap-utils").init()?;
+    let lock = Lock::new().lock()?;
+
+    eprintln!("{} {}Collecting garbage...{}", *BAR_GREEN, *BOLD, *RESET);
+
+    for orphan in &orphans {
+        let path = &orphan.path;
+
+        lock.assert()?;
+
+        match orphan.directory {
+            true => remove_dir_all(path).prepend(|| format!("Failed to delete '{path}'"))?,
+            false => remove_file(path).prepend_io(|| path.into())?,
+        }
+
+        logger.log(Info, &format!("Deleted orphaned {path}"))?;
+    }
+
+    eprintln!("{} Reclaimed {}.", *ARROW_GREEN, reclaimable.to_byteunit(IEC));
+    lock.unlock()
+}
+
+//Symlinked configurations are omitted from the cache, hence the configuration path is consulted as well
+fn is_registered(registered: &[&str], name: &str) -> bool {
+    registered.contains(&name) || Path::new(ContainerVariables::new(name).config_path()).exists()
+}
+
+fn read_entries(dir: &str) -> Result<Vec<String>> {
+    Ok(read_dir(dir)
+        .prepend_io(|| dir.into())?
+        .filter_map(StdResult::ok)
+        .filter_map(|e| e.file_name().to_str().map(|a| a.to_string()))
+        .collect())
+}
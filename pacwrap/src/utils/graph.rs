@@ -0,0 +1,74 @@
+/*
+ * pacwrap
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fmt::Write;
+
+use pacwrap_core::{
+    config::{cache::populate, ContainerType},
+    utils::{arguments::Operand, Arguments},
+    Result,
+};
+
+pub fn graph(args: &mut Arguments) -> Result<()> {
+    while let Some(arg) = args.next() {
+        match arg {
+            Operand::Short('g') | Operand::Long("graph") | Operand::Value("graph") => continue,
+            _ => args.invalid_operand()?,
+        }
+    }
+
+    let cache = populate()?;
+    let mut instances = cache.registered();
+    let mut buffer = String::from("digraph pacwrap {\n    rankdir=BT;\n    node [style=filled];\n");
+
+    instances.sort();
+
+    for ins in &instances {
+        let handle = cache.get_instance(ins)?;
+        let container_type = handle.metadata().container_type();
+        let (shape, color) = match container_type {
+            ContainerType::Base => ("box", "lightcoral"),
+            ContainerType::Slice => ("box", "lightskyblue"),
+            ContainerType::Aggregate => ("box", "palegreen"),
+            ContainerType::Symbolic => ("ellipse", "lightgrey"),
+        };
+
+        writeln!(buffer, "    \"{ins}\" [label=\"{ins}\\n({container_type})\", shape={shape}, fillcolor={color}];").unwrap();
+    }
+
+    for ins in &instances {
+        let handle = cache.get_instance(ins)?;
+        let deps = handle.metadata().dependencies();
+
+        match handle.metadata().container_type() {
+            ContainerType::Symbolic =>
+                if let Some(dep) = deps.last() {
+                    writeln!(buffer, "    \"{ins}\" -> \"{dep}\" [style=dashed];").unwrap();
+                },
+            _ =>
+                for dep in deps {
+                    writeln!(buffer, "    \"{ins}\" -> \"{dep}\";").unwrap();
+                },
+        }
+    }
+
+    buffer.push('}');
+    println!("{buffer}");
+    Ok(())
+}
@@ -19,20 +19,25 @@
 
 use std::{
     collections::HashMap,
-    fs::read_dir,
+    fs::metadata,
     hash::{Hash, Hasher},
-    os::unix::fs::MetadataExt,
+    io::{stdout, IsTerminal},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use indexmap::IndexSet;
+use serde::Serialize;
 use simplebyteunit::simplebyteunit::*;
+use time::{format_description::FormatItem, macros::format_description, OffsetDateTime, UtcOffset};
 
 use pacwrap_core::{
-    config::{cache::populate, ContainerType},
-    constants::{BOLD, CONTAINER_DIR, RESET, UNDERLINE},
+    config::{cache::populate, ContainerHandle, ContainerType},
+    constants::{BOLD, DATA_DIR, IS_COLOR_TERMINAL, RESET, UNDERLINE},
     utils::{
         arguments::Operand,
         table::{ColumnAttribute, Table},
+        text_width,
+        truncate_text,
         whitespace,
         Arguments,
     },
@@ -40,13 +45,22 @@ use pacwrap_core::{
     Result,
 };
 
+use crate::query::explicit_packages;
+
 use Display::*;
 
+const SPACING: usize = 4;
+const DESCRIPTION_MIN: usize = 16;
+const DATE_FORMAT: &[FormatItem<'static>] = format_description!("[year]-[month]-[day] [hour]:[minute]");
+
 #[derive(Eq)]
 enum Display {
     Total(bool),
     Organic(bool),
     Summary(Option<bool>),
+    Packages,
+    Age,
+    Description,
     Name,
     Type,
 }
@@ -59,6 +73,9 @@ impl Hash for Display {
             Self::Summary(_) => 2,
             Self::Total(bytes) => 3 + *bytes as u8,
             Self::Organic(bytes) => 5 + *bytes as u8,
+            Self::Packages => 7,
+            Self::Age => 8,
+            Self::Description => 9,
         })
     }
 }
@@ -72,11 +89,41 @@ impl PartialEq for Display {
                 | (Self::Organic(_), Self::Organic(_))
                 | (Self::Name, Self::Name)
                 | (Self::Type, Self::Type)
+                | (Self::Packages, Self::Packages)
+                | (Self::Age, Self::Age)
+                | (Self::Description, Self::Description)
         )
     }
 }
 
+/// Machine-readable container record, serialized as an element of the array printed in JSON mode.
+#[derive(Serialize)]
+struct ContainerRecord<'a> {
+    name: &'a str,
+    container_type: String,
+    dependencies: Vec<&'a str>,
+    explicit_package_count: Option<usize>,
+    disk_bytes: i64,
+    total_bytes: i64,
+    description: Option<&'a str>,
+    updated: Option<u64>,
+}
+
 impl Display {
+    //Lower priority columns are elided first when the terminal is too narrow to fit the table.
+    fn priority(&self) -> u8 {
+        match self {
+            Self::Name => 0,
+            Self::Type => 1,
+            Self::Organic(_) => 2,
+            Self::Total(_) => 3,
+            Self::Packages => 4,
+            Self::Age => 5,
+            Self::Description => 6,
+            Self::Summary(_) => u8::MAX,
+        }
+    }
+
     fn bytes(&self) -> bool {
         match self {
             Self::Summary(bytes) => bytes.unwrap_or_default(),
@@ -87,8 +134,9 @@ impl Display {
     }
 }
 
-fn parse_arguments(args: &mut Arguments) -> Result<(bool, IndexSet<Display>)> {
+fn parse_arguments(args: &mut Arguments) -> Result<(bool, bool, IndexSet<Display>)> {
     let mut bytes = false;
+    let mut json = false;
     let mut vec = vec![Name, Type];
 
     while let Some(arg) = args.next() {
@@ -97,42 +145,43 @@ fn parse_arguments(args: &mut Arguments) -> Result<(bool, IndexSet<Display>)> {
             Operand::Short('s') | Operand::Long("summary") => vec.push(Summary(Some(bytes))),
             Operand::Short('t') | Operand::Long("total") => vec.push(Total(bytes)),
             Operand::Short('o') | Operand::Long("on-disk") => vec.push(Organic(bytes)),
+            Operand::Short('p') | Operand::Long("packages") => vec.push(Packages),
+            Operand::Short('a') | Operand::Long("age") => vec.push(Age),
+            Operand::Short('d') | Operand::Long("description") => vec.push(Description),
+            Operand::Long("json") => json = true,
             _ => args.invalid_operand()?,
         }
     }
 
-    Ok((vec.len() > 2, IndexSet::from_iter(vec)))
+    let measure_disk = json || vec.iter().any(|a| matches!(a, Summary(_) | Total(_) | Organic(_)));
+
+    Ok((measure_disk, json, IndexSet::from_iter(vec)))
 }
 
 pub fn list_containers(args: &mut Arguments) -> Result<()> {
+    let offset = UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC);
     let handles = populate()?;
     let mut handles = handles.registered_handles();
-    let (measure_disk, table_type) = parse_arguments(args)?;
+    let (measure_disk, json, table_type) = parse_arguments(args)?;
     let containers = &format!("Containers ({})", handles.len());
     let mut container_sizes: HashMap<&str, (i64, i64)> = HashMap::new();
     let mut actual_size = 0;
     let mut total_size = 0;
-    let mut table_header: Vec<&str> = vec![];
-
-    for column in &table_type {
-        match column {
-            Name => table_header.push(containers),
-            Type => table_header.push("Type"),
-            Total(_) => table_header.push("Total"),
-            Organic(_) => table_header.push("Size on Disk"),
-            _ => continue,
-        }
-    }
-
-    let mut table = Table::new().header(&table_header).spacing(4);
-
-    for col in 2 .. if table_type.contains(&Summary(None)) {
-        table_type.len() - 1
-    } else {
-        table_type.len()
-    } {
-        table = table.col_attribute(col, ColumnAttribute::AlignRight);
-    }
+    let columns: Vec<&Display> = table_type.iter().filter(|a| !matches!(a, Summary(_))).collect();
+    let mut rows: Vec<Vec<String>> = vec![columns
+        .iter()
+        .filter_map(|column| match column {
+            Name => Some(containers.as_str()),
+            Type => Some("Type"),
+            Total(_) => Some("Total"),
+            Organic(_) => Some("Size on Disk"),
+            Packages => Some("Explicit"),
+            Age => Some("Updated"),
+            Description => Some("Description"),
+            Summary(_) => None,
+        })
+        .map(|a| a.to_string())
+        .collect()];
 
     //TODO: More advanced sorting options
     handles.sort_by_key(|f| *f.metadata().container_type() == ContainerType::Base);
@@ -142,16 +191,19 @@ pub fn list_containers(args: &mut Arguments) -> Result<()> {
 
     for container in handles.iter() {
         let instance = container.vars().instance();
-        let container_path = &format!("{}/{}", *CONTAINER_DIR, instance);
-        let (len, organic, total) = if measure_disk && container.metadata().container_type() != &ContainerType::Symbolic {
-            directory_size(container_path)?
+        let (organic, total) = if measure_disk && container.metadata().container_type() != &ContainerType::Symbolic {
+            container.disk_usage().map(|(organic, total)| (organic as i64, total as i64))?
         } else {
-            (0, 0, 0)
+            (0, 0)
         };
 
         total_size += total;
-        actual_size += len + organic;
-        container_sizes.insert(instance, (len + organic, total));
+        actual_size += organic;
+        container_sizes.insert(instance, (organic, total));
+    }
+
+    if json {
+        return print_json(&handles, &container_sizes);
     }
 
     for container in handles {
@@ -160,7 +212,7 @@ pub fn list_containers(args: &mut Arguments) -> Result<()> {
         let (organic, total) = container_sizes.get(container_name).unwrap();
         let mut row = vec![];
 
-        for column in &table_type {
+        for column in &columns {
             match column {
                 Name => row.push(container_name.to_string()),
                 Type => row.push(container_type.to_string()),
@@ -172,11 +224,39 @@ pub fn list_containers(args: &mut Arguments) -> Result<()> {
                     false => organic.to_byteunit(SI).to_string(),
                     true => organic.to_string(),
                 }),
-                _ => continue,
+                Packages => row.push(match explicit_packages(container)? {
+                    Some(count) => count.to_string(),
+                    None => "-".into(),
+                }),
+                Age => row.push(match last_updated(container) {
+                    Some(time) => age(time, offset),
+                    None => "never".into(),
+                }),
+                Description => row.push(container.metadata().description().unwrap_or("-").into()),
+                Summary(_) => continue,
             }
         }
 
-        table.insert(row);
+        rows.push(row);
+    }
+
+    let mut table = Table::new().spacing(SPACING);
+
+    truncate_descriptions(&columns, &mut rows, table.width());
+
+    let retained = retained_columns(&columns, &rows, table.width());
+    let header: Vec<&str> = retained.iter().map(|col| rows[0][*col].as_str()).collect();
+
+    table = table.header(&header);
+
+    for (idx, col) in retained.iter().enumerate() {
+        if !matches!(columns[*col], Name | Type | Description) {
+            table = table.col_attribute(idx, ColumnAttribute::AlignRight);
+        }
+    }
+
+    for row in rows.iter().skip(1) {
+        table.insert(retained.iter().map(|col| row[*col].clone()).collect());
     }
 
     if let Some(sum) = table_type.get(&Display::Summary(None)) {
@@ -217,34 +297,94 @@ pub fn list_containers(args: &mut Arguments) -> Result<()> {
     Ok(())
 }
 
-//There might be some value in threading this routine in future.
-fn directory_size(dir: &str) -> Result<(i64, i64, i64)> {
-    let mut len = 0;
-    let mut total = 0;
-    let mut unique = 0;
-
-    for entry in read_dir(dir).prepend_io(|| dir.into())? {
-        let entry = entry.prepend(|| format!("Failure acquiring entry in '{dir}'"))?;
-        let name = entry.file_name().to_str().unwrap().to_string();
-        let meta = entry.metadata().prepend(|| format!("Failure to acquire metadata in '{dir}/{name}'"))?;
-
-        if entry
-            .file_type()
-            .prepend(|| format!("Failure to acquire filetype '{dir}/{name}'"))?
-            .is_dir()
-        {
-            let (l, u, t) = directory_size(&format!("{dir}/{name}"))?;
-
-            len += l;
-            unique += u;
-            total += t;
-        } else if meta.nlink() == 1 {
-            unique += meta.len() as i64;
-        } else {
-            len += (meta.len() / meta.nlink()) as i64;
-            total += meta.len() as i64;
+fn print_json(handles: &[&ContainerHandle], container_sizes: &HashMap<&str, (i64, i64)>) -> Result<()> {
+    let mut records = Vec::new();
+
+    for container in handles {
+        let instance = container.vars().instance();
+        let (organic, total) = container_sizes.get(instance).copied().unwrap_or_default();
+
+        records.push(ContainerRecord {
+            name: instance,
+            container_type: container.metadata().container_type().to_string(),
+            dependencies: container.metadata().dependencies(),
+            explicit_package_count: explicit_packages(container)?,
+            disk_bytes: organic,
+            total_bytes: total,
+            description: container.metadata().description(),
+            updated: last_updated(container)
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|time| time.as_secs()),
+        });
+    }
+
+    println!("{}", serde_json::to_string(&records).prepend(|| "Failure serializing container list".into())?);
+    Ok(())
+}
+
+fn retained_columns(columns: &[&Display], rows: &[Vec<String>], width: usize) -> Vec<usize> {
+    let mut retained: Vec<usize> = (0 .. columns.len()).collect();
+
+    if !stdout().is_terminal() {
+        return retained;
+    }
+
+    let widths: Vec<usize> = (0 .. columns.len())
+        .map(|col| rows.iter().map(|row| text_width(&row[col])).max().unwrap_or_default())
+        .collect();
+
+    while retained.len() > 1 && retained.iter().map(|col| widths[*col] + SPACING).sum::<usize>() - SPACING > width {
+        let lowest = retained.iter().enumerate().max_by_key(|(_, col)| columns[**col].priority()).map(|(idx, _)| idx);
+
+        retained.remove(lowest.unwrap_or_default());
+    }
+
+    retained
+}
+
+//Descriptions are truncated to the remaining width, unless too little remains, whereupon the column is elided.
+fn truncate_descriptions(columns: &[&Display], rows: &mut [Vec<String>], width: usize) {
+    let column = match columns.iter().position(|a| matches!(a, Description)) {
+        Some(column) => column,
+        None => return,
+    };
+
+    if !stdout().is_terminal() {
+        return;
+    }
+
+    let occupied: usize = (0 .. columns.len())
+        .filter(|col| *col != column)
+        .map(|col| rows.iter().map(|row| text_width(&row[col])).max().unwrap_or_default() + SPACING)
+        .sum();
+    let available = width.saturating_sub(occupied);
+
+    if available < DESCRIPTION_MIN {
+        return;
+    }
+
+    for row in rows.iter_mut().skip(1) {
+        if text_width(&row[column]) > available {
+            row[column] = truncate_text(&row[column], available);
         }
     }
+}
+
+fn last_updated(handle: &ContainerHandle) -> Option<SystemTime> {
+    metadata(format!("{}/state/{}.dat", *DATA_DIR, handle.vars().instance())).and_then(|a| a.modified()).ok()
+}
+
+fn age(time: SystemTime, offset: UtcOffset) -> String {
+    if !*IS_COLOR_TERMINAL {
+        return OffsetDateTime::from(time).to_offset(offset).format(DATE_FORMAT).unwrap_or_default();
+    }
 
-    Ok((len, unique, total))
+    let secs = SystemTime::now().duration_since(time).unwrap_or(Duration::ZERO).as_secs();
+
+    match secs {
+        0 ..= 59 => format!("{secs}s ago"),
+        60 ..= 3599 => format!("{}m ago", secs / 60),
+        3600 ..= 86399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
 }
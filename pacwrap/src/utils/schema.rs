@@ -0,0 +1,100 @@
+/*
+ * pacwrap
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use pacwrap_core::{
+    config::{cache::populate, ContainerType},
+    constants::{ARROW_GREEN, BAR_GREEN, BOLD, RESET},
+    err,
+    impl_error,
+    lock::Lock,
+    log::{Level::Info, Logger},
+    sync::schema::{self, SchemaStatus},
+    utils::{
+        arguments::{InvalidArgument, Operand},
+        Arguments,
+    },
+    Error,
+    ErrorTrait,
+    Result,
+};
+
+#[derive(Debug)]
+enum SchemaError {
+    NotBase(String),
+}
+
+impl_error!(SchemaError);
+
+impl Display for SchemaError {
+    fn fmt(&self, fmter: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::NotBase(ins) => write!(fmter, "Container {}{ins}{} is not a base container.", *BOLD, *RESET),
+        }
+    }
+}
+
+pub fn apply_schema(args: &mut Arguments) -> Result<()> {
+    let mut targets = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg {
+            Operand::Long("apply-schema") | Operand::Value("apply-schema") => continue,
+            Operand::ShortPos('t', val) | Operand::LongPos("target", val) | Operand::Value(val) => targets.push(val),
+            Operand::Short('t') | Operand::Long("target") => continue,
+            _ => args.invalid_operand()?,
+        }
+    }
+
+    if targets.is_empty() {
+        err!(InvalidArgument::TargetUnspecified)?
+    }
+
+    let cache = populate()?;
+
+    for target in &targets {
+        if let ContainerType::Base = cache.get_instance(target)?.metadata().container_type() {
+            continue;
+        }
+
+        err!(SchemaError::NotBase(target.to_string()))?
+    }
+
+    let mut logger = Logger::new("pacwrap-utils").init()?;
+    let lock = Lock::new().lock()?;
+
+    println!("{} {}Applying container schema {}...{}", *BAR_GREEN, *BOLD, schema::current_version(), *RESET);
+
+    for target in targets {
+        let handle = cache.get_instance(target)?;
+        let previous = match schema::version(handle)? {
+            SchemaStatus::OutOfDate(Some(state)) => state.version(),
+            SchemaStatus::OutOfDate(None) => "none".into(),
+            SchemaStatus::UpToDate => schema::current_version(),
+        };
+
+        lock.assert()?;
+        schema::apply(handle)?;
+        println!("{} {}{target}{}'s schema applied (previously {previous}).", *ARROW_GREEN, *BOLD, *RESET);
+        logger.log(Info, &format!("Container {target}'s filesystem schema applied: {previous} -> {}", schema::current_version()))?;
+    }
+
+    lock.unlock()
+}
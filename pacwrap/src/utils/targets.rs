@@ -0,0 +1,80 @@
+/*
+ * pacwrap
+ *
+ * Copyright (C) 2023-2024 Xavier Moffett <sapphirus@azorium.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs::read_to_string,
+    io::{read_to_string as read_stream, stdin},
+};
+
+use pacwrap_core::{
+    constants::{BOLD, RESET},
+    err,
+    impl_error,
+    Error,
+    ErrorGeneric,
+    ErrorTrait,
+    Result,
+};
+
+#[derive(Debug)]
+enum TargetFileError {
+    MalformedLine(String, usize),
+}
+
+impl_error!(TargetFileError);
+
+impl Display for TargetFileError {
+    fn fmt(&self, fmter: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MalformedLine(path, line) => write!(
+                fmter,
+                "'{path}': Line {}{line}{} is malformed: Expected 'container: package ...'.",
+                *BOLD,
+                *RESET
+            ),
+        }
+    }
+}
+
+/// Read transaction targets from the specified file, or from stdin where `-` is specified, with each
+/// line comprised of a container followed by its packages: `container: package package ...`
+pub fn read_targets(path: &str) -> Result<Vec<(&'static str, Vec<&'static str>)>> {
+    let contents = match path {
+        "-" => read_stream(stdin()).prepend(|| "Failed to read targets from stdin".into())?,
+        _ => read_to_string(path).prepend_io(|| path.into())?,
+    };
+    let mut targets = Vec::new();
+
+    for (line, contents) in contents.leak().lines().enumerate() {
+        let contents = contents.split('#').next().unwrap_or_default().trim();
+
+        if contents.is_empty() {
+            continue;
+        }
+
+        match contents.split_once(':') {
+            Some((container, packages)) if !container.trim().is_empty() =>
+                targets.push((container.trim(), packages.split_whitespace().collect())),
+            _ => err!(TargetFileError::MalformedLine(path.into(), line + 1))?,
+        }
+    }
+
+    Ok(targets)
+}